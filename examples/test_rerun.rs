@@ -6,25 +6,25 @@ use std::time::Duration;
 
 // Import from the main project
 use project::backend::csi_data::CsiData;
-use project::rerun_stream::RerunStreamer;
+use project::rerun_stream::{CsiFrame, RerunStreamer};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 Rerun Integration Test");
     println!("========================\n");
 
     // Create streamer
-    let mut streamer = RerunStreamer::new();
+    let mut streamer = RerunStreamer::new("esp-csi-tui-test");
 
     // Test 1: Start live streaming
     println!("📡 Starting live stream...");
     println!("   (Make sure 'rerun' viewer is running!)");
-    
-    match streamer.start_stream() {
-        Ok(_) => println!("   ✅ Live stream connected successfully"),
-        Err(e) => {
-            println!("   ⚠️  Live stream failed: {}", e);
-            println!("   💡 Start Rerun viewer with: rerun");
-        }
+
+    streamer.connect("127.0.0.1:9876");
+    if streamer.is_connected() {
+        println!("   ✅ Live stream connected successfully");
+    } else {
+        println!("   ⚠️  Live stream failed");
+        println!("   💡 Start Rerun viewer with: rerun");
     }
 
     // Test 2: Start RRD recording
@@ -40,7 +40,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     for i in 0..20 {
         let mock_csi = generate_mock_csi_packet(i);
         
-        streamer.log_csi(&mock_csi);
+        streamer.push_csi(&CsiFrame::from(&mock_csi));
         
         if i % 5 == 0 {
             println!("   📦 Logged packet {} - RSSI: {}, SNR: {}", 
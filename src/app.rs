@@ -1,28 +1,61 @@
 // --- File: src/app.rs ---
 // --- Purpose: Holds the central Application State and Logic ---
 
+use std::io::Write;
 use std::time::{Duration, Instant};
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
 
 use crate::dataloader::Dataloader;
 use crate::config_manager;
-use crate::frontend::layout_tree::TilingManager;
-use crate::frontend::theme::{Theme, ThemeType};
+use crate::keybindings::{self, KeyBindings};
+use crate::frontend::layout_tree::{TilingManager, ViewType};
+use crate::frontend::theme::{CanvasMarker, PhaseColormap, StatsFormat, Theme, ThemeType};
 use crate::frontend::view_state::ViewState;
-use crate::backend::csi_data::CsiData;
+use crate::backend::csi_data::{AveragingMode, CsiData, OUTLIER_SIGMAS};
 use crate::rerun_stream::SharedRerunStreamer;
 
 // We store fewer packets because we are storing averages now.
 // 10,000 averages @ 10Hz = 1000 seconds (~16 minutes) of history.
 pub const MAX_HISTORY_SIZE: usize = 10000;
 
+// Coarse history: one averaged snapshot per second of the fine history.
+// 3,600 points = one hour of trend, far beyond what the fine history holds.
+pub const COARSE_INTERVAL_MS: u64 = 1000;
+pub const MAX_COARSE_HISTORY_SIZE: usize = 3600;
+
 // Configurable update rate.
 // 0.5s = 500ms (Very slow, but good for long term stats)
 // 0.1s = 100ms (Recommended for "Real-time" feel)
 pub const UPDATE_INTERVAL: Duration = Duration::from_millis(100);
 
+// Local Rerun viewer (default gRPC port)
+pub const DEFAULT_RERUN_ADDR: &str = "127.0.0.1:9876";
+
+// Snapshots averaged into a calibration reference (3 s at UPDATE_INTERVAL)
+pub const REFERENCE_SNAPSHOTS: usize = 30;
+
+// Global amplitude gain limits; each key press doubles or halves it
+pub const MIN_AMPLITUDE_GAIN: f32 = 0.25;
+pub const MAX_AMPLITUDE_GAIN: f32 = 64.0;
+
+// How long a toast (App::notify) stays on screen
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+// Snapshots in the motion energy window (2 s at UPDATE_INTERVAL)
+pub const MOTION_WINDOW: usize = 20;
+
+// Minimum gap between two "input is lagging" toasts, so a long flood doesn't hide other notices
+pub const INPUT_LAG_NOTICE_INTERVAL: Duration = Duration::from_secs(10);
+
+// Pane title tag of the primary source while an overlay source runs
+pub const PRIMARY_SOURCE_LABEL: &str = "MAIN";
+
+// Cached splitter hitbox: (tree path, rect, direction, ratio, container size)
+pub type SplitterRegion = (Vec<usize>, Rect, crate::frontend::layout_tree::SplitDirection, u16, u16);
+
 #[derive(Clone, Debug)]
 pub struct NetworkStats {
     pub id: u64, // Unique sequence ID for the UI
@@ -31,11 +64,301 @@ pub struct NetworkStats {
     pub snr: i32,
     pub timestamp: u64,
     pub csi: Option<CsiData>,
+    // Phase coherence with the previous snapshot (0.0..=1.0), shown as "stability %"
+    pub coherence: f32,
     // Cumulative I/Q Distribution Grid (24x24)
     // Stores the frequency count of (I, Q) pairs accumulated over time.
     pub distribution_grid: [[f32; 24]; 24],
 }
 
+impl NetworkStats {
+    /// Placeholder snapshot used before any packet has arrived
+    pub fn empty() -> Self {
+        Self {
+            id: 0,
+            rssi: -90,
+            pps: 0,
+            snr: 0,
+            timestamp: 0,
+            csi: None,
+            coherence: 0.0,
+            distribution_grid: [[0.0; 24]; 24],
+        }
+    }
+
+    /// One snapshot standing for a run of consecutive ones: CSI, RSSI, SNR, PPS and coherence
+    /// are averaged; id, timestamp and the (cumulative) distribution grid come from the newest
+    pub fn average(snapshots: &[NetworkStats]) -> Self {
+        let Some(last) = snapshots.last() else { return Self::empty() };
+        let n = snapshots.len();
+        let csis: Vec<CsiData> = snapshots.iter().filter_map(|s| s.csi.clone()).collect();
+        Self {
+            id: last.id,
+            rssi: (snapshots.iter().map(|s| s.rssi as i64).sum::<i64>() / n as i64) as i32,
+            pps: snapshots.iter().map(|s| s.pps).sum::<u64>() / n as u64,
+            snr: (snapshots.iter().map(|s| s.snr as i64).sum::<i64>() / n as i64) as i32,
+            timestamp: last.timestamp,
+            csi: if csis.is_empty() { None } else { Some(CsiData::average(&csis)) },
+            coherence: snapshots.iter().map(|s| s.coherence).sum::<f32>() / n as f32,
+            distribution_grid: last.distribution_grid,
+        }
+    }
+}
+
+/// A second packet stream drawn next to the primary source (--overlay <csv>, e.g. an empty-room
+/// capture replayed beside live serial for A/B comparison). It has its own queue and history,
+/// filled by esp_com::run_overlay and App::on_tick; panes opt in with ViewState::overlay_source.
+pub struct SourceStream {
+    pub label: String, // Shown in the title of every pane while an overlay runs
+    pub dataloader: Dataloader,
+    pub history: Vec<NetworkStats>,
+    pub current_stats: NetworkStats,
+    pub pps_window: Vec<usize>,
+}
+
+// Ticks averaged for the overlay's PPS (the primary source uses App::pps_smoothing)
+const OVERLAY_PPS_WINDOW: usize = 10;
+
+impl SourceStream {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            dataloader: Dataloader::new(),
+            history: Vec::new(),
+            current_stats: NetworkStats::empty(),
+            pps_window: Vec::new(),
+        }
+    }
+
+    /// Clears the history when the overlay file starts over. IDs stay monotonic.
+    pub fn restart(&mut self) {
+        let last_id = self.current_stats.id;
+        self.current_stats = NetworkStats::empty();
+        self.current_stats.id = last_id;
+        self.history.clear();
+        self.pps_window.clear();
+        self.dataloader.queue.clear();
+    }
+}
+
+/// The packets one pane draws from (App::source_for): the primary source or the overlay
+#[derive(Clone, Copy)]
+pub struct SourceView<'a> {
+    pub history: &'a [NetworkStats],
+    pub current_stats: &'a NetworkStats,
+    pub overlay_label: Option<&'a str>, // None = primary source
+}
+
+impl<'a> SourceView<'a> {
+    /// Snapshots from `depth` steps before `end_index` up to and including it, clamped like
+    /// App::history_window. Empty when there is no history.
+    pub fn history_window(&self, end_index: usize, depth: usize) -> &'a [NetworkStats] {
+        if self.history.is_empty() {
+            return &[];
+        }
+        let end = end_index.min(self.history.len() - 1);
+        let start = end.saturating_sub(depth);
+        &self.history[start..=end]
+    }
+
+    pub fn index_of(&self, id: u64) -> Option<usize> {
+        self.history.iter().position(|p| p.id == id)
+    }
+
+    pub fn live_id(&self) -> u64 {
+        self.current_stats.id
+    }
+
+    pub fn min_id(&self) -> u64 {
+        self.history.first().map_or(0, |p| p.id)
+    }
+}
+
+/// Scales for the Dashboard gauges. Adjustable from Main Menu -> "Gauge Ranges".
+#[derive(Clone, Copy, Debug)]
+pub struct GaugeRanges {
+    pub pps_max: f64,
+    pub snr_max: f64,
+    pub rssi_min: i32,
+    pub rssi_max: i32,
+    pub snr_floor: i32, // SNR below this turns the gauge red
+    pub rssi_strong: i32, // Pane borders: green at or above this RSSI
+    pub rssi_weak: i32,   // Pane borders: red below this RSSI, yellow in between
+    pub tint_all_borders: bool, // Tint every data pane, not just the Dashboard
+    pub signal_timeout_secs: f32, // No packet for this long = "SIGNAL LOST"
+    pub dim_on_signal_loss: bool, // Also dim every pane while the signal is lost
+    pub motion_max: f32, // Full scale of the Dashboard motion gauge
+    pub motion_threshold: f32, // Motion energy at or above this flags "MOTION" in the header
+}
+
+impl Default for GaugeRanges {
+    fn default() -> Self {
+        Self { pps_max: 1000.0, snr_max: 60.0, rssi_min: -100, rssi_max: 0, snr_floor: 10, rssi_strong: -60, rssi_weak: -75, tint_all_borders: false, signal_timeout_secs: 2.0, dim_on_signal_loss: true, motion_max: 10.0, motion_threshold: 2.0 }
+    }
+}
+
+impl GaugeRanges {
+    pub fn pps_percent(&self, pps: u64) -> u16 { range_percent(pps as f64, 0.0, self.pps_max) }
+    pub fn snr_percent(&self, snr: i32) -> u16 { range_percent(snr as f64, 0.0, self.snr_max) }
+    pub fn rssi_percent(&self, rssi: i32) -> u16 { range_percent(rssi as f64, self.rssi_min as f64, self.rssi_max as f64) }
+    pub fn motion_percent(&self, energy: f32) -> u16 { range_percent(energy as f64, 0.0, self.motion_max as f64) }
+
+    /// Steps one setting (index into GAUGE_SETTINGS) up or down, keeping every range non-empty
+    pub fn adjust(&mut self, index: usize, increase: bool) {
+        let sign = if increase { 1 } else { -1 };
+        match index {
+            0 => self.pps_max = (self.pps_max + 100.0 * sign as f64).clamp(100.0, 100_000.0),
+            1 => self.snr_max = (self.snr_max + 5.0 * sign as f64).clamp(5.0, 120.0),
+            2 => self.rssi_min = (self.rssi_min + 5 * sign).clamp(-127, self.rssi_max - 5),
+            3 => self.rssi_max = (self.rssi_max + 5 * sign).clamp(self.rssi_min + 5, 20),
+            4 => self.snr_floor = (self.snr_floor + sign).clamp(0, 120),
+            5 => self.rssi_strong = (self.rssi_strong + 5 * sign).clamp(self.rssi_weak + 5, 20),
+            6 => self.rssi_weak = (self.rssi_weak + 5 * sign).clamp(-127, self.rssi_strong - 5),
+            7 => self.tint_all_borders = !self.tint_all_borders,
+            8 => self.signal_timeout_secs = (self.signal_timeout_secs + 0.5 * sign as f32).clamp(0.5, 30.0),
+            9 => self.dim_on_signal_loss = !self.dim_on_signal_loss,
+            10 => self.motion_max = (self.motion_max + 1.0 * sign as f32).clamp(self.motion_threshold.max(1.0), 200.0),
+            11 => self.motion_threshold = (self.motion_threshold + 0.5 * sign as f32).clamp(0.5, self.motion_max),
+            _ => {}
+        }
+    }
+}
+
+/// How the PPS readout is smoothed (Main Menu -> PPS Smoothing). Packets are counted once per
+/// UPDATE_INTERVAL, so the readout jitters with every tick unless it is averaged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PpsSmoothing {
+    Window(usize), // Mean over the last N ticks (1 = instantaneous)
+    Ema,           // Exponential moving average (PPS_EMA_ALPHA per tick)
+}
+
+impl Default for PpsSmoothing {
+    fn default() -> Self { PpsSmoothing::Window(10) }
+}
+
+// Weight of the newest tick in PpsSmoothing::Ema (~2 s to settle at 100 ms ticks)
+pub const PPS_EMA_ALPHA: f64 = 0.1;
+
+impl PpsSmoothing {
+    pub fn label(&self) -> String {
+        match self {
+            PpsSmoothing::Window(1) => "Instant".to_string(),
+            PpsSmoothing::Window(n) => format!("{:.1} s window", *n as f64 * UPDATE_INTERVAL.as_secs_f64()),
+            PpsSmoothing::Ema => "EMA".to_string(),
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            PpsSmoothing::Window(1) => PpsSmoothing::Window(10),
+            PpsSmoothing::Window(10) => PpsSmoothing::Window(30),
+            PpsSmoothing::Window(_) => PpsSmoothing::Ema,
+            PpsSmoothing::Ema => PpsSmoothing::Window(1),
+        }
+    }
+}
+
+/// Metric shown as a colored badge at the start of the header (Main Menu -> Header Badge).
+/// Colors follow the Gauge Ranges thresholds: green is fine, yellow marginal, red bad.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum HeaderMetric {
+    #[default]
+    Off,
+    Rssi,
+    Snr,
+    Pps,
+    Motion,
+}
+
+impl HeaderMetric {
+    pub fn as_str(&self) -> &str {
+        match self {
+            HeaderMetric::Off => "Off",
+            HeaderMetric::Rssi => "RSSI",
+            HeaderMetric::Snr => "SNR",
+            HeaderMetric::Pps => "PPS",
+            HeaderMetric::Motion => "Motion",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            HeaderMetric::Off => HeaderMetric::Rssi,
+            HeaderMetric::Rssi => HeaderMetric::Snr,
+            HeaderMetric::Snr => HeaderMetric::Pps,
+            HeaderMetric::Pps => HeaderMetric::Motion,
+            HeaderMetric::Motion => HeaderMetric::Off,
+        }
+    }
+}
+
+// Mean packets per second over per-tick counts (0 for no ticks)
+fn window_pps(window: &[usize]) -> u64 {
+    if window.is_empty() {
+        return 0;
+    }
+    let total_packets: usize = window.iter().sum();
+    (total_packets as f64 / (window.len() as f64 * UPDATE_INTERVAL.as_secs_f64())) as u64
+}
+
+/// Adds every (I, Q) pair of a packet to a 24x24 I/Q histogram covering -128..128, with the
+/// given weight per pair. Pairs outside the grid are dropped.
+pub fn accumulate_grid(grid: &mut [[f32; 24]; 24], csi: &CsiData, weight: f32) {
+    const GRID_SIZE: usize = 24;
+    const MIN_VAL: f64 = -128.0;
+    const MAX_VAL: f64 = 128.0;
+    const BIN_WIDTH: f64 = (MAX_VAL - MIN_VAL) / GRID_SIZE as f64;
+
+    let sc_count = csi.csi_raw_data.len() / 2;
+    for s in 0..sc_count {
+        let i_val = csi.csi_raw_data.get(s * 2).copied().unwrap_or(0) as f64;
+        let q_val = csi.csi_raw_data.get(s * 2 + 1).copied().unwrap_or(0) as f64;
+
+        let bx = ((i_val - MIN_VAL) / BIN_WIDTH).floor() as usize;
+        let by = ((q_val - MIN_VAL) / BIN_WIDTH).floor() as usize;
+
+        if bx < GRID_SIZE && by < GRID_SIZE {
+            grid[bx][by] += weight;
+        }
+    }
+}
+
+/// The snapshot following `prev` for one tick's averaged packet: SNR, coherence with `prev`
+/// and the cumulative I/Q distribution grid
+fn next_snapshot(prev: &NetworkStats, averaged_csi: &CsiData, pps: u64, timestamp: u64) -> NetworkStats {
+    let noise = averaged_csi.noise_floor;
+    let snr = averaged_csi.rssi - noise;
+
+    // --- Calculate Distribution Grid (Cumulative) ---
+    let mut grid = prev.distribution_grid; // Copy previous state (Cumulative)
+    accumulate_grid(&mut grid, averaged_csi, 1.0);
+
+    // Channel stability vs. the previous snapshot
+    let coherence = prev.csi.as_ref()
+        .map(|prev| averaged_csi.coherence_with(prev))
+        .unwrap_or(0.0);
+
+    NetworkStats {
+        id: prev.id + 1,
+        rssi: averaged_csi.rssi,
+        pps,
+        snr,
+        timestamp,
+        csi: Some(averaged_csi.clone()),
+        coherence,
+        distribution_grid: grid,
+    }
+}
+
+// Maps value in [min, max] to 0-100. A zero-width (or inverted) range reads as empty.
+fn range_percent(value: f64, min: f64, max: f64) -> u16 {
+    let span = max - min;
+    if span <= 0.0 {
+        return 0;
+    }
+    ((value - min) / span * 100.0).clamp(0.0, 100.0) as u16
+}
+
 pub struct App {
     pub tiling: TilingManager,
     pub theme: Theme,
@@ -45,40 +368,116 @@ pub struct App {
     pub show_quit_popup: bool,
     pub show_view_selector: bool,
     pub view_selector_index: usize,
+    pub view_selector_marks: Vec<usize>, // Panes the chosen view goes to (empty = focused pane)
     pub show_main_menu: bool,
     pub main_menu_index: usize,
     pub show_theme_selector: bool,
     pub theme_selector_index: usize,
+    pub show_gauge_settings: bool,
+    pub gauge_settings_index: usize,
+    pub show_packet_diff: bool, // Fullscreen only: anchored vs live packet comparison
+    pub show_inspector: bool,
+    pub inspector_index: usize, // Subcarrier (buffer order) shown by the inspector
+    pub inspector_input: String, // Digits typed in the inspector, applied with Enter
     pub show_save_input: bool,
+    pub show_overwrite_confirm: bool,
     pub input_buffer: String,
+    pub save_input_error: Option<String>,
     pub show_export_input: bool,
     pub export_input_buffer: String,
+    pub export_tidy: bool, // Export prompt format: false = raw I/Q rows, true = one row per subcarrier
     pub show_load_selector: bool,
     pub load_selector_index: usize,
     pub available_templates: Vec<(String, bool)>,
+    pub template_filter: String, // Typed in the load selector; narrows available_templates
+    pub confirm_delete_template: Option<String>, // Filename awaiting delete confirmation
 
     pub fullscreen_pane_id: Option<usize>,
+    pub fullscreen_restore: Option<FullscreenRestore>, // Put back by exit_fullscreen
     pub pane_states: HashMap<usize, ViewState>,
     pub should_quit: bool,
+    pub show_onboarding: bool, // First-run guidance until dismissed or data arrives (see onboarding_visible)
     pub should_reset_esp: bool,
 
     // Data State
     pub current_stats: NetworkStats,
     pub history: Vec<NetworkStats>,
+    pub history_coarse: VecDeque<NetworkStats>, // Per-second averages of history, for long-horizon views
+
+    pub averaging_mode: AveragingMode,
+    // Drop packets this many sigma off the interval's median before averaging (None = off)
+    pub outlier_sigma: Option<f32>,
+    pub gauge_ranges: GaugeRanges,
 
     // Timing State
     pub start_time: Instant,
     pub last_update_time: Instant,
-    pub pps_window: Vec<usize>,
+    pub last_packet_time: Instant, // When on_tick last drained at least one packet
+    pub motion_energy: f32, // Amplitude spread over the last MOTION_WINDOW snapshots (see csi_data::motion_energy)
+    pub pps_window: Vec<usize>, // Packets drained per tick, newest last
+    pub pps_ema: Option<f64>, // Kept up to date in every mode so switching to Ema doesn't start from 0
+    pub pps_smoothing: PpsSmoothing,
+    pub header_metric: HeaderMetric, // Badge at the start of the header (Off = none)
+    pub overlay: Option<SourceStream>, // Second source for A/B comparison (--overlay <csv>)
 
     // Interaction Caches & Backend
     pub pane_regions: RefCell<Vec<(usize, Rect)>>,
     pub dataloader: Dataloader,
-    pub splitter_regions: RefCell<Vec<(Vec<usize>, Rect, crate::frontend::layout_tree::SplitDirection, u16, u16)>>,
+    pub splitter_regions: RefCell<Vec<SplitterRegion>>,
     pub drag_state: Option<crate::app::DragState>, // Re-using DragState struct definition or define here if moved
 
     // Rerun Integration
     pub rerun_streamer: Option<SharedRerunStreamer>,
+    pub rerun_addr: String, // Last address used for live streaming (prefills the connect prompt)
+    pub rerun_record_path: Option<String>, // RRD file being written (Shift+L)
+    pub show_rerun_input: bool,
+    pub rerun_input_buffer: String,
+    pub show_rename_input: bool,
+    pub rename_input_buffer: String,
+
+    // Raw serial passthrough (--raw-log <path>): every line read from the ESP is appended here
+    pub raw_log_path: Option<String>,
+    pub serial_status: Option<String>, // Why the serial source isn't reading (no device / can't open); None once a port is open
+    pub serial_reconnecting: bool, // The open port failed mid-capture (e.g. unplugged) and esp_com is waiting for it to return
+    pub source_stopped: bool, // Set by the data source thread once it has flushed and exited (see esp_com::run_source)
+
+    // File Replay (set when started with a CSV; esp_com::run_replay feeds the dataloader)
+    pub replay_file: Option<String>,
+    pub replay_speed: f32, // Playback multiplier, 0.25x - 8x
+    pub replay_loop: bool, // Restart at end of file (false = stop after one pass)
+    pub replay_position: (usize, usize), // (current packet index, total packets), written by run_replay
+    pub replay_seek: Option<usize>, // Pending seek target, consumed by run_replay
+
+    // Snapshot Export
+    pub screenshot_requested: bool, // Served by the main loop right after the next draw
+    pub toast: Option<(String, Instant)>, // Transient notice above the footer, see App::notify
+
+    // Input
+    pub keybindings: KeyBindings, // Loaded from keybindings.json at startup
+    pub input_drain: (usize, Duration), // Events handled and time spent in the last frame's drain
+    pub last_input_lag_notice: Option<Instant>,
+
+    // Calibration
+    pub reference: Option<CsiData>, // Baseline channel (e.g. empty room), subtracted by panes that opt in
+    pub amplitude_gain: f32, // Applied to I/Q in every amplitude view; 1.0 = auto-scaled as usual
+    pub amplitude_clamp: crate::frontend::smoothing::AmplitudeClamp, // Percentile ceiling for auto-scaled views (ignores outliers)
+    pub flatten_subcarriers: bool, // Amplitude views divide each subcarrier by its running mean
+    pub subcarrier_mean: crate::frontend::smoothing::SubcarrierMean, // Running mean amplitude per subcarrier (primary source)
+
+    // Rendering
+    pub canvas_marker: CanvasMarker, // Glyphs used by every Canvas view (saved with templates)
+    pub stats_format: StatsFormat, // Timestamp unit and decimals (saved with templates)
+    pub phase_colormap: PhaseColormap, // Phase colors in the TUI and the Rerun export (saved with templates)
+
+    // Layout
+    pub default_view: ViewType, // Shown by panes opened with the split keys (--default-view, saved with templates)
+}
+
+/// What was on screen before a pane went fullscreen
+#[derive(Clone)]
+pub struct FullscreenRestore {
+    pub focused_pane_id: usize,
+    pub pane_state: Option<ViewState>, // Maximize only: camera, zoom, replay position, depth... of the pane
 }
 
 // State for resizing operation
@@ -90,18 +489,36 @@ pub struct DragState {
     pub container_size: u16,
 }
 
+/// Axis label for a time offset: "-350ms", "-4.2s", "-2m05s"
+pub fn format_seconds_ago(secs: f64) -> String {
+    if secs < 1.0 {
+        format!("-{:.0}ms", secs * 1000.0)
+    } else if secs < 60.0 {
+        format!("-{:.1}s", secs)
+    } else {
+        let total = secs.round() as u64;
+        format!("-{}m{:02}s", total / 60, total % 60)
+    }
+}
+
 impl App {
     pub fn new(rerun_addr: Option<String>, csv_file: Option<String>) -> Self {
+        // A template's saved theme wins; otherwise match the terminal background
         let (tiling, theme) = if let Some(tm) = config_manager::load_startup_template() {
             let loaded_theme = if let Some(variant) = tm.theme_variant {
                 Theme::new(variant)
             } else {
-                Theme::new(ThemeType::Dark)
+                Theme::new(ThemeType::detect_terminal_default())
             };
             (tm, loaded_theme)
         } else {
-            (TilingManager::new(), Theme::new(ThemeType::Dark))
+            (TilingManager::new(), Theme::new(ThemeType::detect_terminal_default()))
         };
+        let pane_states = tiling.pane_states.clone();
+        let canvas_marker = tiling.canvas_marker.unwrap_or_default();
+        let stats_format = tiling.stats_format.unwrap_or_default();
+        let phase_colormap = tiling.phase_colormap.unwrap_or_default();
+        let default_view = tiling.default_view.unwrap_or(ViewType::Empty);
 
         let mut app = Self {
             tiling,
@@ -110,113 +527,728 @@ impl App {
             show_quit_popup: false,
             show_view_selector: false,
             view_selector_index: 0,
+            view_selector_marks: Vec::new(),
             show_main_menu: false,
             main_menu_index: 0,
             show_theme_selector: false,
             theme_selector_index: 0,
+            show_gauge_settings: false,
+            gauge_settings_index: 0,
+            show_packet_diff: false,
+            show_inspector: false,
+            inspector_index: 0,
+            inspector_input: String::new(),
             show_save_input: false,
+            show_overwrite_confirm: false,
             input_buffer: String::new(),
+            save_input_error: None,
             show_export_input: false,
             export_input_buffer: String::new(),
+            export_tidy: false,
             show_load_selector: false,
             load_selector_index: 0,
             available_templates: Vec::new(),
+            template_filter: String::new(),
+            confirm_delete_template: None,
             fullscreen_pane_id: None,
-            pane_states: HashMap::new(),
+            fullscreen_restore: None,
+            pane_states,
             should_quit: false,
+            show_onboarding: true,
             should_reset_esp: false,
 
             dataloader: Dataloader::new(),
-            current_stats: NetworkStats {
-                id: 0,
-                rssi: -90,
-                pps: 0,
-                snr: 0,
-                timestamp: 0,
-                csi: None,
-                distribution_grid: [[0.0; 24]; 24],
-            },
+            current_stats: NetworkStats::empty(),
             history: Vec::with_capacity(MAX_HISTORY_SIZE),
+            history_coarse: VecDeque::with_capacity(MAX_COARSE_HISTORY_SIZE),
+            averaging_mode: AveragingMode::Mean,
+            outlier_sigma: None,
+            gauge_ranges: GaugeRanges::default(),
 
             start_time: Instant::now(),
             last_update_time: Instant::now(),
+            last_packet_time: Instant::now(),
+            motion_energy: 0.0,
             pps_window: Vec::new(),
+            pps_ema: None,
+            pps_smoothing: PpsSmoothing::default(),
+            header_metric: HeaderMetric::default(),
+            overlay: None,
 
             pane_regions: RefCell::new(Vec::new()),
             splitter_regions: RefCell::new(Vec::new()),
             drag_state: None,
             rerun_streamer: Some(crate::rerun_stream::create_shared_streamer()),
+            rerun_addr: rerun_addr.clone().unwrap_or_else(|| DEFAULT_RERUN_ADDR.to_string()),
+            rerun_record_path: None,
+            show_rerun_input: false,
+            rerun_input_buffer: String::new(),
+            show_rename_input: false,
+            rename_input_buffer: String::new(),
+            raw_log_path: None,
+            serial_status: None,
+            serial_reconnecting: false,
+            source_stopped: false,
+
+            replay_file: csv_file,
+            replay_speed: 1.0,
+            replay_loop: true,
+            replay_position: (0, 0),
+            replay_seek: None,
+            screenshot_requested: false,
+            toast: None,
+            keybindings: keybindings::load(),
+            input_drain: (0, Duration::ZERO),
+            last_input_lag_notice: None,
+            reference: None,
+            amplitude_gain: 1.0,
+            amplitude_clamp: Default::default(),
+            flatten_subcarriers: false,
+            subcarrier_mean: Default::default(),
+            canvas_marker,
+            stats_format,
+            phase_colormap,
+            default_view,
         };
 
-        // Load CSV if provided
-        if let Some(path) = csv_file {
-            if let Err(e) = app.dataloader.import_history_from_csv(&path) {
-                eprintln!("Failed to load CSV: {}", e);
+        if let Some(first) = app.keybindings.warnings.first() {
+            let more = app.keybindings.warnings.len() - 1;
+            let msg = if more > 0 { format!("Keybindings: {} (+{} more)", first, more) } else { format!("Keybindings: {}", first) };
+            app.notify(msg);
+        }
+
+        if let Some(addr) = rerun_addr {
+            app.connect_rerun(&addr);
+        }
+
+        app
+    }
+
+    /// Adds one tick's packet count and returns the PPS according to `pps_smoothing`
+    pub fn update_pps(&mut self, count: usize) -> u64 {
+        let tick_secs = UPDATE_INTERVAL.as_secs_f64();
+        let instant = count as f64 / tick_secs;
+        let ema = match self.pps_ema {
+            Some(prev) => prev + PPS_EMA_ALPHA * (instant - prev),
+            None => instant,
+        };
+        self.pps_ema = Some(ema);
+
+        self.pps_window.push(count);
+        let keep = match self.pps_smoothing {
+            PpsSmoothing::Window(n) => n.max(1),
+            PpsSmoothing::Ema => 1,
+        };
+        if self.pps_window.len() > keep {
+            let excess = self.pps_window.len() - keep;
+            self.pps_window.drain(..excess);
+        }
+
+        match self.pps_smoothing {
+            PpsSmoothing::Window(_) => window_pps(&self.pps_window),
+            PpsSmoothing::Ema => ema.round() as u64,
+        }
+    }
+
+    /// Clears per-source accumulators (history, PPS window, I/Q grid, subcarrier means, queued packets)
+    /// so a new data source starts with clean stats. Layout, theme and cameras are kept.
+    pub fn on_source_switched(&mut self) {
+        let last_id = self.current_stats.id;
+        self.current_stats = NetworkStats::empty();
+        // Keep IDs monotonic so nothing downstream sees a repeated packet ID
+        self.current_stats.id = last_id;
+
+        self.history.clear();
+        self.history_coarse.clear();
+        self.pps_window.clear();
+        self.pps_ema = None;
+        self.subcarrier_mean.clear();
+        self.dataloader.queue.clear();
+        self.dataloader.history.clear();
+
+        // Replay anchors point into the old history
+        for state in self.pane_states.values_mut() {
+            state.reset_live();
+        }
+    }
+
+    /// The onboarding overlay is shown until dismissed, and only while no packet has arrived
+    pub fn onboarding_visible(&self) -> bool {
+        self.show_onboarding && self.history.is_empty() && self.current_stats.csi.is_none()
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replay_file.is_some()
+    }
+
+    /// Asks the serial thread (esp_com) to reset the ESP and resend "start".
+    /// Only meaningful for a live serial source; the outcome arrives as a second toast.
+    pub fn request_esp_reset(&mut self) {
+        if self.is_replaying() {
+            self.notify("ESP reset unavailable while replaying a file");
+        } else if self.should_reset_esp {
+            self.notify("ESP reset already in progress");
+        } else {
+            self.should_reset_esp = true;
+            self.notify("Resetting ESP...");
+        }
+    }
+
+    /// Doubles/halves the replay speed within 0.25x - 8x
+    pub fn adjust_replay_speed(&mut self, faster: bool) {
+        let next = if faster { self.replay_speed * 2.0 } else { self.replay_speed / 2.0 };
+        self.replay_speed = next.clamp(0.25, 8.0);
+    }
+
+    /// Requests a jump of `percent` of the file (negative = backwards) from the current replay position
+    pub fn seek_replay(&mut self, percent: i64) {
+        let (current, total) = self.replay_position;
+        if total == 0 { return; }
+        let step = (total as i64 * percent / 100).abs().max(1) * percent.signum();
+        let base = self.replay_seek.unwrap_or(current) as i64;
+        self.replay_seek = Some((base + step).clamp(0, total as i64 - 1) as usize);
+    }
+
+    /// Writes a Markdown snapshot of the current numeric state (layout, link quality, live subcarrier amplitudes)
+    pub fn export_report(&self, path: &str) -> std::io::Result<()> {
+        let mut out = std::io::BufWriter::new(std::fs::File::create(path)?);
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let mac = self.current_stats.csi.as_ref().map(|c| c.mac.as_str()).unwrap_or("N/A");
+
+        // 1. Header
+        writeln!(out, "# esp-csi-tui-rs Report")?;
+        writeln!(out)?;
+        writeln!(out, "- Timestamp: {} (unix s)", timestamp)?;
+        writeln!(out, "- Uptime: {}s", self.start_time.elapsed().as_secs())?;
+        writeln!(out, "- Source: {}", self.replay_file.as_deref().unwrap_or("Serial (live)"))?;
+        writeln!(out, "- Active MAC: {}", mac)?;
+        writeln!(out, "- Averaging: {}", self.averaging_mode.as_str())?;
+        writeln!(out, "- Outlier Rejection: {}", self.outlier_label())?;
+        if let Some(ref streamer) = self.rerun_streamer && let Ok(s) = streamer.lock() {
+            let rerun = if s.is_connected() {
+                format!("streaming to {}", self.rerun_addr)
             } else {
-                // Populate App::history from dataloader.history
-                let mut previous_grid = [[0.0; 24]; 24];
-                let mut id_counter = 0;
-
-                for csi in &app.dataloader.history {
-                    id_counter += 1;
-                    let snr = csi.rssi - csi.noise_floor;
-
-                    // Calculate Grid
-                    let mut grid = previous_grid;
-                    const GRID_SIZE: usize = 24;
-                    const MIN_VAL: f64 = -128.0;
-                    const MAX_VAL: f64 = 128.0;
-                    const BIN_WIDTH: f64 = (MAX_VAL - MIN_VAL) / GRID_SIZE as f64;
-
-                    let sc_count = csi.csi_raw_data.len() / 2;
-                    for s in 0..sc_count {
-                        let i_val = csi.csi_raw_data.get(s * 2).copied().unwrap_or(0) as f64;
-                        let q_val = csi.csi_raw_data.get(s * 2 + 1).copied().unwrap_or(0) as f64;
-
-                        let bx = ((i_val - MIN_VAL) / BIN_WIDTH).floor() as usize;
-                        let by = ((q_val - MIN_VAL) / BIN_WIDTH).floor() as usize;
-
-                        if bx < GRID_SIZE && by < GRID_SIZE {
-                            grid[bx][by] += 1.0;
-                        }
-                    }
-                    previous_grid = grid;
-
-                    let stat = NetworkStats {
-                        id: id_counter,
-                        rssi: csi.rssi,
-                        pps: 0, // Static file
-                        snr,
-                        timestamp: csi.timestamp,
-                        csi: Some(csi.clone()),
-                        distribution_grid: grid,
-                    };
-                    app.history.push(stat);
-                }
+                s.last_error().map(|e| format!("error: {}", e)).unwrap_or_else(|| "off".to_string())
+            };
+            writeln!(out, "- Rerun: {}", rerun)?;
+        }
+        writeln!(out)?;
+
+        // 2. Link Quality
+        writeln!(out, "## Link Quality")?;
+        writeln!(out)?;
+        writeln!(out, "| Metric | Value |")?;
+        writeln!(out, "|---|---|")?;
+        writeln!(out, "| RSSI | {} dBm |", self.current_stats.rssi)?;
+        writeln!(out, "| SNR | {} dB |", self.current_stats.snr)?;
+        writeln!(out, "| PPS | {} |", self.current_stats.pps)?;
+        writeln!(out, "| Coherence | {:.3} |", self.current_stats.coherence)?;
+        writeln!(out, "| Packet ID | {} |", self.current_stats.id)?;
+        writeln!(out)?;
 
-                // Set current stats to last one
-                if let Some(last) = app.history.last() {
-                    app.current_stats = last.clone();
+        // 3. Layout
+        writeln!(out, "## Panes")?;
+        writeln!(out)?;
+        writeln!(out, "| Pane | View |")?;
+        writeln!(out, "|---|---|")?;
+        for (id, view) in self.tiling.panes() {
+            writeln!(out, "| #{} | {} |", id, view.as_str())?;
+        }
+        writeln!(out)?;
+
+        // 4. Per-Subcarrier Amplitude of the live packet
+        writeln!(out, "## Subcarrier Amplitude")?;
+        writeln!(out)?;
+        match self.current_stats.csi {
+            Some(ref csi) => {
+                writeln!(out, "| Subcarrier | I | Q | Amplitude |")?;
+                writeln!(out, "|---|---|---|---|")?;
+                for (s, pair) in csi.csi_raw_data.chunks_exact(2).enumerate() {
+                    let amplitude = ((pair[0] as f64).powi(2) + (pair[1] as f64).powi(2)).sqrt();
+                    writeln!(out, "| {} | {} | {} | {:.2} |", s, pair[0], pair[1], amplitude)?;
                 }
             }
+            None => writeln!(out, "No CSI received yet.")?,
         }
 
-        if let Some(addr) = rerun_addr {
-            if let Some(ref streamer) = app.rerun_streamer {
-                if let Ok(mut s) = streamer.lock() {
-                    s.connect(&addr);
+        out.flush()
+    }
+
+    /// Connects live streaming to `addr` and remembers it for the next connect
+    pub fn connect_rerun(&mut self, addr: &str) {
+        let addr = addr.trim();
+        let addr = if addr.is_empty() { DEFAULT_RERUN_ADDR } else { addr };
+        self.rerun_addr = addr.to_string();
+
+        let result = match self.rerun_streamer {
+            Some(ref streamer) => match streamer.lock() {
+                Ok(mut s) => {
+                    s.connect(addr);
+                    if s.is_connected() { Ok(()) } else { Err(s.last_error().unwrap_or("unknown error").to_string()) }
                 }
+                Err(_) => Err("streamer unavailable".to_string()),
+            },
+            None => Err("streamer unavailable".to_string()),
+        };
+
+        match result {
+            Ok(_) => self.notify(format!("Rerun: streaming to {}", addr)),
+            Err(e) => self.notify(format!("Rerun: connection to {} failed ({})", addr, e)),
+        }
+    }
+
+    pub fn disconnect_rerun(&mut self) {
+        if let Some(ref streamer) = self.rerun_streamer && let Ok(mut s) = streamer.lock() {
+            s.disconnect();
+        }
+        self.notify("Rerun: disconnected");
+    }
+
+    /// Starts an RRD recording to logs/csi_<unix secs>.rrd, or stops the running one
+    pub fn toggle_rerun_recording(&mut self) {
+        let Some(ref streamer) = self.rerun_streamer else { return };
+        let Ok(mut s) = streamer.lock() else { return };
+
+        let message = if s.is_recording() {
+            s.stop_record();
+            match self.rerun_record_path.take() {
+                Some(path) => format!("Rerun: saved {}", path),
+                None => "Rerun: recording stopped".to_string(),
+            }
+        } else {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            let path = format!("logs/csi_{}.rrd", timestamp);
+            match s.start_record(&path) {
+                Ok(_) => {
+                    let message = format!("Rerun: recording to {}", path);
+                    self.rerun_record_path = Some(path);
+                    message
+                }
+                Err(e) => format!("Rerun: recording failed ({})", e),
             }
+        };
+
+        drop(s);
+        self.notify(message);
+    }
+
+    pub fn is_rerun_recording(&self) -> bool {
+        self.rerun_streamer.as_ref()
+            .and_then(|streamer| streamer.lock().ok().map(|s| s.is_recording()))
+            .unwrap_or(false)
+    }
+
+    /// Stops a running RRD recording so the file is flushed and complete.
+    /// Returns the saved path (or a generic note) when something was recording. Called on exit.
+    pub fn finish_recording(&mut self) -> Option<String> {
+        if !self.is_rerun_recording() {
+            return None;
+        }
+        if let Some(ref streamer) = self.rerun_streamer && let Ok(mut s) = streamer.lock() {
+            s.stop_record();
         }
+        Some(self.rerun_record_path.take().unwrap_or_else(|| "RRD recording".to_string()))
+    }
 
-        app
+    /// Rate (Hz) at which averaged snapshots, and with them Doppler frames, are produced: one per
+    /// UPDATE_INTERVAL while packets arrive at least that fast, fewer when the measured PPS is lower
+    /// (a tick without packets adds nothing). 1 / UPDATE_INTERVAL while the PPS is still unknown.
+    pub fn effective_sample_rate_hz(&self) -> f64 {
+        let tick_rate = 1.0 / UPDATE_INTERVAL.as_secs_f64();
+        match self.current_stats.pps {
+            0 => tick_rate,
+            pps => (pps as f64).min(tick_rate),
+        }
+    }
+
+    /// Dominant bin of the live Doppler FFT (the spectrogram fed to Rerun) and its frequency in Hz
+    /// at the effective sample rate
+    pub fn doppler_peak(&self) -> Option<(usize, f64)> {
+        let streamer = self.rerun_streamer.as_ref()?.lock().ok()?;
+        let doppler = streamer.doppler();
+        let bin = doppler.peak_bin()?;
+        Some((bin, doppler.bin_hz(bin, self.effective_sample_rate_hz())))
+    }
+
+    pub fn is_rerun_connected(&self) -> bool {
+        self.rerun_streamer.as_ref()
+            .and_then(|streamer| streamer.lock().ok().map(|s| s.is_connected()))
+            .unwrap_or(false)
+    }
+
+    /// Averages the last REFERENCE_SNAPSHOTS snapshots (coherent I/Q mean) into `reference`.
+    /// Only snapshots with the newest packet's subcarrier count are used, so a format change
+    /// mid-window can't produce a mixed baseline.
+    pub fn capture_reference(&mut self) {
+        let window = self.history_window(self.history.len().saturating_sub(1), REFERENCE_SNAPSHOTS - 1);
+        let Some(sc_len) = window.last().and_then(|s| s.csi.as_ref()).map(|c| c.csi_raw_data.len()) else {
+            self.notify("Reference: no CSI data yet");
+            return;
+        };
+
+        let packets: Vec<CsiData> = window.iter()
+            .filter_map(|s| s.csi.clone())
+            .filter(|c| c.csi_raw_data.len() == sc_len)
+            .collect();
+        let reference = CsiData::average(&packets);
+
+        self.notify(format!("Reference captured ({} snapshots, {} subcarriers)", packets.len(), sc_len / 2));
+        self.reference = Some(reference);
+    }
+
+    /// Doubles or halves the global amplitude gain within [MIN_AMPLITUDE_GAIN, MAX_AMPLITUDE_GAIN]
+    pub fn adjust_amplitude_gain(&mut self, increase: bool) {
+        let gain = if increase { self.amplitude_gain * 2.0 } else { self.amplitude_gain / 2.0 };
+        self.amplitude_gain = gain.clamp(MIN_AMPLITUDE_GAIN, MAX_AMPLITUDE_GAIN);
+        if self.amplitude_gain == 1.0 {
+            self.notify("Amplitude gain: 1x (auto-scale)");
+        } else {
+            self.notify(format!("Amplitude gain: {}x (fixed scale)", self.amplitude_gain));
+        }
+    }
+
+    /// Shows a short notice above the footer for TOAST_DURATION. A new toast replaces the old one.
+    pub fn notify(&mut self, message: impl Into<String>) {
+        self.toast = Some((message.into(), Instant::now()));
+    }
+
+    /// True when no packet has arrived for GaugeRanges::signal_timeout_secs.
+    /// A replay that stopped at the end of the file (loop off) is paused on purpose, not lost.
+    pub fn signal_lost(&self) -> bool {
+        let (index, total) = self.replay_position;
+        if self.is_replaying() && !self.replay_loop && total > 0 && index + 1 >= total {
+            return false;
+        }
+        self.last_packet_time.elapsed().as_secs_f32() >= self.gauge_ranges.signal_timeout_secs
+    }
+
+    /// True while the motion energy is at or above GaugeRanges::motion_threshold.
+    /// Never set without a live signal (the energy is frozen at its last value then).
+    pub fn motion_detected(&self) -> bool {
+        self.history.len() >= 2 && !self.signal_lost() && self.motion_energy >= self.gauge_ranges.motion_threshold
+    }
+
+    /// Text and color of the header badge (None when it is off). Before the first CSI packet
+    /// the value is "—" in the muted border color. Thresholds come from Gauge Ranges:
+    /// RSSI strong / weak, SNR floor (yellow up to 10 dB above it), PPS 0 / a tenth of the
+    /// gauge maximum, motion half / full alarm threshold (high motion is red).
+    pub fn header_badge(&self) -> Option<(String, Color)> {
+        if self.header_metric == HeaderMetric::Off {
+            return None;
+        }
+        let label = self.header_metric.as_str();
+        if self.current_stats.csi.is_none() {
+            return Some((format!("{} —", label), self.theme.normal_border.fg.unwrap_or(Color::DarkGray)));
+        }
+
+        let ranges = &self.gauge_ranges;
+        let stats = &self.current_stats;
+        let grade = |good: bool, bad: bool| if bad { Color::Red } else if good { Color::Green } else { Color::Yellow };
+        let badge = match self.header_metric {
+            HeaderMetric::Rssi => (format!("{} {} dBm", label, stats.rssi), grade(stats.rssi >= ranges.rssi_strong, stats.rssi < ranges.rssi_weak)),
+            HeaderMetric::Snr => (format!("{} {} dB", label, stats.snr), grade(stats.snr >= ranges.snr_floor + 10, stats.snr < ranges.snr_floor)),
+            HeaderMetric::Pps => (format!("{} {}", label, stats.pps), grade(stats.pps as f64 >= ranges.pps_max / 10.0, stats.pps == 0)),
+            HeaderMetric::Motion => {
+                let energy = self.motion_energy;
+                (format!("{} {}", label, self.stats_format.number(energy as f64)), grade(energy < ranges.motion_threshold / 2.0, energy >= ranges.motion_threshold))
+            }
+            HeaderMetric::Off => return None,
+        };
+        Some(badge)
+    }
+
+    /// Records one frame's event drain (replacing the previous frame's numbers). `backlogged`
+    /// means the time budget ran out with events still queued, i.e. input is falling behind;
+    /// that is reported as a toast at most once per INPUT_LAG_NOTICE_INTERVAL.
+    pub fn record_input_drain(&mut self, events: usize, elapsed: Duration, backlogged: bool) {
+        self.input_drain = (events, elapsed);
+        if !backlogged || self.last_input_lag_notice.is_some_and(|at| at.elapsed() < INPUT_LAG_NOTICE_INTERVAL) {
+            return;
+        }
+        self.last_input_lag_notice = Some(Instant::now());
+        self.notify(format!("Input lagging: {} events in {}ms, more queued", events, elapsed.as_millis()));
+    }
+
+    /// "Off" or e.g. "3σ"
+    pub fn outlier_label(&self) -> String {
+        match self.outlier_sigma {
+            Some(k) => format!("{}σ", k),
+            None => "Off".to_string(),
+        }
+    }
+
+    /// Off -> 3σ -> 2σ -> Off
+    pub fn cycle_outlier_sigma(&mut self) {
+        let current = OUTLIER_SIGMAS.iter().position(|s| *s == self.outlier_sigma).unwrap_or(0);
+        self.outlier_sigma = OUTLIER_SIGMAS[(current + 1) % OUTLIER_SIGMAS.len()];
+    }
+
+    /// The packet a pane is showing: its anchored packet while paused/replaying, else the live one.
+    /// None if the anchor has already left the history.
+    pub fn displayed_packet(&self, id: usize) -> Option<&NetworkStats> {
+        let source = self.source_for(id);
+        match self.pane_states.get(&id).and_then(|s| s.anchor_packet_id) {
+            Some(anchor) => source.history.iter().find(|p| p.id == anchor),
+            None => Some(source.current_stats),
+        }
+    }
+
+    pub fn primary_source(&self) -> SourceView<'_> {
+        SourceView { history: &self.history, current_stats: &self.current_stats, overlay_label: None }
+    }
+
+    /// Packets pane `id` draws from: the overlay if the pane opted in and one is running,
+    /// otherwise the primary source
+    pub fn source_for(&self, id: usize) -> SourceView<'_> {
+        let wants_overlay = self.pane_states.get(&id).is_some_and(|s| s.overlay_source);
+        match self.overlay.as_ref() {
+            Some(overlay) if wants_overlay => SourceView {
+                history: &overlay.history,
+                current_stats: &overlay.current_stats,
+                overlay_label: Some(&overlay.label),
+            },
+            _ => self.primary_source(),
+        }
+    }
+
+    /// Packets from `depth` steps before `end_index` up to and including it.
+    /// Both ends are clamped to the current history, so a stale anchor index (after a clear or
+    /// source switch) can't panic. Empty when there is no history.
+    pub fn history_window(&self, end_index: usize, depth: usize) -> &[NetworkStats] {
+        self.primary_source().history_window(end_index, depth)
+    }
+
+    /// Coarse snapshots from `depth` steps before `end_index` up to and including it, clamped
+    /// like history_window. Copied out because the ring buffer isn't one contiguous slice.
+    pub fn coarse_window(&self, end_index: usize, depth: usize) -> Vec<NetworkStats> {
+        if self.history_coarse.is_empty() {
+            return Vec::new();
+        }
+        let end = end_index.min(self.history_coarse.len() - 1);
+        let start = end.saturating_sub(depth);
+        self.history_coarse.range(start..=end).cloned().collect()
+    }
+
+    /// Index of the coarse snapshot whose second contains packet `id` (the newest one while that
+    /// second is still open). None without coarse history or for an unknown future id.
+    pub fn coarse_index_of(&self, id: u64) -> Option<usize> {
+        if self.history_coarse.is_empty() || id > self.current_stats.id {
+            return None;
+        }
+        let newest = self.history_coarse.len() - 1;
+        Some(self.history_coarse.iter().position(|p| p.id >= id).unwrap_or(newest))
+    }
+
+    /// Once the newest snapshot starts a new second, averages the previous second of `history`
+    /// into one `history_coarse` point. Buckets follow NetworkStats::timestamp (app clock).
+    fn update_coarse_history(&mut self) {
+        let n = self.history.len();
+        if n < 2 {
+            return;
+        }
+        let bucket = |s: &NetworkStats| s.timestamp / COARSE_INTERVAL_MS;
+        let closed = bucket(&self.history[n - 2]);
+        if bucket(&self.history[n - 1]) == closed {
+            return;
+        }
+        let start = self.history[..n - 1].iter().rposition(|s| bucket(s) != closed).map_or(0, |i| i + 1);
+
+        if self.history_coarse.len() >= MAX_COARSE_HISTORY_SIZE {
+            self.history_coarse.pop_front();
+        }
+        self.history_coarse.push_back(NetworkStats::average(&self.history[start..n - 1]));
+    }
+
+    /// Indices into `available_templates` whose name contains `template_filter` (case-insensitive).
+    /// `load_selector_index` indexes this list, not `available_templates`.
+    pub fn filtered_templates(&self) -> Vec<usize> {
+        let filter = self.template_filter.to_lowercase();
+        self.available_templates
+            .iter()
+            .enumerate()
+            .filter(|(_, (name, _))| name.strip_suffix(".json").unwrap_or(name).to_lowercase().contains(&filter))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Capture time of a snapshot in ms. Live snapshots use the app clock (ms since start);
+    /// replayed ones use the recorded ESP timestamp (microseconds in CSV captures), so playback
+    /// speed and seeking don't stretch the time axis.
+    pub fn capture_time_ms(&self, stats: &NetworkStats) -> f64 {
+        if self.is_replaying() && let Some(csi) = stats.csi.as_ref().filter(|c| c.timestamp > 0) {
+            return csi.timestamp as f64 / 1000.0;
+        }
+        stats.timestamp as f64
+    }
+
+    /// Seconds from `older` to `newer` (0.0 if the clock went backwards, e.g. a looping replay)
+    pub fn seconds_between(&self, older: &NetworkStats, newer: &NetworkStats) -> f64 {
+        ((self.capture_time_ms(newer) - self.capture_time_ms(older)) / 1000.0).max(0.0)
+    }
+
+    /// How long before history[now_index] the snapshot at history[index] was captured
+    pub fn seconds_ago(&self, index: usize, now_index: usize) -> f64 {
+        match (self.history.get(index), self.history.get(now_index)) {
+            (Some(older), Some(newer)) => self.seconds_between(older, newer),
+            _ => 0.0,
+        }
     }
 
     pub fn get_pane_state_mut(&mut self, id: usize) -> &mut ViewState {
-        self.pane_states.entry(id).or_insert_with(ViewState::new)
+        self.pane_states.entry(id).or_default()
+    }
+
+    /// Splits the focused pane; the new pane shows App::default_view (Empty unless configured)
+    /// and takes the focus. Does nothing once the layout has MAX_PANES panes.
+    pub fn split_focused_pane(&mut self, direction: ratatui::layout::Direction) {
+        if self.tiling.split_with(direction, self.default_view).is_none() {
+            self.notify(format!("At most {} panes", crate::layout_tree::MAX_PANES));
+        }
+    }
+
+    /// Splits the focused pane (side by side) into an exact copy: same view and a clone of its
+    /// ViewState (camera, zoom, replay position, settings, name). The copy takes the focus.
+    /// Does nothing once the layout has MAX_PANES panes.
+    pub fn duplicate_focused_pane(&mut self) {
+        let source_id = self.tiling.focused_pane_id;
+        let Some(view) = self.tiling.panes().into_iter().find(|&(id, _)| id == source_id).map(|(_, view)| view) else { return };
+        let state = self.pane_states.get(&source_id).cloned();
+        let Some(new_id) = self.tiling.split_with(ratatui::layout::Direction::Horizontal, view) else {
+            self.notify(format!("At most {} panes", crate::layout_tree::MAX_PANES));
+            return;
+        };
+        match state {
+            Some(state) => { self.pane_states.insert(new_id, state); }
+            None => { self.pane_states.remove(&new_id); }
+        }
+    }
+
+    /// Closes the focused pane. The remaining panes are renumbered, so their ViewState,
+    /// view selector marks and a fullscreen pane move to the new ids; closing the
+    /// fullscreen pane itself ends fullscreen.
+    pub fn close_focused_pane(&mut self) {
+        let new_ids = self.tiling.close_focused_pane();
+        if new_ids.is_empty() {
+            return;
+        }
+
+        self.pane_states = std::mem::take(&mut self.pane_states)
+            .into_iter()
+            .filter_map(|(id, state)| new_ids.get(&id).map(|&new_id| (new_id, state)))
+            .collect();
+        self.view_selector_marks = self.view_selector_marks.iter().filter_map(|id| new_ids.get(id).copied()).collect();
+
+        self.fullscreen_pane_id = self.fullscreen_pane_id.and_then(|id| new_ids.get(&id).copied());
+        if self.fullscreen_pane_id.is_none() {
+            self.fullscreen_restore = None;
+        } else if let Some(restore) = &mut self.fullscreen_restore {
+            restore.focused_pane_id = new_ids.get(&restore.focused_pane_id).copied().unwrap_or(self.tiling.focused_pane_id);
+        }
     }
 
-    pub fn on_tick(&mut self) {
+    // --- Fullscreen ---
+
+    /// Shows one pane over the whole layout. With `restore_view` (Action::Maximize) the pane's
+    /// ViewState is set aside too, so whatever is changed while fullscreen is undone on exit.
+    pub fn enter_fullscreen(&mut self, id: usize, restore_view: bool) {
+        let pane_state = restore_view.then(|| self.get_pane_state_mut(id).clone());
+        self.fullscreen_restore = Some(FullscreenRestore { focused_pane_id: self.tiling.focused_pane_id, pane_state });
+        self.fullscreen_pane_id = Some(id);
+    }
+
+    /// Back to the tiled layout, with the focus (and for Maximize the pane's view) from before
+    pub fn exit_fullscreen(&mut self) {
+        let (Some(id), Some(restore)) = (self.fullscreen_pane_id.take(), self.fullscreen_restore.take()) else { return };
+        if let Some(state) = restore.pane_state.filter(|_| self.tiling.pane_exists(id)) {
+            self.pane_states.insert(id, state);
+        }
+        if self.tiling.pane_exists(restore.focused_pane_id) {
+            self.tiling.focused_pane_id = restore.focused_pane_id;
+        }
+    }
+
+    /// Drops fullscreen when its pane no longer exists (closed, or a layout without it was loaded),
+    /// which would otherwise leave an empty screen. Called before every frame.
+    pub fn clear_stale_fullscreen(&mut self) {
+        if self.fullscreen_pane_id.is_some_and(|id| !self.tiling.pane_exists(id)) {
+            self.fullscreen_pane_id = None;
+            self.fullscreen_restore = None;
+        }
+    }
+
+    /// Border style for a data pane. The Dashboard (or every view, see GaugeRanges::tint_all_borders)
+    /// is tinted by the live RSSI once data arrives; the focused pane keeps its bold modifier.
+    pub fn pane_border(&self, is_focused: bool, view: ViewType) -> Style {
+        let base = if is_focused { self.theme.focused_border } else { self.theme.normal_border };
+        let tint = view == ViewType::Dashboard || self.gauge_ranges.tint_all_borders;
+        if !tint || self.current_stats.csi.is_none() {
+            return base;
+        }
+        let ranges = &self.gauge_ranges;
+        base.patch(self.theme.signal_border(self.current_stats.rssi, ranges.rssi_strong, ranges.rssi_weak))
+    }
+
+    /// Style of a pane's title text: the normal text style in the view's accent color
+    pub fn title_style(&self, view: ViewType) -> Style {
+        self.theme.text_normal.fg(view.accent_color(&self.theme))
+    }
+
+    /// Title text for a pane: "TX-left · Phase Wireframe" if the user named it, else the view name
+    pub fn pane_label(&self, id: usize, view_name: &str) -> String {
+        let label = match self.pane_states.get(&id).and_then(|s| s.label.as_deref()) {
+            Some(label) => format!("{} · {}", label, view_name),
+            None => view_name.to_string(),
+        };
+        // With two sources running, every pane says which one it shows
+        match self.overlay {
+            Some(_) => format!("[{}] {}", self.source_for(id).overlay_label.unwrap_or(PRIMARY_SOURCE_LABEL), label),
+            None => label,
+        }
+    }
+
+    /// The layout as saved in a template: tiling tree plus theme, canvas marker, number format, phase colors, default view and pane states
+    pub fn layout_for_export(&self) -> TilingManager {
+        let mut tiling = self.tiling.clone();
+        tiling.theme_variant = Some(self.theme.variant);
+        tiling.canvas_marker = Some(self.canvas_marker);
+        tiling.stats_format = Some(self.stats_format);
+        tiling.phase_colormap = Some(self.phase_colormap);
+        tiling.default_view = Some(self.default_view);
+        tiling.is_default = false;
+        tiling.pane_states = self.pane_states.clone();
+        tiling
+    }
+
+    /// Switches to a loaded template (see layout_for_export); settings it doesn't carry are kept
+    pub fn apply_layout(&mut self, tiling: TilingManager) {
+        if let Some(variant) = tiling.theme_variant { self.theme = Theme::new(variant); }
+        if let Some(marker) = tiling.canvas_marker { self.canvas_marker = marker; }
+        if let Some(format) = tiling.stats_format { self.stats_format = format; }
+        if let Some(colormap) = tiling.phase_colormap { self.phase_colormap = colormap; }
+        if let Some(view) = tiling.default_view { self.default_view = view; }
+        self.pane_states = tiling.pane_states.clone();
+        self.tiling = tiling;
+    }
+
+    /// Names a pane (saved with templates). A blank label reverts to the view name.
+    pub fn set_pane_label(&mut self, id: usize, label: &str) {
+        let label = label.trim();
+        self.get_pane_state_mut(id).label = if label.is_empty() { None } else { Some(label.to_string()) };
+    }
+
+    /// Drains the packet queue every UPDATE_INTERVAL and moves auto-advancing panes.
+    /// Returns true if anything drawn may have changed (data, PPS, toast, signal state, playback),
+    /// so the main loop can skip redrawing an idle screen.
+    pub fn on_tick(&mut self) -> bool {
+        let before = (self.current_stats.id, self.current_stats.pps, self.toast.is_some(), self.signal_lost(), self.overlay_live_id());
+
         // 1. Drain the Queue from the background thread
         // We do this every tick to prevent the queue from exploding in memory,
         // even if we don't update the UI yet.
@@ -227,65 +1259,31 @@ impl App {
         // HOWEVER, since Dataloader is now a Queue, we can simply wait until the
         // timer fires to drain it.
 
+        // Expire the toast so the next notify() starts from a clean slate
+        if self.toast.as_ref().is_some_and(|(_, at)| at.elapsed() >= TOAST_DURATION) {
+            self.toast = None;
+        }
+
         if self.last_update_time.elapsed() >= UPDATE_INTERVAL {
             // TIME TO UPDATE!
 
             let raw_packets = self.dataloader.drain_buffer();
             let count = raw_packets.len();
-
-            // Update PPS Window
-            self.pps_window.push(count);
-            // Keep last 1 second of history (10 * 100ms)
-            if self.pps_window.len() > 10 {
-                self.pps_window.remove(0);
+            if count > 0 {
+                self.last_packet_time = Instant::now();
+                // Once data has flowed the guidance is no longer needed, even after a source switch
+                self.show_onboarding = false;
             }
 
-            let total_packets: usize = self.pps_window.iter().sum();
-            let window_secs = self.pps_window.len() as f64 * UPDATE_INTERVAL.as_secs_f64();
-            let calculated_pps = if window_secs > 0.0 {
-                (total_packets as f64 / window_secs) as u64
-            } else {
-                0
-            };
+            let calculated_pps = self.update_pps(count);
 
             if count > 0 {
                 // Calculate Average
-                let averaged_csi = CsiData::average(&raw_packets);
+                let averaged_csi = CsiData::aggregate(&raw_packets, self.averaging_mode, self.outlier_sigma);
                 let elapsed_ms = self.start_time.elapsed().as_millis() as u64;
 
-                let noise = averaged_csi.noise_floor;
-                let snr = averaged_csi.rssi - noise;
-
-                // --- Calculate Distribution Grid (Cumulative) ---
-                let mut grid = self.current_stats.distribution_grid; // Copy previous state (Cumulative)
-                const GRID_SIZE: usize = 24;
-                const MIN_VAL: f64 = -128.0;
-                const MAX_VAL: f64 = 128.0;
-                const BIN_WIDTH: f64 = (MAX_VAL - MIN_VAL) / GRID_SIZE as f64;
-
-                let sc_count = averaged_csi.csi_raw_data.len() / 2;
-                for s in 0..sc_count {
-                    let i_val = averaged_csi.csi_raw_data.get(s * 2).copied().unwrap_or(0) as f64;
-                    let q_val = averaged_csi.csi_raw_data.get(s * 2 + 1).copied().unwrap_or(0) as f64;
-
-                    let bx = ((i_val - MIN_VAL) / BIN_WIDTH).floor() as usize;
-                    let by = ((q_val - MIN_VAL) / BIN_WIDTH).floor() as usize;
-
-                    if bx < GRID_SIZE && by < GRID_SIZE {
-                        grid[bx][by] += 1.0;
-                    }
-                }
-
                 // Create new Stat Snapshot
-                let new_stat = NetworkStats {
-                    id: self.current_stats.id + 1,
-                    rssi: averaged_csi.rssi,
-                    pps: calculated_pps,
-                    snr,
-                    timestamp: elapsed_ms,
-                    csi: Some(averaged_csi.clone()),
-                    distribution_grid: grid,
-                };
+                let new_stat = next_snapshot(&self.current_stats, &averaged_csi, calculated_pps, elapsed_ms);
 
                 self.current_stats = new_stat.clone();
 
@@ -294,15 +1292,23 @@ impl App {
                     self.history.remove(0);
                 }
                 self.history.push(new_stat);
+                self.update_coarse_history();
+                self.subcarrier_mean.update(&averaged_csi);
+
+                // Motion energy over the most recent snapshots
+                let window: Vec<&CsiData> = self.history[self.history.len().saturating_sub(MOTION_WINDOW)..]
+                    .iter()
+                    .filter_map(|p| p.csi.as_ref())
+                    .collect();
+                self.motion_energy = crate::backend::csi_data::motion_energy(&window);
 
                 // Log to Rerun if enabled
-                if let Some(ref streamer) = self.rerun_streamer {
-                    if let Ok(mut s) = streamer.lock() {
-                        #[cfg(feature = "rerun")]
-                        {
-                            let frame = crate::rerun_stream::CsiFrame::from(&averaged_csi);
-                            s.push_csi(&frame);
-                        }
+                if let Some(ref streamer) = self.rerun_streamer && let Ok(mut s) = streamer.lock() {
+                    #[cfg(feature = "rerun")]
+                    {
+                        let frame = crate::rerun_stream::CsiFrame::from(&averaged_csi);
+                        s.phase_colormap = self.phase_colormap;
+                        s.push_csi(&frame);
                     }
                 }
             } else {
@@ -311,8 +1317,55 @@ impl App {
                  self.current_stats.pps = calculated_pps;
             }
 
+            self.tick_overlay();
             self.last_update_time = Instant::now();
         }
+
+        // Panes playing through history (ViewState::auto_advance), each in its own source
+        let primary = (self.current_stats.id, self.history.first().map_or(0, |p| p.id));
+        let overlay = self.overlay.as_ref().map(|o| (o.current_stats.id, o.history.first().map_or(0, |p| p.id)));
+        let mut playing = false;
+        for state in self.pane_states.values_mut() {
+            let (live_id, min_id) = match overlay {
+                Some(ids) if state.overlay_source => ids,
+                _ => primary,
+            };
+            state.advance(live_id, min_id);
+            playing |= state.auto_advance.is_some();
+        }
+
+        playing || before != (self.current_stats.id, self.current_stats.pps, self.toast.is_some(), self.signal_lost(), self.overlay_live_id())
+    }
+
+    fn overlay_live_id(&self) -> Option<u64> {
+        self.overlay.as_ref().map(|o| o.current_stats.id)
+    }
+
+    /// One tick of the overlay source: drains its queue into a snapshot, like the primary source
+    /// (same averaging mode and outlier rejection, no Rerun/motion/coarse history)
+    fn tick_overlay(&mut self) {
+        let (mode, sigma) = (self.averaging_mode, self.outlier_sigma);
+        let elapsed_ms = self.start_time.elapsed().as_millis() as u64;
+        let Some(overlay) = self.overlay.as_mut() else { return };
+
+        let packets = overlay.dataloader.drain_buffer();
+        overlay.pps_window.push(packets.len());
+        if overlay.pps_window.len() > OVERLAY_PPS_WINDOW {
+            overlay.pps_window.remove(0);
+        }
+        let pps = window_pps(&overlay.pps_window);
+        if packets.is_empty() {
+            overlay.current_stats.pps = pps;
+            return;
+        }
+
+        let averaged_csi = CsiData::aggregate(&packets, mode, sigma);
+        let snapshot = next_snapshot(&overlay.current_stats, &averaged_csi, pps, elapsed_ms);
+        overlay.current_stats = snapshot.clone();
+        if overlay.history.len() >= MAX_HISTORY_SIZE {
+            overlay.history.remove(0);
+        }
+        overlay.history.push(snapshot);
     }
 
     pub fn next_theme(&mut self) {
@@ -325,4 +1378,156 @@ impl App {
         };
         self.theme = Theme::new(next);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Direction;
+
+    fn test_app() -> App {
+        let mut app = App::new(None, None);
+        app.tiling = TilingManager::new();
+        app.pane_states.clear();
+        app.rerun_streamer = None;
+        app.toast = None;
+        app
+    }
+
+    #[test]
+    fn coarse_history_averages_each_second() {
+        let mut app = test_app();
+        // Four snapshots per second for three seconds; the last second is still open
+        for ms in (0..3000).step_by(250) {
+            app.start_time = Instant::now() - Duration::from_millis(ms);
+            app.last_update_time = Instant::now() - UPDATE_INTERVAL;
+            let rssi = -40 - (ms / 1000) as i32 * 10;
+            app.dataloader.queue.push_back(CsiData { rssi, csi_raw_data: vec![4, 3, 8, 6], ..Default::default() });
+            app.on_tick();
+        }
+        assert_eq!(app.history.len(), 12);
+        let rssi: Vec<i32> = app.history_coarse.iter().map(|s| s.rssi).collect();
+        assert_eq!(rssi, vec![-40, -50]);
+        assert_eq!(app.history_coarse[1].id, app.history[7].id);
+        assert_eq!(app.coarse_index_of(app.history[5].id), Some(1));
+        assert_eq!(app.coarse_index_of(app.current_stats.id), Some(1));
+        assert_eq!(app.coarse_window(5, 10).len(), 2);
+
+        app.on_source_switched();
+        assert!(app.history_coarse.is_empty());
+    }
+
+    #[test]
+    fn pps_smoothing_window_and_ema() {
+        let mut app = test_app();
+
+        // A window of one tick is the instantaneous rate
+        app.pps_smoothing = PpsSmoothing::Window(1);
+        assert_eq!(app.update_pps(10), 100);
+        assert_eq!(app.update_pps(0), 0);
+        assert_eq!(app.pps_window.len(), 1);
+
+        // Longer windows average the ticks they hold
+        app.pps_smoothing = PpsSmoothing::Window(10);
+        for _ in 0..9 {
+            app.update_pps(10);
+        }
+        assert_eq!(app.pps_window.len(), 10);
+        assert_eq!(app.update_pps(10), 100);
+        assert_eq!(app.pps_window.len(), 10);
+
+        // The EMA was tracking all along, so switching doesn't restart from zero
+        app.pps_smoothing = PpsSmoothing::Ema;
+        let steady = app.update_pps(10);
+        assert!((85..=100).contains(&steady), "{}", steady);
+        let after_drop = app.update_pps(0);
+        assert!(after_drop < steady && after_drop > 50, "{}", after_drop);
+
+        app.on_source_switched();
+        assert!(app.pps_ema.is_none() && app.pps_window.is_empty());
+    }
+
+    #[test]
+    fn source_switch_clears_the_subcarrier_means() {
+        let mut app = test_app();
+        app.subcarrier_mean.update(&CsiData { csi_raw_data: vec![10, 0, 40, 0], ..Default::default() });
+        app.on_source_switched();
+        assert!(app.subcarrier_mean.mean.is_empty());
+    }
+
+    #[test]
+    fn closing_a_pane_moves_state_to_the_renumbered_ids() {
+        let mut app = test_app();
+        app.tiling.split(Direction::Horizontal); // Panes 1 | 2
+        app.tiling.split(Direction::Vertical); // Panes 1 | 2 / 3, focus on 3
+        app.tiling.set_current_view(ViewType::Polar);
+        app.get_pane_state_mut(3).zoom = 2.0;
+        app.enter_fullscreen(3, false);
+
+        // Close pane 2: pane 3 becomes pane 2 and takes its camera and fullscreen with it
+        app.tiling.focused_pane_id = 2;
+        app.close_focused_pane();
+        assert_eq!(app.tiling.get_pane_count(), 2);
+        assert_eq!(app.fullscreen_pane_id, Some(2));
+        assert_eq!(app.get_pane_state_mut(2).zoom, 2.0);
+        assert!(!app.pane_states.contains_key(&3));
+
+        // Closing the fullscreen pane itself ends fullscreen
+        app.tiling.focused_pane_id = 2;
+        app.close_focused_pane();
+        assert_eq!(app.fullscreen_pane_id, None);
+        assert_eq!(app.tiling.focused_pane_id, 1);
+    }
+
+    #[test]
+    fn duplicating_a_pane_copies_view_and_state() {
+        let mut app = test_app();
+        app.tiling.set_current_view(ViewType::Polar);
+        let state = app.get_pane_state_mut(1);
+        state.zoom = 2.5;
+        state.anchor_packet_id = Some(4);
+
+        app.duplicate_focused_pane();
+        assert_eq!(app.tiling.panes(), vec![(1, ViewType::Polar), (2, ViewType::Polar)]);
+        assert_eq!(app.tiling.focused_pane_id, 2);
+        assert_eq!(app.get_pane_state_mut(2).zoom, 2.5);
+        assert_eq!(app.get_pane_state_mut(2).anchor_packet_id, Some(4));
+
+        // The copy is independent
+        app.get_pane_state_mut(2).zoom = 1.0;
+        assert_eq!(app.get_pane_state_mut(1).zoom, 2.5);
+
+        // Still works after closing and renumbering, and stops at the pane cap
+        app.tiling.focused_pane_id = 1;
+        app.close_focused_pane();
+        app.duplicate_focused_pane();
+        assert_eq!(app.tiling.panes(), vec![(1, ViewType::Polar), (2, ViewType::Polar)]);
+        assert_eq!(app.get_pane_state_mut(2).zoom, 1.0);
+        while app.tiling.get_pane_count() < crate::frontend::layout_tree::MAX_PANES {
+            app.duplicate_focused_pane();
+        }
+        app.duplicate_focused_pane();
+        assert_eq!(app.tiling.get_pane_count(), crate::frontend::layout_tree::MAX_PANES);
+    }
+
+    #[test]
+    fn split_panes_start_with_the_default_view() {
+        let mut app = test_app();
+        app.split_focused_pane(Direction::Horizontal);
+        assert_eq!(app.tiling.panes(), vec![(1, ViewType::Empty), (2, ViewType::Empty)]);
+
+        app.default_view = ViewType::Dashboard;
+        app.split_focused_pane(Direction::Vertical);
+        assert_eq!(app.tiling.panes()[2], (3, ViewType::Dashboard));
+        assert_eq!(app.tiling.focused_pane_id, 3);
+
+        // Saved with the layout; a template without it keeps the current choice
+        let saved = app.layout_for_export();
+        assert_eq!(saved.default_view, Some(ViewType::Dashboard));
+        app.default_view = ViewType::Empty;
+        app.apply_layout(saved);
+        assert_eq!(app.default_view, ViewType::Dashboard);
+        app.apply_layout(TilingManager::new());
+        assert_eq!(app.default_view, ViewType::Dashboard);
+    }
+}
@@ -3,7 +3,7 @@
 
 use serde::{Serialize, Deserialize};
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CsiData {
     pub mac: String,
     pub rssi: i32,
@@ -30,8 +30,297 @@ pub struct CsiData {
     pub csi_raw_data: Vec<i32>,
 }
 
+/// How the packets received during one UI update interval are folded into one snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AveragingMode {
+    Mean,
+    Incoherent,
+    Median,
+    LatestOnly,
+}
+
+impl AveragingMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AveragingMode::Mean => "Mean (Coherent)",
+            AveragingMode::Incoherent => "Mean (Incoherent)",
+            AveragingMode::Median => "Median",
+            AveragingMode::LatestOnly => "Latest Only",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            AveragingMode::Mean => AveragingMode::Incoherent,
+            AveragingMode::Incoherent => AveragingMode::Median,
+            AveragingMode::Median => AveragingMode::LatestOnly,
+            AveragingMode::LatestOnly => AveragingMode::Mean,
+        }
+    }
+}
+
+/// Outlier rejection thresholds cycled from the main menu (None = keep every packet)
+pub const OUTLIER_SIGMAS: [Option<f32>; 3] = [None, Some(3.0), Some(2.0)];
+
+// Smallest spread assumed when judging outliers, so a batch of identical values (RSSI is an
+// integer and often constant) doesn't make every small change an outlier
+const MIN_RSSI_SIGMA: f64 = 1.0; // dB
+const MIN_AMPLITUDE_SIGMA_FRACTION: f64 = 0.05; // of the median total amplitude
+
+/// Robust spread: 1.4826 * median absolute deviation (equals sigma for normal data),
+/// so the outlier itself can't inflate it the way it inflates a plain standard deviation
+fn median_and_sigma(values: &[f64]) -> (f64, f64) {
+    let median = |v: &mut Vec<f64>| -> f64 {
+        v.sort_by(|a, b| a.total_cmp(b));
+        let mid = v.len() / 2;
+        if v.len() % 2 == 1 { v[mid] } else { (v[mid - 1] + v[mid]) / 2.0 }
+    };
+    let center = median(&mut values.to_vec());
+    let mad = median(&mut values.iter().map(|v| (v - center).abs()).collect());
+    (center, 1.4826 * mad)
+}
+
+fn median_i32(mut values: Vec<i32>) -> i32 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len() % 2 == 1 {
+        values[mid]
+    } else {
+        (values[mid - 1] + values[mid]) / 2
+    }
+}
+
+/// Why a serial block could not be turned into a CsiData packet
+#[derive(Debug, Clone, PartialEq)]
+pub enum CsiParseError {
+    /// The block had no `csi raw data:` section, or the array was empty
+    NoCsi,
+    /// A field that we can't do without held an unparseable value
+    BadField { field: String, value: String },
+}
+
+impl std::fmt::Display for CsiParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CsiParseError::NoCsi => write!(f, "no csi raw data in packet"),
+            CsiParseError::BadField { field, value } => write!(f, "invalid {}: '{}'", field, value),
+        }
+    }
+}
+
+impl std::error::Error for CsiParseError {}
+
+// Marker line that precedes the bracketed CSI array in a firmware block
+const CSI_MARKER: &str = "csi raw data:";
+
+// Metadata lines kept while waiting for the marker. Older ones are dropped, so a stream that
+// never contains it (wrong firmware, garbled baud rate) can't grow the buffer forever.
+pub const DEFAULT_MAX_BLOCK_LINES: usize = 64;
+
+/// Splits the line-based serial stream into blocks for CsiData::parse. A block ends with the
+/// bracketed array after "csi raw data:", however many metadata lines came before it, so
+/// firmware variants with extra or missing fields stay aligned. ESP-IDF log lines ("I (123) wifi:
+/// ...") are skipped wherever they appear, and an array wrapped over several lines is joined.
+#[derive(Debug)]
+pub struct BlockAssembler {
+    max_lines: usize,
+    lines: Vec<String>,
+    array: Option<String>, // Some once the marker was seen: the array text collected so far
+}
+
+impl Default for BlockAssembler {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BLOCK_LINES)
+    }
+}
+
+impl BlockAssembler {
+    pub fn new(max_lines: usize) -> Self {
+        Self { max_lines: max_lines.max(1), lines: Vec::new(), array: None }
+    }
+
+    /// Feeds one line; returns the finished block once its CSI array is closed
+    pub fn push_line(&mut self, line: &str) -> Option<String> {
+        let line = line.trim();
+        if line.is_empty() || is_firmware_log(line) {
+            return None;
+        }
+
+        if let Some(array) = self.array.as_mut() {
+            let starts = array.is_empty() && line.starts_with('[');
+            let continues = !array.is_empty() && !line.contains(':');
+            if starts || continues {
+                if continues {
+                    array.push(' ');
+                }
+                array.push_str(line);
+                if !line.ends_with(']') {
+                    return None;
+                }
+                let block = format!("{}\n{}\n{}\n", self.lines.join("\n"), CSI_MARKER, array);
+                self.clear();
+                return Some(block);
+            }
+            // The array never came (or was cut off): this line already belongs to the next block
+            self.clear();
+        }
+
+        if line == CSI_MARKER {
+            self.array = Some(String::new());
+        } else {
+            if self.lines.len() >= self.max_lines {
+                self.lines.remove(0);
+            }
+            self.lines.push(line.to_string());
+        }
+        None
+    }
+
+    /// Drops a partial block (e.g. after an ESP reset)
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.array = None;
+    }
+}
+
+/// ESP-IDF log output: a level letter and the uptime in parentheses, e.g. "W (1234) wifi: ..."
+fn is_firmware_log(line: &str) -> bool {
+    let mut chars = line.chars();
+    matches!(chars.next(), Some('E' | 'W' | 'I' | 'D' | 'V'))
+        && chars.next() == Some(' ')
+        && chars.next() == Some('(')
+        && line[3..].split_once(')').is_some_and(|(uptime, _)| !uptime.is_empty() && uptime.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Per-subcarrier change between two packets (see CsiData::diff)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SubcarrierDelta {
+    pub index: usize,
+    pub amplitude_before: f64,
+    pub amplitude_after: f64,
+    /// amplitude_after - amplitude_before
+    pub amplitude_delta: f64,
+    /// Phase change in radians, wrapped to -PI..=PI
+    pub phase_delta: f64,
+}
+
+/// ESP prints some signed 8-bit fields as unsigned bytes (e.g. 161 for -95)
+fn wrap_i8(val: i32) -> i32 {
+    if val > 127 { val - 256 } else { val }
+}
+
+/// Subcarriers per LTF block: 64 at 20 MHz, 128 at 40 MHz (cwb = 1).
+/// A buffer that isn't a whole number of blocks is treated as a single block.
+fn ltf_block_size(count: usize, cwb: u32) -> usize {
+    let block = if cwb == 1 { 128 } else { 64 };
+    if count >= block && count.is_multiple_of(block) { block } else { count.max(1) }
+}
+
+/// Signed subcarrier number of buffer position `i` in a CSI buffer of `count` subcarriers.
+/// The ESP32 stores each LTF block as 0..N/2-1 followed by -N/2..-1 (DC first), so e.g. at
+/// 20 MHz position 33 is subcarrier -31. Buffers holding LLTF + HT-LTF repeat the numbering.
+pub fn frequency_index(i: usize, count: usize, cwb: u32) -> i32 {
+    let block = ltf_block_size(count, cwb);
+    let j = i % block;
+    if j < block.div_ceil(2) { j as i32 } else { j as i32 - block as i32 }
+}
+
+/// Reorders per-subcarrier values from buffer order into ascending frequency
+/// (-N/2 .. N/2-1, DC in the middle), block by block
+pub fn to_frequency_order<T: Clone>(values: &[T], cwb: u32) -> Vec<T> {
+    let block = ltf_block_size(values.len(), cwb);
+    values
+        .chunks(block)
+        .flat_map(|chunk| {
+            let half = chunk.len().div_ceil(2);
+            chunk[half..].iter().chain(chunk[..half].iter()).cloned()
+        })
+        .collect()
+}
+
+/// Subcarrier numbers in frequency order, i.e. the axis labels for `to_frequency_order` output
+pub fn frequency_axis(count: usize, cwb: u32) -> Vec<i32> {
+    let positions: Vec<usize> = (0..count).collect();
+    to_frequency_order(&positions, cwb)
+        .into_iter()
+        .map(|i| frequency_index(i, count, cwb))
+        .collect()
+}
+
+/// Wraps an angle to -PI..PI
+fn wrap_angle(angle: f64) -> f64 {
+    use std::f64::consts::{PI, TAU};
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+/// Phase of each (I, Q) pair, unwrapped along the slice (neighbours never differ by more than PI).
+/// Pass values in frequency order (see `to_frequency_order`). Zero-amplitude entries (DC and guard
+/// nulls) have no phase: they are skipped while unwrapping and then linearly interpolated from
+/// the nearest valid neighbours (or copied from the nearest one at the edges).
+pub fn unwrap_phase(iq: &[(f64, f64)]) -> Vec<f64> {
+    let valid: Vec<usize> = (0..iq.len()).filter(|&s| iq[s].0 != 0.0 || iq[s].1 != 0.0).collect();
+    let mut out = vec![0.0; iq.len()];
+
+    // 1. Unwrap the subcarriers that carry a phase
+    let mut prev: Option<f64> = None;
+    for &s in &valid {
+        let wrapped = iq[s].1.atan2(iq[s].0);
+        let value = match prev {
+            Some(p) => p + wrap_angle(wrapped - p),
+            None => wrapped,
+        };
+        out[s] = value;
+        prev = Some(value);
+    }
+
+    // 2. Fill the nulls from their neighbours
+    for s in 0..iq.len() {
+        let next = valid.partition_point(|&v| v < s);
+        if valid.get(next) == Some(&s) {
+            continue;
+        }
+        let before = next.checked_sub(1).map(|i| valid[i]);
+        out[s] = match (before, valid.get(next)) {
+            (Some(a), Some(&b)) => out[a] + (out[b] - out[a]) * (s - a) as f64 / (b - a) as f64,
+            (Some(a), None) => out[a],
+            (None, Some(&b)) => out[b],
+            (None, None) => 0.0,
+        };
+    }
+    out
+}
+
+/// Mean over subcarriers of the standard deviation of each subcarrier's amplitude across
+/// `packets`. Near zero in a static room, it jumps when something moves. Only the subcarriers
+/// present in every packet count; fewer than 2 packets give 0.0.
+pub fn motion_energy(packets: &[&CsiData]) -> f32 {
+    let sc_count = packets.iter().map(|p| p.csi_raw_data.len() / 2).min().unwrap_or(0);
+    if packets.len() < 2 || sc_count == 0 {
+        return 0.0;
+    }
+
+    let n = packets.len() as f64;
+    let total: f64 = (0..sc_count)
+        .map(|s| {
+            let amps = packets.iter().map(|p| {
+                let (i_val, q_val) = (p.csi_raw_data[s * 2] as f64, p.csi_raw_data[s * 2 + 1] as f64);
+                (i_val * i_val + q_val * q_val).sqrt()
+            });
+            let (sum, sum_sq) = amps.fold((0.0, 0.0), |(a, b), amp| (a + amp, b + amp * amp));
+            let mean = sum / n;
+            (sum_sq / n - mean * mean).max(0.0).sqrt()
+        })
+        .sum();
+    (total / sc_count as f64) as f32
+}
+
 impl CsiData {
-    pub fn parse(input: &str) -> Result<Self, String> {
+    /// Parses one firmware block. Metadata fields that fail to parse keep their defaults
+    /// (truncated reads are common at startup); only a missing/empty/garbled CSI array is fatal.
+    pub fn parse(input: &str) -> Result<Self, CsiParseError> {
         let mut data = CsiData::default();
         let mut lines = input.lines();
 
@@ -47,7 +336,10 @@ impl CsiData {
                             .split(',')
                             .map(|s| s.trim().parse::<i32>())
                             .collect::<Result<Vec<_>, _>>()
-                            .map_err(|e| format!("Failed to parse csi data: {}", e))?;
+                            .map_err(|_| CsiParseError::BadField {
+                                field: "csi raw data".to_string(),
+                                value: content.to_string(),
+                            })?;
                     }
                 }
                 continue;
@@ -58,42 +350,278 @@ impl CsiData {
                 let value = value.trim();
                 match key {
                     "mac" => data.mac = value.to_string(),
-                    "rssi" => {
-                        let val: i32 = value.parse().map_err(|_| "Invalid rssi")?;
-                        data.rssi = if val > 127 { val - 256 } else { val };
-                    }
-                    "rate" => data.rate = value.parse().map_err(|_| "Invalid rate")?,
-                    "noise floor" => {
-                        let val: i32 = value.parse().map_err(|_| "Invalid noise floor")?;
-                        data.noise_floor = if val > 127 { val - 256 } else { val };
-                    }
-                    "channel" => data.channel = value.parse().map_err(|_| "Invalid channel")?,
-                    "timestamp" => data.timestamp = value.parse().map_err(|_| "Invalid timestamp")?,
-                    "sig len" => data.sig_len = value.parse().map_err(|_| "Invalid sig len")?,
-                    "rx state" => data.rx_state = value.parse().map_err(|_| "Invalid rx state")?,
-                    "secondary channel" => {
-                        data.secondary_channel = value.parse().map_err(|_| "Invalid secondary channel")?
-                    }
-                    "sgi" => data.sgi = value.parse().map_err(|_| "Invalid sgi")?,
-                    "ant" => data.ant = value.parse().map_err(|_| "Invalid ant")?,
-                    "ampdu cnt" => data.ampdu_cnt = value.parse().map_err(|_| "Invalid ampdu cnt")?,
-                    "sig_mode" => data.sig_mode = value.parse().map_err(|_| "Invalid sig_mode")?,
-                    "mcs" => data.mcs = value.parse().map_err(|_| "Invalid mcs")?,
-                    "cwb" => data.cwb = value.parse().map_err(|_| "Invalid cwb")?,
-                    "smoothing" => data.smoothing = value.parse().map_err(|_| "Invalid smoothing")?,
-                    "not sounding" => data.not_sounding = value.parse().map_err(|_| "Invalid not sounding")?,
-                    "aggregation" => data.aggregation = value.parse().map_err(|_| "Invalid aggregation")?,
-                    "stbc" => data.stbc = value.parse().map_err(|_| "Invalid stbc")?,
-                    "fec coding" => data.fec_coding = value.parse().map_err(|_| "Invalid fec coding")?,
-                    "sig_len" => data.sig_len_extra = value.parse().map_err(|_| "Invalid sig_len_extra")?,
-                    "data length" => data.data_length = value.parse().map_err(|_| "Invalid data length")?,
+                    "rssi" => if let Ok(val) = value.parse::<i32>() { data.rssi = wrap_i8(val); },
+                    "rate" => if let Ok(val) = value.parse() { data.rate = val; },
+                    "noise floor" => if let Ok(val) = value.parse::<i32>() { data.noise_floor = wrap_i8(val); },
+                    "channel" => if let Ok(val) = value.parse() { data.channel = val; },
+                    "timestamp" => if let Ok(val) = value.parse() { data.timestamp = val; },
+                    "sig len" => if let Ok(val) = value.parse() { data.sig_len = val; },
+                    "rx state" => if let Ok(val) = value.parse() { data.rx_state = val; },
+                    "secondary channel" => if let Ok(val) = value.parse() { data.secondary_channel = val; },
+                    "sgi" => if let Ok(val) = value.parse() { data.sgi = val; },
+                    "ant" => if let Ok(val) = value.parse() { data.ant = val; },
+                    "ampdu cnt" => if let Ok(val) = value.parse() { data.ampdu_cnt = val; },
+                    "sig_mode" => if let Ok(val) = value.parse() { data.sig_mode = val; },
+                    "mcs" => if let Ok(val) = value.parse() { data.mcs = val; },
+                    "cwb" => if let Ok(val) = value.parse() { data.cwb = val; },
+                    "smoothing" => if let Ok(val) = value.parse() { data.smoothing = val; },
+                    "not sounding" => if let Ok(val) = value.parse() { data.not_sounding = val; },
+                    "aggregation" => if let Ok(val) = value.parse() { data.aggregation = val; },
+                    "stbc" => if let Ok(val) = value.parse() { data.stbc = val; },
+                    "fec coding" => if let Ok(val) = value.parse() { data.fec_coding = val; },
+                    "sig_len" => if let Ok(val) = value.parse() { data.sig_len_extra = val; },
+                    "data length" => if let Ok(val) = value.parse() { data.data_length = val; },
                     _ => {} // Ignore unknown fields
                 }
             }
         }
+
+        if data.csi_raw_data.is_empty() {
+            return Err(CsiParseError::NoCsi);
+        }
         Ok(data)
     }
 
+    /// Normalized complex correlation between the subcarrier patterns of two packets.
+    /// |sum(H1 * conj(H2))| / sqrt(sum|H1|^2 * sum|H2|^2), in 0.0 (unrelated) ..= 1.0 (identical shape).
+    /// Packets with differing subcarrier counts (or no data) return 0.0.
+    pub fn coherence_with(&self, other: &CsiData) -> f32 {
+        let a = &self.csi_raw_data;
+        let b = &other.csi_raw_data;
+        if a.len() != b.len() || a.len() < 2 {
+            return 0.0;
+        }
+
+        let mut cross_re = 0.0f64;
+        let mut cross_im = 0.0f64;
+        let mut power_a = 0.0f64;
+        let mut power_b = 0.0f64;
+
+        for s in 0..a.len() / 2 {
+            let (i1, q1) = (a[s * 2] as f64, a[s * 2 + 1] as f64);
+            let (i2, q2) = (b[s * 2] as f64, b[s * 2 + 1] as f64);
+
+            // H1 * conj(H2) = (i1 + j*q1)(i2 - j*q2)
+            cross_re += i1 * i2 + q1 * q2;
+            cross_im += q1 * i2 - i1 * q2;
+            power_a += i1 * i1 + q1 * q1;
+            power_b += i2 * i2 + q2 * q2;
+        }
+
+        let denom = (power_a * power_b).sqrt();
+        if denom <= 0.0 {
+            return 0.0;
+        }
+        ((cross_re * cross_re + cross_im * cross_im).sqrt() / denom).clamp(0.0, 1.0) as f32
+    }
+
+    /// Phase per subcarrier (buffer order) without ±PI jumps between adjacent frequencies.
+    /// Unwrapping runs in frequency order, so it never crosses from the band edge back to DC.
+    pub fn unwrapped_phase(&self) -> Vec<f32> {
+        let iq: Vec<(f64, f64)> = self.csi_raw_data
+            .chunks_exact(2)
+            .map(|pair| (pair[0] as f64, pair[1] as f64))
+            .collect();
+        let positions: Vec<usize> = (0..iq.len()).collect();
+        let positions = to_frequency_order(&positions, self.cwb);
+        let phases = unwrap_phase(&to_frequency_order(&iq, self.cwb));
+
+        let mut out = vec![0.0; iq.len()];
+        for (&position, phase) in positions.iter().zip(phases) {
+            out[position] = phase as f32;
+        }
+        out
+    }
+
+    /// Raw (I, Q) of subcarrier `i` (buffer order), None past the end of the buffer
+    pub fn subcarrier_iq(&self, i: usize) -> Option<(i32, i32)> {
+        match self.csi_raw_data.get(i * 2..i * 2 + 2) {
+            Some(&[i_val, q_val]) => Some((i_val, q_val)),
+            _ => None,
+        }
+    }
+
+    /// Signed subcarrier number (DC = 0) of subcarrier `i` of this packet's buffer
+    pub fn subcarrier_frequency_index(&self, i: usize) -> i32 {
+        frequency_index(i, self.csi_raw_data.len() / 2, self.cwb)
+    }
+
+    /// Per-subcarrier amplitude/phase change from `self` (before) to `later` (after).
+    /// Only the subcarriers present in both packets are compared.
+    pub fn diff(&self, later: &CsiData) -> Vec<SubcarrierDelta> {
+        self.csi_raw_data
+            .chunks_exact(2)
+            .zip(later.csi_raw_data.chunks_exact(2))
+            .enumerate()
+            .map(|(index, (a, b))| {
+                let (i1, q1) = (a[0] as f64, a[1] as f64);
+                let (i2, q2) = (b[0] as f64, b[1] as f64);
+                let amplitude_before = (i1 * i1 + q1 * q1).sqrt();
+                let amplitude_after = (i2 * i2 + q2 * q2).sqrt();
+
+                // Angle of H2 * conj(H1) is the phase change, already wrapped
+                let phase_delta = (q2 * i1 - i2 * q1).atan2(i2 * i1 + q2 * q1);
+
+                SubcarrierDelta {
+                    index,
+                    amplitude_before,
+                    amplitude_after,
+                    amplitude_delta: amplitude_after - amplitude_before,
+                    phase_delta,
+                }
+            })
+            .collect()
+    }
+
+    /// Channel width from `cwb`: 20 or 40 MHz
+    pub fn bandwidth_mhz(&self) -> u32 {
+        if self.cwb == 1 { 40 } else { 20 }
+    }
+
+    /// PHY mode from `sig_mode` (0 = non-HT, i.e. only the legacy LTF; 1 = HT; 3 = VHT)
+    pub fn mode_label(&self) -> &'static str {
+        match self.sig_mode {
+            0 => "LLTF",
+            1 => "HT",
+            3 => "VHT",
+            _ => "?",
+        }
+    }
+
+    /// "above"/"below" for a 40 MHz secondary channel, None without one
+    pub fn secondary_channel_label(&self) -> Option<&'static str> {
+        match self.secondary_channel {
+            1 => Some("above"),
+            2 => Some("below"),
+            _ => None,
+        }
+    }
+
+    /// Subcarrier counts the ESP32 can report for this mode/bandwidth, depending on which
+    /// LTFs (legacy, HT, STBC) the firmware enables
+    pub fn expected_subcarrier_counts(&self) -> &'static [usize] {
+        match (self.sig_mode, self.cwb) {
+            (0, _) => &[64],
+            (_, 1) => &[128, 192, 256],
+            _ => &[64, 128, 192],
+        }
+    }
+
+    /// False if the buffer size doesn't fit the packet's mode (usually a firmware misconfiguration)
+    pub fn subcarrier_count_ok(&self) -> bool {
+        self.csi_raw_data.len().is_multiple_of(2)
+            && self.expected_subcarrier_counts().contains(&(self.csi_raw_data.len() / 2))
+    }
+
+    /// Sum of all subcarrier amplitudes (0 for an all-zero or CSI-less packet)
+    pub fn total_amplitude(&self) -> f64 {
+        self.csi_raw_data
+            .chunks_exact(2)
+            .map(|iq| ((iq[0] as f64).powi(2) + (iq[1] as f64).powi(2)).sqrt())
+            .sum()
+    }
+
+    /// Drops packets whose RSSI or total amplitude is more than `k` sigma from the batch median.
+    /// Needs 3+ packets to judge; if nothing would survive, the batch is returned unchanged.
+    pub fn reject_outliers(packets: &[CsiData], k: f32) -> Vec<CsiData> {
+        if packets.len() < 3 {
+            return packets.to_vec();
+        }
+
+        let rssi: Vec<f64> = packets.iter().map(|p| p.rssi as f64).collect();
+        let amplitude: Vec<f64> = packets.iter().map(|p| p.total_amplitude()).collect();
+        let (rssi_center, rssi_sigma) = median_and_sigma(&rssi);
+        let (amp_center, amp_sigma) = median_and_sigma(&amplitude);
+        let rssi_limit = k as f64 * rssi_sigma.max(MIN_RSSI_SIGMA);
+        let amp_limit = k as f64 * amp_sigma.max(amp_center * MIN_AMPLITUDE_SIGMA_FRACTION).max(1.0);
+
+        let kept: Vec<CsiData> = packets
+            .iter()
+            .zip(rssi.iter().zip(&amplitude))
+            .filter(|(_, (r, a))| (*r - rssi_center).abs() <= rssi_limit && (*a - amp_center).abs() <= amp_limit)
+            .map(|(p, _)| p.clone())
+            .collect();
+
+        if kept.is_empty() { packets.to_vec() } else { kept }
+    }
+
+    /// `average` over the packets that survive `reject_outliers`
+    pub fn average_robust(packets: &[CsiData], k: f32) -> Self {
+        Self::average(&Self::reject_outliers(packets, k))
+    }
+
+    /// Folds one interval's packets into a single snapshot using the chosen mode,
+    /// after dropping outliers if `outlier_sigma` is set
+    pub fn aggregate(packets: &[CsiData], mode: AveragingMode, outlier_sigma: Option<f32>) -> Self {
+        let filtered;
+        let packets = match outlier_sigma {
+            Some(k) => {
+                filtered = Self::reject_outliers(packets, k);
+                &filtered[..]
+            }
+            None => packets,
+        };
+        match mode {
+            AveragingMode::Mean => Self::average(packets),
+            AveragingMode::Incoherent => Self::average_incoherent(packets),
+            AveragingMode::Median => Self::median(packets),
+            AveragingMode::LatestOnly => packets.last().cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Averages magnitude and phase separately instead of summing raw I/Q.
+    /// Each subcarrier gets the mean of the per-packet magnitudes, and the phase of the
+    /// coherent sum, so packets with opposing phase no longer cancel to ~0 amplitude.
+    /// Metadata (RSSI, noise floor, ...) is the same as `average`.
+    pub fn average_incoherent(packets: &[CsiData]) -> Self {
+        let mut result = Self::average(packets);
+        if packets.is_empty() {
+            return result;
+        }
+
+        let sc_count = result.csi_raw_data.len() / 2;
+        let mut raw = Vec::with_capacity(sc_count * 2);
+        for s in 0..sc_count {
+            let mut sum_mag = 0.0f64;
+            let mut sum_i = 0.0f64;
+            let mut sum_q = 0.0f64;
+            let mut n = 0usize;
+
+            for p in packets {
+                if let (Some(&i_val), Some(&q_val)) = (p.csi_raw_data.get(s * 2), p.csi_raw_data.get(s * 2 + 1)) {
+                    let (i_val, q_val) = (i_val as f64, q_val as f64);
+                    sum_mag += (i_val * i_val + q_val * q_val).sqrt();
+                    sum_i += i_val;
+                    sum_q += q_val;
+                    n += 1;
+                }
+            }
+
+            let mag = if n > 0 { sum_mag / n as f64 } else { 0.0 };
+            let phase = sum_q.atan2(sum_i);
+            raw.push((mag * phase.cos()).round() as i32);
+            raw.push((mag * phase.sin()).round() as i32);
+        }
+        result.csi_raw_data = raw;
+        result
+    }
+
+    /// Per-field median (RSSI, noise floor and each raw I/Q component).
+    /// Robust to the odd outlier packet; metadata comes from the most recent packet.
+    pub fn median(packets: &[CsiData]) -> Self {
+        let Some(last) = packets.last() else {
+            return CsiData::default();
+        };
+
+        let mut result = last.clone();
+        result.rssi = median_i32(packets.iter().map(|p| p.rssi).collect());
+        result.noise_floor = median_i32(packets.iter().map(|p| p.noise_floor).collect());
+        result.csi_raw_data = (0..last.csi_raw_data.len())
+            .map(|i| median_i32(packets.iter().filter_map(|p| p.csi_raw_data.get(i).copied()).collect()))
+            .collect();
+        result
+    }
+
     /// Takes a list of raw packets and produces a single "Averaged" packet
     pub fn average(packets: &[CsiData]) -> Self {
         if packets.is_empty() {
@@ -152,4 +680,34 @@ impl CsiData {
             csi_raw_data: sum_csi.iter().map(|&x| (x / count as i64) as i32).collect(),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outlier_rejection_drops_a_zeroed_packet() {
+        let good = |rssi| CsiData { rssi, csi_raw_data: vec![10, 10, 20, 5, -8, 12, 7, -3], ..Default::default() };
+        let mut packets = vec![good(-50), good(-51), good(-50), good(-49)];
+        packets.push(CsiData { rssi: -50, csi_raw_data: vec![0; 8], ..Default::default() });
+
+        assert_eq!(CsiData::reject_outliers(&packets, 3.0).len(), 4);
+        assert_eq!(CsiData::average_robust(&packets, 3.0).csi_raw_data, good(-50).csi_raw_data);
+        assert_ne!(CsiData::average(&packets).csi_raw_data, good(-50).csi_raw_data);
+        // Too few packets to judge: nothing is dropped
+        assert_eq!(CsiData::reject_outliers(&packets[3..], 3.0).len(), 2);
+    }
+
+    #[test]
+    fn incoherent_average_keeps_the_magnitude_of_opposite_phases() {
+        let packets = vec![
+            CsiData { csi_raw_data: vec![3, 4], ..Default::default() },
+            CsiData { csi_raw_data: vec![-3, -4], ..Default::default() },
+        ];
+        // The coherent mean cancels to nothing; the incoherent one keeps |3 + 4i| = 5
+        assert_eq!(CsiData::average(&packets).csi_raw_data, vec![0, 0]);
+        let raw = CsiData::average_incoherent(&packets).csi_raw_data;
+        assert_eq!(raw[0] * raw[0] + raw[1] * raw[1], 25);
+    }
+}
@@ -6,12 +6,127 @@ use std::collections::VecDeque;
 use std::error::Error;
 use std::fs::File;
 
+// Helper struct for CSV serialization to handle Vec<i32>
+#[derive(serde::Serialize)]
+struct CsiDataCsv<'a> {
+    mac: &'a str,
+    rssi: i32,
+    rate: u32,
+    noise_floor: i32,
+    channel: u32,
+    timestamp: u64,
+    sig_len: u32,
+    rx_state: u32,
+    secondary_channel: u32,
+    sgi: u32,
+    ant: u32,
+    ampdu_cnt: u32,
+    sig_mode: u32,
+    mcs: u32,
+    cwb: u32,
+    smoothing: u32,
+    not_sounding: u32,
+    aggregation: u32,
+    stbc: u32,
+    fec_coding: u32,
+    sig_len_extra: u32,
+    data_length: u32,
+    csi_raw_data: String,
+}
+
+/// Opens a CSV file in the export format (header is written with the first row)
+pub fn create_csv_writer(filename: &str) -> Result<csv::Writer<File>, Box<dyn Error>> {
+    Ok(csv::Writer::from_writer(File::create(filename)?))
+}
+
+/// Appends one packet in the export format. Used for bulk export and streaming capture.
+pub fn write_csv_row(wtr: &mut csv::Writer<File>, data: &CsiData) -> Result<(), Box<dyn Error>> {
+    let csv_row = CsiDataCsv {
+        mac: &data.mac,
+        rssi: data.rssi,
+        rate: data.rate,
+        noise_floor: data.noise_floor,
+        channel: data.channel,
+        timestamp: data.timestamp,
+        sig_len: data.sig_len,
+        rx_state: data.rx_state,
+        secondary_channel: data.secondary_channel,
+        sgi: data.sgi,
+        ant: data.ant,
+        ampdu_cnt: data.ampdu_cnt,
+        sig_mode: data.sig_mode,
+        mcs: data.mcs,
+        cwb: data.cwb,
+        smoothing: data.smoothing,
+        not_sounding: data.not_sounding,
+        aggregation: data.aggregation,
+        stbc: data.stbc,
+        fec_coding: data.fec_coding,
+        sig_len_extra: data.sig_len_extra,
+        data_length: data.data_length,
+        csi_raw_data: format!("{:?}", data.csi_raw_data),
+    };
+    wtr.serialize(csv_row)?;
+    Ok(())
+}
+
+// One (packet, subcarrier) row of the tidy export
+#[derive(serde::Serialize)]
+struct TidyRow {
+    timestamp: u64,
+    subcarrier_index: usize,
+    amplitude: f64,
+    phase: f64, // Radians, -PI to PI
+    rssi: i32,
+}
+
+// One subcarrier row of a single-packet export
+#[derive(serde::Serialize)]
+struct PacketRow {
+    subcarrier_index: usize, // Position in the ESP buffer
+    subcarrier: i32,         // Physical subcarrier number, DC = 0
+    i: i32,
+    q: i32,
+    amplitude: f64,
+    phase: f64, // Radians, -PI to PI
+}
+
+/// Writes one packet as one row per subcarrier (I, Q, amplitude, phase), e.g. for a spreadsheet.
+/// A packet without CSI data is an error rather than an empty file.
+pub fn export_packet_csv(data: &CsiData, path: &str) -> Result<(), Box<dyn Error>> {
+    if data.csi_raw_data.len() < 2 {
+        return Err("packet has no CSI data".into());
+    }
+    let mut wtr = create_csv_writer(path)?;
+
+    for (subcarrier_index, iq) in data.csi_raw_data.chunks_exact(2).enumerate() {
+        let (i_val, q_val) = (iq[0] as f64, iq[1] as f64);
+        wtr.serialize(PacketRow {
+            subcarrier_index,
+            subcarrier: data.subcarrier_frequency_index(subcarrier_index),
+            i: iq[0],
+            q: iq[1],
+            amplitude: (i_val * i_val + q_val * q_val).sqrt(),
+            phase: q_val.atan2(i_val),
+        })?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
 pub struct Dataloader {
     // Changed from random-access Vec to a Queue
     pub queue: VecDeque<CsiData>,
     pub history: Vec<CsiData>,
 }
 
+impl Default for Dataloader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Dataloader {
     pub fn new() -> Self {
         Self {
@@ -34,73 +149,48 @@ impl Dataloader {
 
     /// Exports the entire history of CsiData to a CSV file.
     pub fn export_history_to_csv(&self, filename: &str) -> Result<(), Box<dyn Error>> {
-        let file = File::create(filename)?;
-        let mut wtr = csv::Writer::from_writer(file);
+        let mut wtr = create_csv_writer(filename)?;
 
-        // Define a helper struct for CSV serialization to handle Vec<i32>
-        #[derive(serde::Serialize)]
-        struct CsiDataCsv<'a> {
-            mac: &'a str,
-            rssi: i32,
-            rate: u32,
-            noise_floor: i32,
-            channel: u32,
-            timestamp: u64,
-            sig_len: u32,
-            rx_state: u32,
-            secondary_channel: u32,
-            sgi: u32,
-            ant: u32,
-            ampdu_cnt: u32,
-            sig_mode: u32,
-            mcs: u32,
-            cwb: u32,
-            smoothing: u32,
-            not_sounding: u32,
-            aggregation: u32,
-            stbc: u32,
-            fec_coding: u32,
-            sig_len_extra: u32,
-            data_length: u32,
-            csi_raw_data: String,
+        for data in &self.history {
+            write_csv_row(&mut wtr, data)?;
         }
 
-        for data in &self.history {
-            let csv_row = CsiDataCsv {
-                mac: &data.mac,
-                rssi: data.rssi,
-                rate: data.rate,
-                noise_floor: data.noise_floor,
-                channel: data.channel,
-                timestamp: data.timestamp,
-                sig_len: data.sig_len,
-                rx_state: data.rx_state,
-                secondary_channel: data.secondary_channel,
-                sgi: data.sgi,
-                ant: data.ant,
-                ampdu_cnt: data.ampdu_cnt,
-                sig_mode: data.sig_mode,
-                mcs: data.mcs,
-                cwb: data.cwb,
-                smoothing: data.smoothing,
-                not_sounding: data.not_sounding,
-                aggregation: data.aggregation,
-                stbc: data.stbc,
-                fec_coding: data.fec_coding,
-                sig_len_extra: data.sig_len_extra,
-                data_length: data.data_length,
-                csi_raw_data: format!("{:?}", data.csi_raw_data),
-            };
-            wtr.serialize(csv_row)?;
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// Exports the history in "long" format: one row per (packet, subcarrier) with
+    /// amplitude and phase derived from the interleaved I/Q pairs.
+    /// Packets without CSI data are skipped.
+    pub fn export_tidy_csv(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let mut wtr = create_csv_writer(path)?;
+
+        for data in self.history.iter().filter(|d| !d.csi_raw_data.is_empty()) {
+            for (subcarrier_index, iq) in data.csi_raw_data.chunks_exact(2).enumerate() {
+                let (i_val, q_val) = (iq[0] as f64, iq[1] as f64);
+                wtr.serialize(TidyRow {
+                    timestamp: data.timestamp,
+                    subcarrier_index,
+                    amplitude: (i_val * i_val + q_val * q_val).sqrt(),
+                    phase: q_val.atan2(i_val),
+                    rssi: data.rssi,
+                })?;
+            }
         }
 
         wtr.flush()?;
         Ok(())
     }
 
+    /// Loads a CSV capture into history. Accepts this tool's own export format and the
+    /// native CSV of ESP-IDF's csi_recv example (detected from the header row).
     pub fn import_history_from_csv(&mut self, filename: &str) -> Result<(), Box<dyn Error>> {
         let file = File::open(filename)?;
-        let mut rdr = csv::Reader::from_reader(file);
+        let mut rdr = csv::ReaderBuilder::new().flexible(true).from_reader(file);
+
+        if is_esp_idf_header(rdr.headers()?) {
+            return self.import_esp_idf_records(&mut rdr);
+        }
 
         #[derive(serde::Deserialize)]
         struct CsiDataCsv {
@@ -132,15 +222,7 @@ impl Dataloader {
         for result in rdr.deserialize() {
             let record: CsiDataCsv = result?;
 
-            // Parse the "[1, 2, 3]" string
-            let raw_str = record.csi_raw_data.trim().trim_start_matches('[').trim_end_matches(']');
-            let csi_vec: Vec<i32> = if raw_str.is_empty() {
-                Vec::new()
-            } else {
-                raw_str.split(',')
-                    .map(|s| s.trim().parse::<i32>())
-                    .collect::<Result<Vec<_>, _>>()?
-            };
+            let csi_vec = parse_csi_array(&record.csi_raw_data)?;
 
             let data = CsiData {
                 mac: record.mac,
@@ -172,4 +254,79 @@ impl Dataloader {
         }
         Ok(())
     }
+
+    /// ESP-IDF csi_recv rows: "CSI_DATA,id,mac,rssi,...,[i0,q0,i1,q1,...]".
+    /// Columns are looked up by name, so older/newer example versions with extra or
+    /// reordered columns still load. Missing numeric columns default to 0.
+    fn import_esp_idf_records(&mut self, rdr: &mut csv::Reader<File>) -> Result<(), Box<dyn Error>> {
+        let headers = rdr.headers()?.clone();
+        let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.trim()));
+
+        let type_idx = column(&["type"]);
+        // The CSI array is the last column; the header calls it "data" or "CSI_DATA"
+        let data_idx = column(&["data", "CSI_DATA"]).unwrap_or(headers.len().saturating_sub(1));
+
+        for result in rdr.records() {
+            let record = result?;
+
+            // Firmware logs can be interleaved with the CSI lines; only keep CSI rows
+            if let Some(idx) = type_idx && record.get(idx).map(str::trim) != Some("CSI_DATA") {
+                continue;
+            }
+
+            let text = |names: &[&str]| column(names).and_then(|i| record.get(i)).map(str::trim).unwrap_or("");
+            let uint = |names: &[&str]| text(names).parse::<u32>().unwrap_or(0);
+            let int = |names: &[&str]| text(names).parse::<i32>().unwrap_or(0);
+
+            // An unquoted array is split across the trailing fields, so glue them back together
+            let raw = record.iter().skip(data_idx).collect::<Vec<_>>().join(",");
+
+            let data = CsiData {
+                mac: text(&["mac"]).to_string(),
+                rssi: int(&["rssi"]),
+                rate: uint(&["rate"]),
+                noise_floor: int(&["noise_floor"]),
+                channel: uint(&["channel"]),
+                timestamp: text(&["local_timestamp", "timestamp"]).parse().unwrap_or(0),
+                sig_len: uint(&["sig_len"]),
+                rx_state: uint(&["rx_state"]),
+                secondary_channel: uint(&["secondary_channel"]),
+                sgi: uint(&["sgi"]),
+                ant: uint(&["ant"]),
+                ampdu_cnt: uint(&["ampdu_cnt"]),
+                sig_mode: uint(&["sig_mode"]),
+                mcs: uint(&["mcs"]),
+                cwb: uint(&["bandwidth", "cwb"]),
+                smoothing: uint(&["smoothing"]),
+                not_sounding: uint(&["not_sounding"]),
+                aggregation: uint(&["aggregation"]),
+                stbc: uint(&["stbc"]),
+                fec_coding: uint(&["fec_coding"]),
+                sig_len_extra: 0,
+                data_length: uint(&["len", "data_length"]),
+                csi_raw_data: parse_csi_array(&raw)?,
+            };
+
+            self.history.push(data);
+        }
+        Ok(())
+    }
+}
+
+/// True for the header written by ESP-IDF's csi_recv example ("type,id,mac,rssi,...")
+fn is_esp_idf_header(headers: &csv::StringRecord) -> bool {
+    let has = |name: &str| headers.iter().any(|h| h.trim() == name);
+    has("type") && (has("data") || has("CSI_DATA")) && !has("csi_raw_data")
+}
+
+/// Parses a "[1, 2, 3]" CSI array (empty entries from trailing commas are ignored)
+fn parse_csi_array(raw: &str) -> Result<Vec<i32>, std::num::ParseIntError> {
+    raw.trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::parse::<i32>)
+        .collect()
 }
@@ -6,9 +6,35 @@ use rerun::{RecordingStream, Tensor, TensorData, TensorBuffer};
 
 use crate::rerun_stream::CsiFrame;
 
+// Default hop as a fraction of the window (1/4 = 75% overlap, common STFT practice)
+const DEFAULT_HOP_DIVISOR: usize = 4;
+
+// Speed of light (m/s), for channel wavelengths
+const SPEED_OF_LIGHT: f64 = 299_792_458.0;
+
+/// Wavelength (m) of a Wi-Fi channel's center frequency. Channels 1-14 are 2.4 GHz, higher
+/// numbers 5 GHz; 0 (not reported) is taken as channel 6.
+pub fn wavelength_m(channel: u32) -> f64 {
+    let mhz = match channel {
+        0 => 2437.0,
+        14 => 2484.0,
+        1..=13 => 2407.0 + 5.0 * channel as f64,
+        _ => 5000.0 + 5.0 * channel as f64,
+    };
+    SPEED_OF_LIGHT / (mhz * 1e6)
+}
+
+/// Radial speed (m/s) of a reflector that shifts the signal by `doppler_hz`. The reflected path
+/// changes by twice the distance the reflector moves, hence the factor 2: v = f_D * λ / 2.
+pub fn velocity_mps(doppler_hz: f64, channel: u32) -> f64 {
+    doppler_hz * wavelength_m(channel) / 2.0
+}
+
 pub struct DopplerSpectrogram {
     window_size: usize,
     history_size: usize,
+    hop_size: usize, // Samples between two FFTs (1 = a column per pushed frame)
+    pending: usize, // Samples pushed since the last FFT
     buffer: VecDeque<f32>, // Sliding window of averaged amplitudes
     spectrogram: VecDeque<Vec<f32>>, // History of FFT frames (Time x Frequency)
     planner: FftPlanner<f32>,
@@ -25,6 +51,8 @@ impl DopplerSpectrogram {
         Self {
             window_size,
             history_size,
+            hop_size: (window_size / DEFAULT_HOP_DIVISOR).max(1),
+            pending: 0,
             buffer: VecDeque::with_capacity(window_size),
             spectrogram: VecDeque::with_capacity(history_size),
             planner: FftPlanner::new(),
@@ -32,6 +60,49 @@ impl DopplerSpectrogram {
         }
     }
 
+    /// Sets how many samples are pushed between two FFTs. Rejects 0 and hops longer than the
+    /// window, which would skip samples entirely.
+    pub fn set_hop_size(&mut self, hop_size: usize) -> Result<(), String> {
+        if hop_size == 0 || hop_size > self.window_size {
+            return Err(format!("hop size must be 1..={} (window size), got {}", self.window_size, hop_size));
+        }
+        self.hop_size = hop_size;
+        self.pending = 0;
+        Ok(())
+    }
+
+    pub fn hop_size(&self) -> usize {
+        self.hop_size
+    }
+
+    /// FFT columns currently held (at most history_size)
+    pub fn frame_count(&self) -> usize {
+        self.spectrogram.len()
+    }
+
+    /// Strongest frequency bin of the newest FFT column. The DC bin (only rounding is left there,
+    /// the window mean is removed before the FFT) is skipped. None before the first column or if
+    /// every other bin is 0.
+    pub fn peak_bin(&self) -> Option<usize> {
+        self.spectrogram.back()?
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, magnitude)| **magnitude > 0.0)
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(bin, _)| bin)
+    }
+
+    /// Frequency (Hz) of a bin when frames are pushed `sample_rate_hz` times a second
+    pub fn bin_hz(&self, bin: usize, sample_rate_hz: f64) -> f64 {
+        bin as f64 * sample_rate_hz / self.window_size as f64
+    }
+
+    /// peak_bin in Hz
+    pub fn peak_hz(&self, sample_rate_hz: f64) -> Option<f64> {
+        self.peak_bin().map(|bin| self.bin_hz(bin, sample_rate_hz))
+    }
+
     pub fn push_frame(&mut self, csi_frame: &CsiFrame) {
         // 1. Preprocessing
         // Compute magnitude for each subcarrier and take the mean
@@ -44,8 +115,10 @@ impl DopplerSpectrogram {
         self.buffer.push_back(mean_amp);
 
         // 2. Sliding Window & FFT
-        // Only compute FFT if we have enough samples
-        if self.buffer.len() == self.window_size {
+        // Only compute FFT once the window is full, then every hop_size samples
+        self.pending += 1;
+        if self.buffer.len() == self.window_size && (self.frame_count() == 0 || self.pending >= self.hop_size) {
+            self.pending = 0;
             self.generate_fft();
         }
     }
@@ -53,10 +126,13 @@ impl DopplerSpectrogram {
     fn generate_fft(&mut self) {
         let fft = self.planner.plan_fft_forward(self.window_size);
         
-        // Prepare input buffer with Hann window applied
+        // Prepare input buffer: mean removed, then Hann window applied.
+        // The mean amplitude is far larger than any motion; windowed as is, its main lobe
+        // spreads into bin 1 and outweighs the actual Doppler peak.
+        let mean = self.buffer.iter().sum::<f32>() / self.window_size as f32;
         let mut buffer: Vec<Complex<f32>> = self.buffer.iter()
             .zip(self.hann_window.iter())
-            .map(|(&val, &win)| Complex::new(val * win, 0.0))
+            .map(|(&val, &win)| Complex::new((val - mean) * win, 0.0))
             .collect();
 
         // 3. Compute FFT
@@ -90,6 +166,57 @@ impl DopplerSpectrogram {
         self.spectrogram.push_back(magnitudes);
     }
 
+    /// Writes the spectrogram as CSV: one row per FFT frame (oldest first), one column per
+    /// frequency bin (0 = DC), normalized magnitudes. An empty spectrogram is an error.
+    pub fn to_csv(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if self.spectrogram.is_empty() {
+            return Err(self.empty_error().into());
+        }
+
+        let mut wtr = csv::Writer::from_path(path)?;
+        let bins = self.spectrogram[0].len();
+        let mut header = vec!["frame".to_string()];
+        header.extend((0..bins).map(|bin| format!("bin_{}", bin)));
+        wtr.write_record(&header)?;
+
+        for (frame, magnitudes) in self.spectrogram.iter().enumerate() {
+            let mut row = vec![frame.to_string()];
+            row.extend(magnitudes.iter().map(|m| m.to_string()));
+            wtr.write_record(&row)?;
+        }
+
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// Writes the spectrogram as a grayscale PNG: time to the right, DC at the bottom,
+    /// one pixel per frame and bin. An empty spectrogram is an error.
+    #[cfg(feature = "screenshot")]
+    pub fn to_png(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if self.spectrogram.is_empty() {
+            return Err(self.empty_error().into());
+        }
+
+        let height = self.spectrogram[0].len();
+        let width = self.spectrogram.len();
+        let mut pixels = Vec::with_capacity(width * height);
+        for freq_idx in (0..height).rev() {
+            for frame in &self.spectrogram {
+                let value = frame.get(freq_idx).copied().unwrap_or(0.0);
+                pixels.push((value.clamp(0.0, 1.0) * 255.0) as u8);
+            }
+        }
+
+        let image = image::GrayImage::from_raw(width as u32, height as u32, pixels)
+            .ok_or("spectrogram rows have different lengths")?;
+        image.save(path)?;
+        Ok(())
+    }
+
+    fn empty_error(&self) -> String {
+        format!("Doppler spectrogram is empty (needs {} packets first)", self.window_size)
+    }
+
     #[cfg(feature = "rerun")]
     pub fn to_rerun(&self, rec: &RecordingStream) {
         if self.spectrogram.is_empty() {
@@ -132,9 +259,69 @@ impl DopplerSpectrogram {
             TensorBuffer::U8(img_data.into())
         );
 
-        let _ = rec.log(
-            "csi/doppler_spectrogram",
-            &Tensor::new(tensor_data),
-        );
+        crate::rerun_stream::log_or_warn(rec, "csi/doppler_spectrogram", &Tensor::new(tensor_data));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::csi_data::CsiData;
+
+    #[test]
+    fn fft_runs_once_per_hop() {
+        let frame = CsiFrame::from(&CsiData { csi_raw_data: [3, 4].repeat(64), ..Default::default() });
+
+        let mut doppler = DopplerSpectrogram::new(16, 100);
+        assert_eq!(doppler.hop_size(), 4);
+        assert!(doppler.set_hop_size(17).is_err());
+        assert!(doppler.set_hop_size(0).is_err());
+        doppler.set_hop_size(8).unwrap();
+
+        // First column once the window fills, then one per hop
+        for _ in 0..16 + 24 {
+            doppler.push_frame(&frame);
+        }
+        assert_eq!(doppler.frame_count(), 4);
+    }
+
+    #[test]
+    fn peak_skips_dc_and_converts_to_hz() {
+        let mut doppler = DopplerSpectrogram::new(16, 100);
+        assert_eq!(doppler.peak_bin(), None);
+
+        // Mean amplitude oscillating 3 times per window, on top of a large constant (DC) level
+        for n in 0..16 {
+            let level = 50.0 + 10.0 * (std::f64::consts::TAU * 3.0 * n as f64 / 16.0).cos();
+            let frame = CsiFrame::from(&CsiData { csi_raw_data: [level as i32, 0].repeat(64), ..Default::default() });
+            doppler.push_frame(&frame);
+        }
+        assert_eq!(doppler.peak_bin(), Some(3));
+        assert!((doppler.peak_hz(10.0).unwrap() - 1.875).abs() < 1e-9);
+
+        // 2.4 GHz channel 6: λ ≈ 12.3 cm, so 1 Hz is ~6 cm/s
+        assert!((velocity_mps(1.0, 6) - 0.0615).abs() < 0.001);
+    }
+
+    #[test]
+    fn doppler_spectrogram_exports_to_csv() {
+        let path = std::env::temp_dir().join(format!("esp-csi-tui-doppler-{}.csv", std::process::id()));
+        let path = path.to_str().expect("temp dir is valid UTF-8");
+
+        let mut doppler = DopplerSpectrogram::new(16, 100);
+        let err = doppler.to_csv(path).expect_err("nothing to export yet");
+        assert!(err.to_string().contains("empty"));
+
+        let frame = CsiFrame::from(&CsiData { csi_raw_data: [3, 4].repeat(64), ..Default::default() });
+        for _ in 0..16 + 8 {
+            doppler.push_frame(&frame);
+        }
+        doppler.to_csv(path).expect("export");
+        let text = std::fs::read_to_string(path).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let mut lines = text.lines();
+        assert_eq!(lines.next().unwrap().split(',').count(), 1 + 8); // frame + window/2 bins
+        assert_eq!(lines.count(), doppler.frame_count());
     }
 }
@@ -0,0 +1,65 @@
+// --- File: src/clipboard.rs ---
+// --- Purpose: Copies text (the layout JSON) to the system clipboard and reads it back ---
+//
+// The system clipboard needs the "clipboard" feature (arboard) and a display server. Without
+// either (SSH, headless, plain build) the text goes to a file in the temp directory instead,
+// and pasting reads that file back, so the same keys still work on one machine.
+
+use std::io;
+use std::path::PathBuf;
+
+// File used when there is no clipboard (inside std::env::temp_dir())
+const FALLBACK_FILE: &str = "esp-csi-tui-layout.json";
+
+// Kept alive for the whole session: on X11/Wayland the owning process serves the clipboard,
+// so dropping the handle right after copying would empty it again
+#[cfg(feature = "clipboard")]
+static CLIPBOARD: std::sync::Mutex<Option<arboard::Clipboard>> = std::sync::Mutex::new(None);
+
+/// Where `copy` put the text
+pub enum CopyTarget {
+    Clipboard,
+    File(PathBuf),
+}
+
+pub fn fallback_path() -> PathBuf {
+    std::env::temp_dir().join(FALLBACK_FILE)
+}
+
+/// Puts `text` on the system clipboard, or writes it to `fallback_path()` if there is none
+pub fn copy(text: &str) -> io::Result<CopyTarget> {
+    #[cfg(feature = "clipboard")]
+    {
+        match with_clipboard(|cb| cb.set_text(text)) {
+            Ok(()) => return Ok(CopyTarget::Clipboard),
+            Err(e) => tracing::info!(error = %e, "no system clipboard, writing layout to a file"),
+        }
+    }
+    let path = fallback_path();
+    std::fs::write(&path, text)?;
+    Ok(CopyTarget::File(path))
+}
+
+/// Reads the system clipboard, or `fallback_path()` if there is no clipboard
+pub fn paste() -> io::Result<String> {
+    #[cfg(feature = "clipboard")]
+    {
+        match with_clipboard(|cb| cb.get_text()) {
+            Ok(text) => return Ok(text),
+            Err(e) => tracing::info!(error = %e, "no system clipboard, reading layout from a file"),
+        }
+    }
+    std::fs::read_to_string(fallback_path())
+}
+
+#[cfg(feature = "clipboard")]
+fn with_clipboard<T>(f: impl FnOnce(&mut arboard::Clipboard) -> Result<T, arboard::Error>) -> Result<T, arboard::Error> {
+    let mut guard = CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner());
+    if guard.is_none() {
+        *guard = Some(arboard::Clipboard::new()?);
+    }
+    match guard.as_mut() {
+        Some(cb) => f(cb),
+        None => Err(arboard::Error::ClipboardNotSupported),
+    }
+}
@@ -17,8 +17,28 @@ pub fn init() -> std::io::Result<()> {
     Ok(())
 }
 
+/// Strips path separators and control characters so a template name can't escape TEMPLATE_DIR.
+/// Leading dots are dropped too ("../foo" -> "foo", no hidden files). May return an empty string.
+pub fn sanitize_template_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .filter(|c| *c != '/' && *c != '\\' && !c.is_control())
+        .collect();
+    cleaned.trim().trim_start_matches('.').trim().to_string()
+}
+
+/// Checks whether a template with this name is already on disk
+pub fn template_exists(name: &str) -> bool {
+    let filename = format!("{}/{}.json", TEMPLATE_DIR, sanitize_template_name(name));
+    Path::new(&filename).exists()
+}
+
 /// Saves the current layout tree to a JSON file
 pub fn save_template(name: &str, manager: &TilingManager) -> std::io::Result<()> {
+    let name = sanitize_template_name(name);
+    if name.is_empty() {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Template name is empty"));
+    }
     init()?;
     let json = serde_json::to_string_pretty(manager)?;
     let filename = format!("{}/{}.json", TEMPLATE_DIR, name);
@@ -34,6 +54,15 @@ pub fn load_template(filename: &str) -> std::io::Result<TilingManager> {
     Ok(manager)
 }
 
+/// Removes a template file (as listed by list_templates, e.g. "lab.json").
+/// The default flag lives inside the file, so deleting the default template leaves no default.
+pub fn delete_template(filename: &str) -> std::io::Result<()> {
+    if sanitize_template_name(filename) != filename {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid template name"));
+    }
+    fs::remove_file(format!("{}/{}", TEMPLATE_DIR, filename))
+}
+
 /// Lists all available .json files with their default status
 /// Returns: Vec<(filename, is_default)>
 pub fn list_templates() -> std::io::Result<Vec<(String, bool)>> {
@@ -42,15 +71,14 @@ pub fn list_templates() -> std::io::Result<Vec<(String, bool)>> {
     for entry in fs::read_dir(TEMPLATE_DIR)? {
         let entry = entry?;
         let path = entry.path();
-        if let Some(ext) = path.extension() {
-            if ext == "json" {
-                if let Some(name) = path.file_name() {
-                    let name_str = name.to_string_lossy().to_string();
-                    // Peek inside to see if it's default
-                    let is_default = is_template_default(&name_str).unwrap_or(false);
-                    files.push((name_str, is_default));
-                }
-            }
+        if let Some(ext) = path.extension() && ext == "json" && let Some(name) = path.file_name() {
+            let name_str = name.to_string_lossy().to_string();
+            // Peek inside to see if it's default (unreadable files are listed, never default)
+            let is_default = is_template_default(&name_str).unwrap_or_else(|e| {
+                tracing::warn!(file = %name_str, error = %e, "reading template failed");
+                false
+            });
+            files.push((name_str, is_default));
         }
     }
     files.sort_by(|a, b| a.0.cmp(&b.0));
@@ -65,11 +93,13 @@ fn is_template_default(filename: &str) -> std::io::Result<bool> {
 
 /// Iterates through all templates to find the one marked default
 pub fn load_startup_template() -> Option<TilingManager> {
-    if let Ok(files) = list_templates() {
+    let files = list_templates().inspect_err(|e| tracing::warn!(dir = TEMPLATE_DIR, error = %e, "listing templates failed"));
+    if let Ok(files) = files {
         for (filename, is_default) in files {
             if is_default {
-                if let Ok(tm) = load_template(&filename) {
-                    return Some(tm);
+                match load_template(&filename) {
+                    Ok(tm) => return Some(tm),
+                    Err(e) => tracing::warn!(file = %filename, error = %e, "loading default template failed"),
                 }
             }
         }
@@ -1,126 +1,397 @@
-use std::io::{self, BufRead, BufReader};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info, warn};
 
 use crate::{App, backend};
 
 pub use backend::csi_data;
 pub use csi_data::CsiData;
 
-pub fn esp_com(app: Arc<Mutex<App>>) {
-    // Switch to mock data for now
-    // mock_esp_com(app);
+/// Where packets come from. Each variant runs on its own background thread via `run_source`.
+pub enum DataSource {
+    Serial,                // First USB serial port (ESP32 running csi-cli)
+    Replay(Vec<CsiData>),  // Packets loaded from a CSV capture
+    Mock { seed: u64 },    // Synthetic, reproducible packets (--mock [--seed N])
+}
 
-    // Real ESP implementation
-    let ports = serialport::available_ports().unwrap_or_default();
+/// Feeds `app` from the given source. Blocks; call from a spawned thread.
+/// Sets `App::source_stopped` when the source gives up (e.g. after flushing on quit).
+pub fn run_source(app: Arc<Mutex<App>>, source: DataSource) {
+    match source {
+        DataSource::Serial => esp_com(Arc::clone(&app)),
+        DataSource::Replay(packets) => run_replay(Arc::clone(&app), packets),
+        DataSource::Mock { seed } => run_mock(Arc::clone(&app), seed),
+    }
+    if let Ok(mut app) = app.lock() {
+        app.source_stopped = true;
+    }
+}
 
-    // Find first USB port, or fallback to default /dev/ttyUSB0
-    let port_name = ports
-        .iter()
-        .find(|p| matches!(p.port_type, serialport::SerialPortType::UsbPort(_)))
-        .map(|p| p.port_name.clone())
-        .unwrap_or_else(|| "/dev/ttyUSB0".to_string());
+// Shown (App::serial_status) while no port can be used; the thread keeps retrying
+pub const NO_SERIAL_DEVICE: &str = "No serial device — plug in an ESP32, or restart with a capture.csv or --mock";
+const SERIAL_RETRY_INTERVAL: Duration = Duration::from_secs(2);
+const BAUD_RATE: u32 = 115200;
 
-    let baud_rate = 115200;
+// Tried when enumeration finds no USB port (some containers hide the USB metadata)
+const FALLBACK_PORT: &str = "/dev/ttyUSB0";
 
-    let port = serialport::new(&port_name, baud_rate)
-        .timeout(Duration::from_millis(1000))
-        .open();
+/// Where the serial thread is in its connect / read / reconnect cycle
+enum LinkState {
+    Searching,                                          // No port opened yet
+    Connected(Box<dyn serialport::SerialPort>, String), // Reading from this port
+    Reconnecting(String),                               // This port failed mid-capture; waiting for it (or its replacement)
+}
 
-    match port {
-        Ok(mut port) => {
-            let mut reader = BufReader::new(port.try_clone().expect("Failed to clone port"));
+/// Why read_serial stopped
+enum ReadEnd {
+    Quit,
+    Lost(io::Error), // Unplugged, or the port handle went stale
+}
 
-            loop {
-                // Check for Reset Command
-                let should_reset = if let Ok(app) = app.lock() {
-                    app.should_reset_esp
-                } else {
-                    false
+pub fn esp_com(app: Arc<Mutex<App>>) {
+    // Real ESP implementation (hardware-free alternative: DataSource::Mock)
+    let mut state = LinkState::Searching;
+    loop {
+        state = match state {
+            LinkState::Connected(port, port_name) => match read_serial(&app, port, &port_name) {
+                ReadEnd::Quit => return,
+                ReadEnd::Lost(e) => {
+                    warn!(port = %port_name, error = %e, "serial link lost, reconnecting");
+                    LinkState::Reconnecting(port_name)
+                }
+            },
+            LinkState::Searching | LinkState::Reconnecting(_) => {
+                let lost = match &state {
+                    LinkState::Reconnecting(port_name) => Some(port_name.as_str()),
+                    _ => None,
                 };
+                match try_connect(&app, lost) {
+                    Some((port, port_name)) => LinkState::Connected(port, port_name),
+                    None if wait_for_retry(&app) => state,
+                    None => return,
+                }
+            }
+        };
+    }
+}
 
-                if should_reset {
-                    if let Err(_e) = backend::esp_utility::reset_and_start_esp(&mut port) {}
-                    if let Ok(mut app) = app.lock() {
-                        app.should_reset_esp = false;
+/// Finds and opens a port (the lost one first, if it is back). Updates App::serial_status
+/// and App::serial_reconnecting either way; None means try again after SERIAL_RETRY_INTERVAL.
+fn try_connect(app: &Arc<Mutex<App>>, lost: Option<&str>) -> Option<(Box<dyn serialport::SerialPort>, String)> {
+    let status = match (find_serial_port(lost), lost) {
+        (Some(port_name), _) => match serialport::new(&port_name, BAUD_RATE).timeout(Duration::from_millis(1000)).open() {
+            Ok(port) => {
+                info!(port = %port_name, baud_rate = BAUD_RATE, "serial port opened");
+                set_serial_status(app, None);
+                if let Ok(mut app) = app.lock() {
+                    app.serial_reconnecting = false;
+                    // The port may come back under another name (e.g. ttyUSB0 -> ttyUSB1)
+                    match lost {
+                        Some(old) if old != port_name => app.notify(format!("Serial reconnected on {} (was {})", port_name, old)),
+                        Some(_) => app.notify(format!("Serial reconnected on {}", port_name)),
+                        None => {}
                     }
-                    // Re-create reader after reset might be needed if the port state changes significantly,
-                    // but usually just flushing is enough.
-                    // However, reset_and_start_esp writes to the port.
                 }
+                return Some((port, port_name));
+            }
+            Err(e) => format!("Can't open {}: {} — retrying", port_name, e),
+        },
+        (None, Some(old)) => format!("Lost {} — waiting for the ESP32 to be plugged back in", old),
+        (None, None) => NO_SERIAL_DEVICE.to_string(),
+    };
 
-                let mut collected_lines = String::new();
-                let mut lines_read = 0;
-                while lines_read < 24 {
-                    // Check for reset request
-                    if let Ok(guard) = app.lock() {
-                        if guard.should_reset_esp {
-                            break;
-                        }
+    if set_serial_status(app, Some(status.clone())) {
+        warn!(status = %status, "no usable serial port, retrying every {:?}", SERIAL_RETRY_INTERVAL);
+    }
+    if let Ok(mut app) = app.lock() {
+        app.serial_reconnecting = lost.is_some();
+    }
+    None
+}
+
+/// First USB serial port, or `preferred` if it is among them. Elsewhere than on Windows,
+/// FALLBACK_PORT is used if it exists; COM port numbers can't be guessed, so Windows only
+/// gets enumerated ports.
+fn find_serial_port(preferred: Option<&str>) -> Option<String> {
+    let ports = serialport::available_ports().unwrap_or_else(|e| {
+        warn!(error = %e, "listing serial ports failed");
+        Vec::new()
+    });
+
+    let usb: Vec<&String> = ports
+        .iter()
+        .filter(|p| matches!(p.port_type, serialport::SerialPortType::UsbPort(_)))
+        .map(|p| &p.port_name)
+        .collect();
+
+    usb.iter()
+        .find(|name| Some(name.as_str()) == preferred)
+        .or_else(|| usb.first())
+        .map(|name| name.to_string())
+        .or_else(|| (cfg!(not(windows)) && std::path::Path::new(FALLBACK_PORT).exists()).then(|| FALLBACK_PORT.to_string()))
+}
+
+/// Updates App::serial_status; returns true if it changed (so callers log once, not every retry)
+fn set_serial_status(app: &Arc<Mutex<App>>, status: Option<String>) -> bool {
+    match app.lock() {
+        Ok(mut app) if app.serial_status != status => {
+            app.serial_status = status;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Sleeps SERIAL_RETRY_INTERVAL in short steps; false if the app is quitting
+fn wait_for_retry(app: &Arc<Mutex<App>>) -> bool {
+    let deadline = Instant::now() + SERIAL_RETRY_INTERVAL;
+    while Instant::now() < deadline {
+        if app.lock().map(|guard| guard.should_quit).unwrap_or(true) {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    true
+}
+
+/// Reads CSI blocks from an open port until the app quits or the port stops working
+fn read_serial(app: &Arc<Mutex<App>>, mut port: Box<dyn serialport::SerialPort>, port_name: &str) -> ReadEnd {
+    let reader = match port.try_clone() {
+        Ok(reader) => reader,
+        Err(e) => return ReadEnd::Lost(e.into()),
+    };
+    let mut reader = BufReader::new(reader);
+    let mut raw_log = open_raw_log(app);
+    let mut last_flush = Instant::now();
+    let mut assembler = csi_data::BlockAssembler::default();
+
+    loop {
+        // Check for Reset Command
+        let should_reset = if let Ok(app) = app.lock() {
+            app.should_reset_esp
+        } else {
+            false
+        };
+
+        if should_reset {
+            let result = backend::esp_utility::reset_and_start_esp(&mut port);
+            // Whatever was half-read before the reset won't be completed
+            assembler.clear();
+            if let Ok(mut app) = app.lock() {
+                app.should_reset_esp = false;
+                match result {
+                    Ok(_) => app.notify("ESP reset, CSI capture restarted"),
+                    Err(e) => {
+                        warn!(port = %port_name, error = %e, "ESP reset failed");
+                        app.notify(format!("ESP reset failed: {}", e));
                     }
+                }
+            }
+            // Re-create reader after reset might be needed if the port state changes significantly,
+            // but usually just flushing is enough.
+            // However, reset_and_start_esp writes to the port.
+        }
 
-                    let mut line = String::new();
-                    match reader.read_line(&mut line) {
-                        Ok(len) => {
-                            if len > 0 {
-                                collected_lines.push_str(&line);
-                                lines_read += 1;
-                            }
-                        }
-                        Err(ref e) if e.kind() == io::ErrorKind::TimedOut => {
-                            continue;
+        // Read until the assembler has a complete block (or a reset / quit is requested)
+        let mut block = None;
+        while block.is_none() {
+            // Check for reset / quit request
+            if let Ok(guard) = app.lock() && (guard.should_reset_esp || guard.should_quit) {
+                break;
+            }
+
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Ok(len) => {
+                    if len > 0 {
+                        if let Some(log) = raw_log.as_mut() && let Err(e) = log.write_all(line.as_bytes()) {
+                            warn!(error = %e, "writing the raw log failed");
                         }
-                        Err(_e) => {}
+                        block = assembler.push_line(&line);
+                    } else {
+                        // A tty only reports end-of-file once the device is gone
+                        flush_raw_log(&mut raw_log);
+                        return ReadEnd::Lost(io::ErrorKind::UnexpectedEof.into());
                     }
                 }
+                Err(ref e) if matches!(e.kind(), io::ErrorKind::TimedOut | io::ErrorKind::Interrupted) => {
+                    continue;
+                }
+                Err(e) => {
+                    flush_raw_log(&mut raw_log);
+                    return ReadEnd::Lost(e);
+                }
+            }
+        }
 
-                match CsiData::parse(&collected_lines) {
-                    Ok(data) => {
-                        if let Ok(mut app) = app.lock() {
-                            app.dataloader.push_data_packet(data.clone());
-
-                            // Log to Rerun if enabled
-                            if let Some(ref streamer) = app.rerun_streamer {
-                                if let Ok(mut s) = streamer.lock() {
-                                    #[cfg(feature = "rerun")]
-                                    {
-                                        let frame = crate::rerun_stream::CsiFrame::from(&data);
-                                        s.push_csi(&frame);
-                                    }
-                                }
-                            }
+        // Quitting: flush what is buffered for the raw log and stop
+        if app.lock().map(|guard| guard.should_quit).unwrap_or(true) {
+            flush_raw_log(&mut raw_log);
+            return ReadEnd::Quit;
+        }
+
+        // Flush the raw log about once a second rather than per line
+        if let Some(log) = raw_log.as_mut() && last_flush.elapsed() >= Duration::from_secs(1) {
+            if let Err(e) = log.flush() {
+                warn!(error = %e, "flushing the raw log failed");
+            }
+            last_flush = Instant::now();
+        }
+
+        let Some(block) = block else { continue };
+        match CsiData::parse(&block) {
+            Ok(data) => {
+                if let Ok(mut app) = app.lock() {
+                    app.dataloader.push_data_packet(data.clone());
+
+                    // Log to Rerun if enabled
+                    if let Some(ref streamer) = app.rerun_streamer && let Ok(mut s) = streamer.lock() {
+                        #[cfg(feature = "rerun")]
+                        {
+                            let frame = crate::rerun_stream::CsiFrame::from(&data);
+                            s.phase_colormap = app.phase_colormap;
+                            s.push_csi(&frame);
                         }
                     }
-                    Err(_e) => {}
                 }
             }
+            // Partial blocks are normal at startup and after a reset
+            Err(e) => debug!(error = %e, "dropped unparsable CSI block"),
         }
-        Err(_e) => {}
     }
 }
 
-pub fn mock_esp_com(app: Arc<Mutex<App>>) {
-    let file_path = "example_data.mock";
-    let content = std::fs::read_to_string(file_path).unwrap_or_else(|_| String::new());
+fn flush_raw_log(raw_log: &mut Option<BufWriter<File>>) {
+    if let Some(log) = raw_log.as_mut() && let Err(e) = log.flush() {
+        warn!(error = %e, "flushing the raw log failed");
+    }
+}
+
+/// Opens the --raw-log file once (append mode) if one was requested
+fn open_raw_log(app: &Arc<Mutex<App>>) -> Option<BufWriter<File>> {
+    let path = app.lock().ok()?.raw_log_path.clone()?;
+    match OpenOptions::new().create(true).append(true).open(&path) {
+        Ok(file) => Some(BufWriter::new(file)),
+        Err(e) => {
+            warn!(path, error = %e, "opening the raw log failed");
+            None
+        }
+    }
+}
 
-    let mut packets = Vec::new();
-    let mut current_chunk = String::new();
-    let mut line_count = 0;
+// Fallback gap when the CSV has no usable timestamps, and a cap so long pauses in a capture don't stall playback
+const REPLAY_DEFAULT_GAP: Duration = Duration::from_millis(10);
+const REPLAY_MAX_GAP: Duration = Duration::from_secs(2);
 
-    for line in content.lines() {
-        current_chunk.push_str(line);
-        current_chunk.push('\n');
-        line_count += 1;
+/// Delay between two recorded packets (ESP timestamps are in microseconds), scaled by playback speed
+fn replay_gap(prev: &CsiData, next: &CsiData, speed: f32) -> Duration {
+    let gap = if next.timestamp > prev.timestamp {
+        Duration::from_micros(next.timestamp - prev.timestamp).min(REPLAY_MAX_GAP)
+    } else {
+        REPLAY_DEFAULT_GAP
+    };
+    gap.div_f32(speed.max(0.01))
+}
 
-        if line_count == 24 {
-            if let Ok(data) = CsiData::parse(&current_chunk) {
-                packets.push(data);
+/// Feeds recorded packets into the dataloader as if they were arriving live.
+/// Speed and looping are read from App each step so they can change while playing. Stops when the app quits.
+pub fn run_replay(app: Arc<Mutex<App>>, packets: Vec<CsiData>) {
+    if packets.is_empty() {
+        return;
+    }
+
+    if let Ok(mut app_guard) = app.lock() {
+        app_guard.on_source_switched();
+    }
+
+    let mut index = 0;
+    loop {
+        let (speed, looping) = match app.lock() {
+            Ok(mut app_guard) if !app_guard.should_quit => {
+                // Seeking restarts the stats from the new position
+                if let Some(target) = app_guard.replay_seek.take() {
+                    index = target.min(packets.len() - 1);
+                    app_guard.on_source_switched();
+                }
+                app_guard.replay_position = (index, packets.len());
+                app_guard.dataloader.push_data_packet(packets[index].clone());
+                (app_guard.replay_speed, app_guard.replay_loop)
             }
-            current_chunk.clear();
-            line_count = 0;
+            _ => return,
+        };
+
+        if index + 1 < packets.len() {
+            thread::sleep(replay_gap(&packets[index], &packets[index + 1], speed));
+            index += 1;
+            continue;
+        }
+
+        // End of file: in one-shot mode, hold here until looping is switched back on (or a seek arrives)
+        if !looping {
+            loop {
+                match app.lock() {
+                    Ok(app_guard) if app_guard.should_quit => return,
+                    Ok(app_guard) if app_guard.replay_loop || app_guard.replay_seek.is_some() => break,
+                    Ok(_) => {}
+                    Err(_) => return,
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+        }
+
+        thread::sleep(REPLAY_DEFAULT_GAP.div_f32(speed.max(0.01)));
+        if let Ok(mut app_guard) = app.lock() {
+            app_guard.on_source_switched();
         }
+        index = 0;
     }
+}
+
+/// Feeds the overlay source (App::overlay) from a recorded capture at recorded speed, looping,
+/// next to whatever the primary source does. Stops when the app quits.
+pub fn run_overlay(app: Arc<Mutex<App>>, packets: Vec<CsiData>) {
+    if packets.is_empty() {
+        return;
+    }
+
+    let mut index = 0;
+    loop {
+        match app.lock() {
+            Ok(mut app_guard) if !app_guard.should_quit => {
+                let Some(overlay) = app_guard.overlay.as_mut() else { return };
+                // Straight into the queue: the overlay keeps no raw packet history
+                overlay.dataloader.queue.push_back(packets[index].clone());
+            }
+            _ => return,
+        }
+
+        if index + 1 < packets.len() {
+            thread::sleep(replay_gap(&packets[index], &packets[index + 1], 1.0));
+            index += 1;
+        } else {
+            thread::sleep(REPLAY_DEFAULT_GAP);
+            if let Ok(mut app_guard) = app.lock() && let Some(overlay) = app_guard.overlay.as_mut() {
+                overlay.restart();
+            }
+            index = 0;
+        }
+    }
+}
+
+pub fn mock_esp_com(app: Arc<Mutex<App>>) {
+    let file_path = "example_data.mock";
+    let content = std::fs::read_to_string(file_path).unwrap_or_else(|_| String::new());
+
+    let mut assembler = csi_data::BlockAssembler::default();
+    let packets: Vec<CsiData> = content
+        .lines()
+        .filter_map(|line| assembler.push_line(line))
+        .filter_map(|block| CsiData::parse(&block).ok())
+        .collect();
 
     if packets.is_empty() {
         return;
@@ -145,3 +416,104 @@ pub fn mock_esp_com(app: Arc<Mutex<App>>) {
         thread::sleep(Duration::from_millis(100)); // 10Hz
     }
 }
+
+// --- Deterministic mock source ---
+
+pub const DEFAULT_MOCK_SEED: u64 = 1;
+
+// 50 packets/s: several per UI update, so averaging behaves as with a real ESP
+const MOCK_INTERVAL: Duration = Duration::from_millis(20);
+
+const MOCK_SUBCARRIERS: usize = 64;
+
+/// Closed-form CSI with a small seeded noise term: the same seed always yields the same packets.
+/// Shape: frequency-selective fading, a slowly breathing "target" around subcarrier 20,
+/// a linear phase slope plus a constant rotation, and zeroed DC/guard subcarriers (0, 27-37)
+/// like a 20 MHz HT capture.
+pub struct MockGenerator {
+    state: u64,
+    index: u64,
+    fading_offset: f64,
+}
+
+impl MockGenerator {
+    pub fn new(seed: u64) -> Self {
+        let mut generator = Self { state: seed, index: 0, fading_offset: 0.0 };
+        generator.fading_offset = generator.noise(std::f64::consts::PI);
+        generator
+    }
+
+    /// SplitMix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [-scale, scale)
+    fn noise(&mut self, scale: f64) -> f64 {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        (unit * 2.0 - 1.0) * scale
+    }
+
+    pub fn next_packet(&mut self) -> CsiData {
+        use std::f64::consts::TAU;
+
+        let t = self.index as f64 * MOCK_INTERVAL.as_secs_f64();
+        let breathing = (TAU * 0.25 * t).sin();
+
+        let mut csi_raw_data = Vec::with_capacity(MOCK_SUBCARRIERS * 2);
+        for k in 0..MOCK_SUBCARRIERS {
+            if k == 0 || (27..=37).contains(&k) {
+                csi_raw_data.extend([0, 0]);
+                continue;
+            }
+            let x = k as f64;
+            let fading = 30.0 + 12.0 * (TAU * 3.0 * x / MOCK_SUBCARRIERS as f64 + self.fading_offset).sin();
+            let target = 8.0 * breathing * (-((x - 20.0) / 6.0).powi(2)).exp();
+            let amplitude = fading + target;
+            let phase = -0.15 * x + 0.3 * t;
+
+            let i_val = amplitude * phase.cos() + self.noise(1.5);
+            let q_val = amplitude * phase.sin() + self.noise(1.5);
+            csi_raw_data.push(i_val.round() as i32);
+            csi_raw_data.push(q_val.round() as i32);
+        }
+
+        let rssi = (-48.0 + 2.0 * breathing + self.noise(2.0)).round() as i32;
+        let packet = CsiData {
+            mac: "02:00:00:00:00:01".to_string(),
+            rssi,
+            noise_floor: -93,
+            channel: 6,
+            timestamp: self.index * MOCK_INTERVAL.as_micros() as u64,
+            data_length: csi_raw_data.len() as u32,
+            csi_raw_data,
+            ..CsiData::default()
+        };
+        self.index += 1;
+        packet
+    }
+}
+
+/// Streams MockGenerator packets at MOCK_INTERVAL. ESP reset requests are acknowledged
+/// immediately since there is no device behind this source.
+pub fn run_mock(app: Arc<Mutex<App>>, seed: u64) {
+    let mut generator = MockGenerator::new(seed);
+    loop {
+        let packet = generator.next_packet();
+        match app.lock() {
+            Ok(mut app_guard) => {
+                if app_guard.should_reset_esp {
+                    app_guard.should_reset_esp = false;
+                    app_guard.notify("Mock source: nothing to reset");
+                }
+                app_guard.dataloader.push_data_packet(packet);
+            }
+            Err(_) => return,
+        }
+        thread::sleep(MOCK_INTERVAL);
+    }
+}
@@ -1,9 +1,15 @@
 // --- File: src/layout_tree.rs ---
 // --- Purpose: Defines the Tiling Window Manager (TWM) logic, pane splitting, and focus management ---
 
+use std::collections::HashMap;
 use ratatui::prelude::*;
 use serde::{Serialize, Deserialize};
-use crate::frontend::theme::ThemeType;
+use crate::frontend::theme::{CanvasMarker, PhaseColormap, StatsFormat, Theme, ThemeType};
+use crate::frontend::view_state::ViewState;
+use crate::keybindings::Action;
+
+// Most panes a layout can hold (further splits are ignored)
+pub const MAX_PANES: usize = 10;
 
 #[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
 pub enum SplitDirection {
@@ -30,9 +36,41 @@ pub enum ViewType {
     Phase,
     Camera,
     RawScatter,
+    Waterfall,
+    Overview,
+    Ridgeline,
+    Constellation,
 }
 
+// Names accepted by --views, in view selector order
+pub const VIEW_NAMES: [&str; 11] = [
+    "dashboard", "polar", "isometric", "spectrogram", "phase", "scatter",
+    "waterfall", "ridgeline", "constellation", "overview", "empty",
+];
+
+// Actions shared by several views' footer hints (see ViewType::hint_line)
+const CAMERA_ACTIONS: &[Action] = &[Action::CameraUp, Action::CameraLeft, Action::CameraDown, Action::CameraRight];
+const DEPTH_ACTIONS: &[Action] = &[Action::DepthIncrease, Action::DepthDecrease];
+
 impl ViewType {
+    /// View for a --views name (case-insensitive, see VIEW_NAMES; "cir" and "iq" are aliases)
+    pub fn from_name(name: &str) -> Option<ViewType> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "dashboard" | "stats" => Some(ViewType::Dashboard),
+            "polar" => Some(ViewType::Polar),
+            "isometric" | "cir" => Some(ViewType::Isometric),
+            "spectrogram" | "doppler" => Some(ViewType::Spectrogram),
+            "phase" => Some(ViewType::Phase),
+            "scatter" | "iq" => Some(ViewType::RawScatter),
+            "waterfall" => Some(ViewType::Waterfall),
+            "ridgeline" => Some(ViewType::Ridgeline),
+            "constellation" => Some(ViewType::Constellation),
+            "overview" => Some(ViewType::Overview),
+            "empty" => Some(ViewType::Empty),
+            _ => None,
+        }
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
             ViewType::Empty => "Empty Pane",
@@ -43,15 +81,162 @@ impl ViewType {
             ViewType::Phase => "Phase Plot",
             ViewType::Camera => "(NO_CAMERA_STREAM)",
             ViewType::RawScatter => "Multipath Scatter",
+            ViewType::Waterfall => "Amplitude Waterfall",
+            ViewType::Overview => "Overview",
+            ViewType::Ridgeline => "Amplitude Ridgeline",
+            ViewType::Constellation => "I/Q Constellation",
         }
     }
 
-    pub fn is_spatial(&self) -> bool {
-        matches!(self, ViewType::Isometric | ViewType::RawScatter | ViewType::Polar)
+    /// Signature color of the view, used for pane titles and the view selector so view types
+    /// can be told apart at a glance. Light themes get darker shades; Empty stays muted.
+    pub fn accent_color(&self, theme: &Theme) -> Color {
+        let light = theme.variant == ThemeType::Light;
+        match self {
+            ViewType::Empty | ViewType::Camera => theme.text_normal.fg.unwrap_or(Color::Gray),
+            ViewType::Dashboard => Color::Green,
+            ViewType::Polar => if light { Color::Blue } else { Color::Cyan },
+            ViewType::Isometric => if light { Color::Rgb(0, 95, 175) } else { Color::LightBlue },
+            ViewType::Spectrogram => if light { Color::Magenta } else { Color::LightMagenta },
+            ViewType::Phase => if light { Color::Rgb(175, 95, 0) } else { Color::Yellow },
+            ViewType::RawScatter => if light { Color::Red } else { Color::LightRed },
+            ViewType::Waterfall => if light { Color::Rgb(0, 135, 95) } else { Color::LightGreen },
+            ViewType::Overview => if light { Color::Rgb(95, 0, 175) } else { Color::Rgb(175, 135, 255) },
+            ViewType::Ridgeline => if light { Color::Rgb(135, 0, 95) } else { Color::Rgb(255, 135, 175) },
+            ViewType::Constellation => if light { Color::Rgb(175, 75, 0) } else { Color::Rgb(255, 175, 95) },
+        }
     }
 
-    pub fn is_temporal(&self) -> bool {
-        matches!(self, ViewType::Isometric | ViewType::Spectrogram | ViewType::Phase | ViewType::RawScatter | ViewType::Polar | ViewType::Dashboard)
+    /// The view's own keys, shown in the footer for the focused pane. Each hint is the actions
+    /// whose current keys are listed (looked up in App::keybindings) and what they do; hints
+    /// with no actions are shown as they are
+    pub fn hint_line(&self) -> &'static [(&'static [Action], &'static str)] {
+        match self {
+            ViewType::Empty => &[(&[Action::ViewSelector], "to select view.")],
+            ViewType::Dashboard => &[
+                (&[Action::StepBack, Action::StepForward], "step"),
+                (&[Action::ToggleMetadata], "gauges / metadata"),
+                (&[Action::ResetLive], "live"),
+            ],
+            ViewType::Polar => &[
+                (CAMERA_ACTIONS, "rotate"),
+                (DEPTH_ACTIONS, "depth"),
+                (&[Action::CycleProjection], "projection"),
+                (&[Action::ToggleAmplitudeColor], "color"),
+                (&[Action::ToggleGrid], "grid"),
+                (&[Action::CycleSmoothing], "smooth"),
+            ],
+            ViewType::Isometric => &[
+                (CAMERA_ACTIONS, "rotate"),
+                (DEPTH_ACTIONS, "depth"),
+                (&[Action::CycleProjection], "projection"),
+                (&[Action::ToggleDcNull], "DC null"),
+                (&[Action::ToggleGrid], "grid"),
+                (&[Action::CycleSmoothing], "smooth"),
+            ],
+            ViewType::Spectrogram => &[
+                (&[Action::StepBack, Action::StepForward], "step"),
+                (DEPTH_ACTIONS, "depth"),
+                (&[Action::TogglePhaseUnwrap], "unwrap"),
+                (&[Action::CycleSmoothing], "smooth heatmap"),
+            ],
+            ViewType::Phase => &[
+                (&[Action::StepBack, Action::StepForward], "step"),
+                (&[Action::SubcarrierZoomIn, Action::SubcarrierZoomOut], "zoom"),
+                (&[Action::SubcarrierPanLeft, Action::SubcarrierPanRight], "pan"),
+                (&[Action::TogglePhaseUnwrap], "unwrap"),
+                (DEPTH_ACTIONS, "depth"),
+                (&[Action::CycleSmoothing], "smooth"),
+            ],
+            ViewType::RawScatter => &[
+                (CAMERA_ACTIONS, "rotate"),
+                (&[], "wheel zoom"),
+                (DEPTH_ACTIONS, "persistence"),
+                (&[Action::ThresholdUp, Action::ThresholdDown], "threshold"),
+            ],
+            ViewType::Waterfall => &[
+                (&[Action::StepBack, Action::StepForward], "step"),
+                (DEPTH_ACTIONS, "depth"),
+                (&[Action::ToggleCoarseHistory], "per-second history"),
+            ],
+            ViewType::Overview => &[
+                (&[Action::StepBack, Action::StepForward], "step"),
+                (&[Action::CycleSmoothing], "smooth"),
+                (&[Action::TogglePhaseUnwrap], "unwrap"),
+                (&[Action::ResetLive], "live"),
+            ],
+            ViewType::Ridgeline => &[
+                (&[Action::StepBack, Action::StepForward], "step"),
+                (DEPTH_ACTIONS, "depth"),
+                (&[Action::SubcarrierZoomIn, Action::SubcarrierZoomOut], "zoom"),
+                (&[Action::SubcarrierPanLeft, Action::SubcarrierPanRight], "pan"),
+                (&[Action::CycleSmoothing], "smooth"),
+            ],
+            ViewType::Constellation => &[
+                (&[Action::StepBack, Action::StepForward], "step"),
+                (DEPTH_ACTIONS, "persistence"),
+            ],
+            ViewType::Camera => &[(&[], "No camera stream in this build")],
+        }
+    }
+
+    /// Default history depth for views that draw several packets at once
+    pub fn default_depth(&self) -> Option<usize> {
+        match self {
+            ViewType::Polar => Some(crate::frontend::views::polar::DEFAULT_DEPTH),
+            ViewType::Phase => Some(crate::frontend::views::phase::DEFAULT_DEPTH),
+            ViewType::Spectrogram => Some(crate::frontend::views::spectrogram::DEFAULT_DEPTH),
+            ViewType::Isometric => Some(crate::frontend::views::time_domain_iso::DEFAULT_DEPTH),
+            ViewType::Waterfall => Some(crate::frontend::views::waterfall::DEFAULT_DEPTH),
+            ViewType::Ridgeline => Some(crate::frontend::views::ridgeline::DEFAULT_DEPTH),
+            _ => None,
+        }
+    }
+
+    /// False for views with no backend in this build (the camera feed needs OpenCV, which is disabled)
+    pub fn is_available(&self) -> bool {
+        !matches!(self, ViewType::Camera)
+    }
+
+    /// Smallest pane (width, height, borders included) the view is drawn in; below it the router
+    /// shows a "too small" placeholder. Canvas views need the most room to keep sane bounds.
+    pub fn min_size(&self) -> (u16, u16) {
+        match self {
+            ViewType::Polar | ViewType::Isometric => (24, 10),
+            ViewType::Phase | ViewType::Spectrogram | ViewType::Waterfall | ViewType::RawScatter | ViewType::Ridgeline | ViewType::Constellation => (20, 8),
+            ViewType::Dashboard | ViewType::Overview => (16, 5),
+            _ => (0, 0),
+        }
+    }
+
+    /// 3D views and their starting projection (per-pane override: ViewState::projection)
+    pub fn default_projection(&self) -> Option<crate::frontend::view_state::Projection> {
+        use crate::frontend::view_state::Projection;
+        match self {
+            ViewType::Polar => Some(Projection::Perspective),
+            ViewType::Isometric => Some(Projection::Oblique),
+            _ => None,
+        }
+    }
+
+    /// Views that plot per-subcarrier I/Q and honor ViewState::smoothing_alpha
+    pub fn is_smoothable(&self) -> bool {
+        matches!(self, ViewType::Polar | ViewType::Phase | ViewType::Isometric | ViewType::Overview | ViewType::Ridgeline)
+    }
+
+    /// Views that read per-subcarrier phase and honor ViewState::unwrap_phase
+    pub fn uses_phase(&self) -> bool {
+        matches!(self, ViewType::Phase | ViewType::Spectrogram | ViewType::Overview)
+    }
+
+    /// Views that can keep recent packets on screen (ViewState::persistence)
+    pub fn has_persistence(&self) -> bool {
+        matches!(self, ViewType::Constellation | ViewType::RawScatter)
+    }
+
+    /// Views that can show live CSI minus App::reference (ViewState::subtract_reference)
+    pub fn uses_reference(&self) -> bool {
+        matches!(self, ViewType::Polar | ViewType::Phase | ViewType::Waterfall | ViewType::Overview | ViewType::Ridgeline)
     }
 }
 
@@ -101,7 +286,14 @@ impl LayoutNode {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+/// Parses a --layout grid such as "2x2" or "3x1" (columns x rows) with 1..=MAX_PANES panes
+pub fn parse_grid(spec: &str) -> Option<(usize, usize)> {
+    let (cols, rows) = spec.trim().to_ascii_lowercase().split_once('x')
+        .and_then(|(c, r)| Some((c.parse::<usize>().ok()?, r.parse::<usize>().ok()?)))?;
+    cols.checked_mul(rows).is_some_and(|n| (1..=MAX_PANES).contains(&n)).then_some((cols, rows))
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct TilingManager {
     pub root: LayoutNode,
     pub focused_pane_id: usize,
@@ -112,6 +304,29 @@ pub struct TilingManager {
 
     #[serde(default)]
     pub theme_variant: Option<ThemeType>,
+
+    #[serde(default)]
+    pub canvas_marker: Option<CanvasMarker>,
+
+    #[serde(default)]
+    pub stats_format: Option<StatsFormat>,
+
+    #[serde(default)]
+    pub phase_colormap: Option<PhaseColormap>,
+
+    // View of panes opened with the split keys (App::default_view); None = Empty
+    #[serde(default)]
+    pub default_view: Option<ViewType>,
+
+    // Per-pane camera/zoom, keyed by pane id (snapshot of App::pane_states at save time)
+    #[serde(default)]
+    pub pane_states: HashMap<usize, ViewState>,
+}
+
+impl Default for TilingManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TilingManager {
@@ -122,6 +337,51 @@ impl TilingManager {
             next_id: 2,
             is_default: false,
             theme_variant: None,
+            canvas_marker: None,
+            stats_format: None,
+            phase_colormap: None,
+            default_view: None,
+            pane_states: HashMap::new(),
+        }
+    }
+
+    /// A `cols` x `rows` grid of equally sized panes, numbered row by row (left to right, top to
+    /// bottom) and given `views` in that order; panes past the end of `views` stay Empty.
+    /// Used by --layout / --views. Callers keep cols * rows within MAX_PANES.
+    pub fn grid(cols: usize, rows: usize, views: &[ViewType]) -> Self {
+        let (cols, rows) = (cols.max(1), rows.max(1));
+        let mut next_id = 1;
+        let row_nodes = (0..rows)
+            .map(|_| {
+                let panes = (0..cols)
+                    .map(|_| {
+                        let id = next_id;
+                        next_id += 1;
+                        LayoutNode::Pane { id, view: views.get(id - 1).copied().unwrap_or(ViewType::Empty) }
+                    })
+                    .collect();
+                Self::even_split(panes, SplitDirection::Horizontal)
+            })
+            .collect();
+
+        Self {
+            root: Self::even_split(row_nodes, SplitDirection::Vertical),
+            next_id,
+            ..Self::new()
+        }
+    }
+
+    // Nests binary splits so every node gets the same share (the first takes 1/n, the rest split the remainder)
+    fn even_split(mut nodes: Vec<LayoutNode>, direction: SplitDirection) -> LayoutNode {
+        if nodes.len() <= 1 {
+            return nodes.pop().unwrap_or(LayoutNode::Pane { id: 1, view: ViewType::Empty });
+        }
+        let ratio = (100 / nodes.len()) as u16;
+        let rest = nodes.split_off(1);
+        LayoutNode::Split {
+            direction,
+            ratio,
+            children: vec![nodes.remove(0), Self::even_split(rest, direction)],
         }
     }
 
@@ -134,23 +394,30 @@ impl TilingManager {
     }
 
     pub fn split(&mut self, direction: Direction) {
-        if self.get_pane_count() >= 10 { return; }
+        self.split_with(direction, ViewType::Empty);
+    }
+
+    /// Splits the focused pane; the new pane shows `view` and takes the focus.
+    /// Returns its id, or None when the layout already has MAX_PANES panes.
+    pub fn split_with(&mut self, direction: Direction, view: ViewType) -> Option<usize> {
+        if self.get_pane_count() >= MAX_PANES { return None; }
 
         let local_dir = match direction {
             Direction::Horizontal => SplitDirection::Horizontal,
             Direction::Vertical => SplitDirection::Vertical,
         };
 
-        self.root = self.split_recursive(self.root.clone(), local_dir);
+        self.root = self.split_recursive(self.root.clone(), local_dir, view);
+        Some(self.focused_pane_id)
     }
 
-    fn split_recursive(&mut self, node: LayoutNode, dir: SplitDirection) -> LayoutNode {
+    fn split_recursive(&mut self, node: LayoutNode, dir: SplitDirection, new_view: ViewType) -> LayoutNode {
         match node {
             LayoutNode::Pane { id, view } => {
                 if id == self.focused_pane_id {
                     let new_id = self.next_id;
                     self.next_id += 1;
-                    let new_pane = LayoutNode::Pane { id: new_id, view: ViewType::Empty };
+                    let new_pane = LayoutNode::Pane { id: new_id, view: new_view };
                     let old_pane = LayoutNode::Pane { id, view };
                     self.focused_pane_id = new_id;
                     return LayoutNode::Split {
@@ -162,40 +429,44 @@ impl TilingManager {
                 LayoutNode::Pane { id, view }
             }
             LayoutNode::Split { direction, ratio, children } => {
-                let new_children: Vec<LayoutNode> = children.into_iter().map(|c| self.split_recursive(c, dir)).collect();
+                let new_children: Vec<LayoutNode> = children.into_iter().map(|c| self.split_recursive(c, dir, new_view)).collect();
                 LayoutNode::Split { direction, ratio, children: new_children }
             }
         }
     }
 
-    pub fn close_focused_pane(&mut self) {
-        if self.get_pane_count() <= 1 { return; }
+    /// Closes the focused pane and renumbers the rest 1..n in layout order.
+    /// Returns old id -> new id for every remaining pane (empty when nothing was closed),
+    /// so state keyed by pane id can follow (App::close_focused_pane).
+    pub fn close_focused_pane(&mut self) -> HashMap<usize, usize> {
+        if self.get_pane_count() <= 1 { return HashMap::new(); }
         let removed_root = self.remove_recursive(self.root.clone(), self.focused_pane_id);
         if let Some(node) = removed_root { self.root = node; }
         if !self.node_exists(self.focused_pane_id, &self.root) {
             self.focused_pane_id = self.find_first_id(&self.root);
         }
-        self.reindex_ids();
+        self.reindex_ids()
     }
 
-    fn reindex_ids(&mut self) {
+    fn reindex_ids(&mut self) -> HashMap<usize, usize> {
         let mut counter = 1;
-        let mut new_focus = self.focused_pane_id;
-        self.root = self.reindex_recursive(self.root.clone(), &mut counter, &mut new_focus);
-        self.focused_pane_id = new_focus;
+        let mut new_ids = HashMap::new();
+        self.root = self.reindex_recursive(self.root.clone(), &mut counter, &mut new_ids);
+        self.focused_pane_id = new_ids.get(&self.focused_pane_id).copied().unwrap_or(1);
         self.next_id = counter;
+        new_ids
     }
 
-    fn reindex_recursive(&self, node: LayoutNode, counter: &mut usize, new_focus: &mut usize) -> LayoutNode {
+    fn reindex_recursive(&self, node: LayoutNode, counter: &mut usize, new_ids: &mut HashMap<usize, usize>) -> LayoutNode {
         match node {
             LayoutNode::Pane { id, view } => {
                 let new_id = *counter;
                 *counter += 1;
-                if id == *new_focus { *new_focus = new_id; }
+                new_ids.insert(id, new_id);
                 LayoutNode::Pane { id: new_id, view }
             }
             LayoutNode::Split { direction, ratio, children } => {
-                let new_children = children.into_iter().map(|c| self.reindex_recursive(c, counter, new_focus)).collect();
+                let new_children = children.into_iter().map(|c| self.reindex_recursive(c, counter, new_ids)).collect();
                 LayoutNode::Split { direction, ratio, children: new_children }
             }
         }
@@ -217,16 +488,21 @@ impl TilingManager {
     }
 
     pub fn set_current_view(&mut self, new_view: ViewType) {
-        self.root = self.set_view_recursive(self.root.clone(), new_view);
+        self.set_view_for(&[self.focused_pane_id], new_view);
+    }
+
+    /// Sets the view of every pane in `ids` (unknown ids are ignored)
+    pub fn set_view_for(&mut self, ids: &[usize], new_view: ViewType) {
+        self.root = Self::set_view_recursive(self.root.clone(), ids, new_view);
     }
 
-    fn set_view_recursive(&self, node: LayoutNode, new_view: ViewType) -> LayoutNode {
+    fn set_view_recursive(node: LayoutNode, ids: &[usize], new_view: ViewType) -> LayoutNode {
         match node {
             LayoutNode::Pane { id, view } => {
-                if id == self.focused_pane_id { LayoutNode::Pane { id, view: new_view } } else { LayoutNode::Pane { id, view } }
+                if ids.contains(&id) { LayoutNode::Pane { id, view: new_view } } else { LayoutNode::Pane { id, view } }
             }
             LayoutNode::Split { direction, ratio, children } => {
-                let new_children = children.into_iter().map(|c| self.set_view_recursive(c, new_view)).collect();
+                let new_children = children.into_iter().map(|c| Self::set_view_recursive(c, ids, new_view)).collect();
                 LayoutNode::Split { direction, ratio, children: new_children }
             }
         }
@@ -246,6 +522,10 @@ impl TilingManager {
         }
     }
 
+    pub fn pane_exists(&self, id: usize) -> bool {
+        self.node_exists(id, &self.root)
+    }
+
     fn node_exists(&self, target_id: usize, node: &LayoutNode) -> bool {
         match node {
             LayoutNode::Pane { id, .. } => *id == target_id,
@@ -253,6 +533,19 @@ impl TilingManager {
         }
     }
 
+    /// All panes as (id, view) in layout order (left/top first)
+    pub fn panes(&self) -> Vec<(usize, ViewType)> {
+        let mut out = Vec::new();
+        Self::collect_panes(&self.root, &mut out);
+        out
+    }
+    fn collect_panes(node: &LayoutNode, out: &mut Vec<(usize, ViewType)>) {
+        match node {
+            LayoutNode::Pane { id, view } => out.push((*id, *view)),
+            LayoutNode::Split { children, .. } => children.iter().for_each(|c| Self::collect_panes(c, out)),
+        }
+    }
+
     pub fn get_pane_count(&self) -> usize { self.count_recursive(&self.root) }
     fn count_recursive(&self, node: &LayoutNode) -> usize {
         match node {
@@ -266,4 +559,56 @@ impl TilingManager {
             LayoutNode::Split { children, .. } => self.find_first_id(&children[0]),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_grid_layout_assigns_views_row_by_row() {
+        assert_eq!(parse_grid("2x2"), Some((2, 2)));
+        assert_eq!(parse_grid("3X1"), Some((3, 1)));
+        assert_eq!(parse_grid("4x3"), None); // More than 10 panes
+        assert_eq!(parse_grid("2by2"), None);
+        assert_eq!(ViewType::from_name(" Spectrogram"), Some(ViewType::Spectrogram));
+        assert_eq!(ViewType::from_name("radar"), None);
+
+        // Fewer views than panes: the rest stay Empty
+        let tiling = TilingManager::grid(2, 2, &[ViewType::Dashboard, ViewType::Phase, ViewType::Waterfall]);
+        assert_eq!(tiling.panes(), vec![
+            (1, ViewType::Dashboard), (2, ViewType::Phase), (3, ViewType::Waterfall), (4, ViewType::Empty),
+        ]);
+        assert_eq!(tiling.next_id, 5);
+    }
+
+    #[test]
+    fn set_view_for_changes_only_the_listed_panes() {
+        let mut tiling = TilingManager::new();
+        tiling.split(Direction::Horizontal);
+        tiling.split(Direction::Vertical);
+        let ids: Vec<usize> = tiling.panes().iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids.len(), 3);
+
+        tiling.set_view_for(&[ids[0], ids[2], 99], ViewType::Waterfall);
+        let views: Vec<ViewType> = tiling.panes().iter().map(|(_, v)| *v).collect();
+        assert_eq!(views[0], ViewType::Waterfall);
+        assert_ne!(views[1], ViewType::Waterfall);
+        assert_eq!(views[2], ViewType::Waterfall);
+    }
+
+    #[test]
+    fn split_with_gives_the_new_pane_a_view_up_to_the_cap() {
+        let mut tiling = TilingManager::new();
+        tiling.set_current_view(ViewType::Phase);
+        assert_eq!(tiling.split_with(Direction::Horizontal, ViewType::Phase), Some(2));
+        assert_eq!(tiling.focused_pane_id, 2);
+        assert_eq!(tiling.panes(), vec![(1, ViewType::Phase), (2, ViewType::Phase)]);
+
+        while tiling.get_pane_count() < MAX_PANES {
+            tiling.split(Direction::Vertical);
+        }
+        assert_eq!(tiling.split_with(Direction::Vertical, ViewType::Phase), None);
+        assert_eq!(tiling.get_pane_count(), MAX_PANES);
+    }
+}
@@ -6,6 +6,9 @@ pub mod theme;
 pub mod view_router;
 pub mod view_traits;
 pub mod view_state;
+pub mod smoothing;
+pub mod screenshot;
 
 pub mod views;
+pub mod widgets;
 pub mod overlays;
\ No newline at end of file
@@ -0,0 +1,26 @@
+// --- File: src/frontend/overlays/delete_template.rs ---
+// --- Purpose: Confirmation popup before deleting a template from the load selector ---
+
+use ratatui::{prelude::*, widgets::*};
+use crate::App;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let Some(ref filename) = app.confirm_delete_template else { return };
+    let area = crate::frontend::overlays::help::centered_rect(40, 20, area);
+
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Confirm Delete ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+        .style(app.theme.root);
+
+    let name = filename.strip_suffix(".json").unwrap_or(filename);
+    let text = Paragraph::new(format!("Delete template '{}'?\nThis cannot be undone.\n\n[Y] Yes    [N] No", name))
+        .block(block)
+        .alignment(Alignment::Center)
+        .style(app.theme.text_highlight);
+
+    f.render_widget(text, area);
+}
@@ -5,7 +5,7 @@ use ratatui::{prelude::*, widgets::*};
 use crate::App;
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect) {
-    let area = crate::frontend::overlays::help::centered_rect(50, 20, area);
+    let area = crate::frontend::overlays::help::centered_rect(50, 25, area);
     f.render_widget(Clear, area);
 
     let block = Block::default()
@@ -17,9 +17,20 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let instructions = "Enter filename prefix (e.g. 'capture_01')\n\
-                        Will be saved as: [prefix]_[timestamp].csv\n\n\
-                        [Enter] Export  [Esc] Cancel";
+    // Raw: one row per packet (re-importable). Tidy: one row per (packet, subcarrier)
+    let (format_label, suffix) = if app.export_tidy {
+        ("Tidy (timestamp, subcarrier, amplitude, phase, rssi)", "_tidy")
+    } else {
+        ("Raw I/Q (replayable)", "")
+    };
+
+    let instructions = format!(
+        "Enter filename prefix (e.g. 'capture_01')\n\
+         Will be saved as: [prefix]_[timestamp]{}.csv\n\
+         Format: {}\n\n\
+         [Enter] Export  [Tab] Format  [Esc] Cancel",
+        suffix, format_label
+    );
 
     let text = format!("{}\n\n{}", app.export_input_buffer, instructions);
     let input = Paragraph::new(text)
@@ -0,0 +1,61 @@
+// --- File: src/frontend/overlays/gauge_settings.rs ---
+// --- Purpose: Popup to adjust the Dashboard gauge ranges, the SNR warning floor, the RSSI border tint and the signal timeout and motion alarm ---
+
+use ratatui::{prelude::*, widgets::*};
+use crate::App;
+
+// Order matches GaugeRanges::adjust
+pub const GAUGE_SETTINGS: [&str; 12] = [
+    "PPS Max",
+    "SNR Max (dB)",
+    "RSSI Min (dBm)",
+    "RSSI Max (dBm)",
+    "SNR Floor (dB)",
+    "RSSI Strong (dBm)",
+    "RSSI Weak (dBm)",
+    "Tint All Panes",
+    "Signal Timeout (s)",
+    "Dim When Lost",
+    "Motion Max",
+    "Motion Alarm",
+];
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let area = crate::frontend::overlays::help::centered_rect(40, 50, area);
+    f.render_widget(Clear, area);
+
+    let ranges = &app.gauge_ranges;
+    let values = [
+        format!("{:.0}", ranges.pps_max),
+        format!("{:.0}", ranges.snr_max),
+        ranges.rssi_min.to_string(),
+        ranges.rssi_max.to_string(),
+        ranges.snr_floor.to_string(),
+        ranges.rssi_strong.to_string(),
+        ranges.rssi_weak.to_string(),
+        (if ranges.tint_all_borders { "On" } else { "Off" }).to_string(),
+        format!("{:.1}", ranges.signal_timeout_secs),
+        (if ranges.dim_on_signal_loss { "On" } else { "Off" }).to_string(),
+        format!("{:.0}", ranges.motion_max),
+        format!("{:.1}", ranges.motion_threshold),
+    ];
+
+    let items: Vec<ListItem> = GAUGE_SETTINGS
+        .iter()
+        .zip(values.iter())
+        .enumerate()
+        .map(|(i, (label, value))| {
+            let style = if i == app.gauge_settings_index { app.theme.sidebar_selected } else { app.theme.text_normal };
+            ListItem::new(format!(" {:<16} < {} > ", label, value)).style(style)
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(" Gauge Ranges ")
+        .title_bottom(Line::from(" Left/Right: Adjust | Esc: Close ").alignment(Alignment::Center))
+        .borders(Borders::ALL)
+        .border_style(app.theme.focused_border)
+        .style(app.theme.root);
+
+    f.render_widget(List::new(items).block(block), area);
+}
@@ -3,6 +3,94 @@
 
 use ratatui::{prelude::*, widgets::*};
 use crate::App;
+use crate::keybindings::{Action, UNBOUND};
+
+/// Where a help row's keys come from
+enum Keys {
+    Bound(&'static [Action]), // Looked up in App::keybindings, so remapped keys show up as they are
+    Fixed(&'static str),      // Not remappable (mouse, popups, CSV replay controls)
+}
+
+// (keys, where they apply, what they do), grouped into the sections of the table
+type HelpRow = (Keys, &'static str, &'static str);
+
+const SECTIONS: &[(&str, &[HelpRow])] = &[
+    (" TILING & GENERAL ", &[
+        (Keys::Bound(&[Action::SplitHorizontal, Action::SplitVertical]), "", "Split Pane"),
+        (Keys::Bound(&[Action::SplitHorizontalSame, Action::SplitVerticalSame]), "", "Split Pane, Showing the Same View"),
+        (Keys::Bound(&[Action::DuplicatePane]), "", "Duplicate Pane (View, Camera, Replay Position)"),
+        (Keys::Bound(&[Action::ClosePane]), "", "Close Pane"),
+        (Keys::Bound(&[Action::FocusNext]), "", "Focus Next Pane"),
+        (Keys::Fixed("Click"), "", "Focus Pane Under the Mouse"),
+        (Keys::Bound(&[Action::Fullscreen]), "", "Toggle Fullscreen"),
+        (Keys::Bound(&[Action::Maximize]), "", "Maximize (Leaving Restores Pane as It Was)"),
+        (Keys::Bound(&[Action::ExitFullscreen]), "(Fullscreen)", "Back to the Layout"),
+        (Keys::Fixed("Drag Divider"), "", "Resize Panes"),
+        (Keys::Bound(&[Action::RenamePane]), "", "Name Pane (blank = default)"),
+        (Keys::Bound(&[Action::CopyLayout, Action::PasteLayout]), "", "Copy / Paste Layout JSON"),
+    ]),
+    (" PLAYBACK & CAMERA ", &[
+        (Keys::Bound(&[Action::StepBack, Action::StepForward]), "", "Step History (Paused)"),
+        (Keys::Bound(&[Action::AutoAdvance, Action::ToggleAdvanceLoop]), "", "Play Through History (2/5/10 per s) / Loop"),
+        (Keys::Bound(&[Action::ToggleSource]), "", "Pane Source: Main / Overlay (--overlay)"),
+        (Keys::Bound(&[Action::CameraLeft, Action::CameraRight]), "(Fullscreen)", "Rotate 3D Camera"),
+        (Keys::Bound(&[Action::CameraUp, Action::CameraDown]), "(Fullscreen)", "Tilt 3D Camera"),
+        (Keys::Fixed("Mouse Wheel"), "", "Zoom 3D Camera (0.25x-8x)"),
+        (Keys::Bound(&[Action::CycleSmoothing]), "(Fullscreen)", "Cycle Smoothing (Off/Light/Heavy)"),
+        (Keys::Bound(&[Action::CycleSmoothing]), "(Spectrogram)", "Smooth Heatmap (Interpolated) / Blocks"),
+        (Keys::Bound(&[Action::SubcarrierZoomIn, Action::SubcarrierZoomOut, Action::SubcarrierPanLeft, Action::SubcarrierPanRight]), "(Phase)", "Zoom / Pan Subcarrier Range"),
+        (Keys::Bound(&[Action::TogglePhaseUnwrap]), "(Phase / Doppler)", "Toggle Phase Unwrapping"),
+        (Keys::Bound(&[Action::ToggleAmplitudeColor]), "(Polar)", "Color by Age / Amplitude / Phase"),
+        (Keys::Bound(&[Action::CycleProjection]), "(Polar / Isometric)", "Projection: Ortho / Persp / Oblique"),
+        (Keys::Bound(&[Action::ToggleGrid]), "(3D Views)", "Show / Hide Grid and Axis Labels (g is Gain)"),
+        (Keys::Bound(&[Action::ToggleDcNull]), "(Isometric)", "Null DC Subcarrier in CIR"),
+        (Keys::Bound(&[Action::ThresholdUp, Action::ThresholdDown]), "(I/Q Dist.)", "Hide Bins Below Amplitude (Tail)"),
+        (Keys::Bound(&[Action::ToggleCoarseHistory]), "(Waterfall)", "Per-Second History (Long Trends)"),
+        (Keys::Bound(&[Action::ToggleMetadata]), "(Dashboard)", "Gauges / Packet Metadata Table"),
+        (Keys::Bound(&[Action::DepthIncrease, Action::DepthDecrease]), "(Fullscreen)", "History Depth (3D / Spectrogram)"),
+        (Keys::Bound(&[Action::DepthIncrease, Action::DepthDecrease]), "(Constellation / I/Q Dist.)", "Persistence (Recent Packets, Fading)"),
+        (Keys::Bound(&[Action::ResetLive]), "", "Reset to Live/Default"),
+        (Keys::Bound(&[Action::ComparePackets]), "(Fullscreen)", "Compare Anchored vs Live Packet"),
+        (Keys::Bound(&[Action::Inspector]), "", "Inspect One Subcarrier (I/Q, Amp, Phase)"),
+        (Keys::Bound(&[Action::ExportPacket]), "", "Export Shown Packet to CSV (Pane)"),
+        (Keys::Bound(&[Action::CaptureReference, Action::ToggleReference]), "", "Capture Reference / Subtract It (Pane)"),
+        (Keys::Bound(&[Action::GainUp, Action::GainDown]), "", "Amplitude Gain x2 / /2 (All Panes)"),
+        (Keys::Fixed("{ / }"), "", "Replay Speed (CSV only)"),
+        (Keys::Fixed("o"), "", "Replay Loop / Once (CSV only)"),
+        (Keys::Fixed(", / ."), "", "Replay Seek -/+ 5% (CSV only)"),
+    ]),
+    (" MENUS & SYSTEM ", &[
+        (Keys::Bound(&[Action::ViewSelector]), "", "View Selector"),
+        (Keys::Fixed("0-9"), "(in View Selector)", "Mark Panes to Change Together"),
+        (Keys::Bound(&[Action::PrevView, Action::NextView]), "", "Previous / Next View (Focused Pane)"),
+        (Keys::Bound(&[Action::MainMenu]), "", "Main Menu"),
+        (Keys::Bound(&[Action::NextTheme]), "", "Next Theme"),
+        (Keys::Bound(&[Action::Screenshot]), "", "Screenshot (PNG / ANSI)"),
+        (Keys::Bound(&[Action::ExportReport]), "", "Export Report (Markdown)"),
+        (Keys::Bound(&[Action::ResetEsp]), "", "Reset ESP (Serial only)"),
+        (Keys::Bound(&[Action::Help]), "", "This Help"),
+        (Keys::Bound(&[Action::Quit]), "", "Quit"),
+    ]),
+    (" RERUN INTEGRATION ", &[
+        (Keys::Bound(&[Action::ToggleStream]), "", "Toggle Live Streaming"),
+        (Keys::Bound(&[Action::ToggleRecord]), "", "Toggle RRD Recording"),
+    ]),
+];
+
+/// Key column text: the current keys of every action (unbound ones left out), then the context
+fn key_label(app: &App, keys: &Keys, context: &str) -> String {
+    let keys = match keys {
+        Keys::Fixed(text) => text.to_string(),
+        Keys::Bound(actions) => {
+            let bound: Vec<String> = actions.iter()
+                .map(|&action| app.keybindings.keys_for(action))
+                .filter(|keys| keys != UNBOUND)
+                .collect();
+            if bound.is_empty() { UNBOUND.to_string() } else { bound.join(" / ") }
+        }
+    };
+    if context.is_empty() { format!(" {}", keys) } else { format!(" {} {}", keys, context) }
+}
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     // 1. Center the popup
@@ -14,41 +102,22 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     // 3. Define Block
     let block = Block::default()
         .title(" Help ")
+        .title_bottom(Line::from(" Keys follow keybindings.json ").alignment(Alignment::Right))
         .borders(Borders::ALL)
         .border_style(app.theme.focused_border)
         .style(app.theme.root);
 
-    // 4. Content - Table
-    let rows = vec![
-        // Section: Tiling
-        Row::new(vec![Span::styled(" TILING & GENERAL ", Style::default().add_modifier(Modifier::BOLD)), Span::raw("")]),
-        Row::new(vec![" Shift + Arrows", " Split Pane"]),
-        Row::new(vec![" Delete", " Close Pane"]),
-        Row::new(vec![" Tab / Click", " Focus Pane"]),
-        Row::new(vec![" Space", " Toggle Fullscreen"]),
-        Row::new(vec![" Drag Divider", " Resize Panes"]),
-        Row::new(vec!["", ""]),
-
-        // Section: Playback
-        Row::new(vec![Span::styled(" PLAYBACK & CAMERA ", Style::default().add_modifier(Modifier::BOLD)), Span::raw("")]),
-        Row::new(vec![" Left / Right", " Step History (Paused)"]),
-        Row::new(vec![" W / A / S / D", " Move 3D Camera"]),
-        Row::new(vec![" R", " Reset to Live/Default"]),
-        Row::new(vec!["", ""]),
-
-        // Section: Menus
-        Row::new(vec![Span::styled(" MENUS & SYSTEM ", Style::default().add_modifier(Modifier::BOLD)), Span::raw("")]),
-        Row::new(vec![" Enter", " View Selector"]),
-        Row::new(vec![" M", " Main Menu"]),
-        Row::new(vec![" T", " Next Theme"]),
-        Row::new(vec![" Q", " Quit"]),
-        Row::new(vec!["", ""]),
-
-        // Section: Rerun
-        Row::new(vec![Span::styled(" RERUN INTEGRATION ", Style::default().add_modifier(Modifier::BOLD)), Span::raw("")]),
-        Row::new(vec![" Shift + R", " Toggle Live Streaming"]),
-        Row::new(vec![" Shift + L", " Toggle RRD Recording"]),
-    ];
+    // 4. Content - Table, built from the live keybindings
+    let mut rows = Vec::new();
+    for (i, (title, entries)) in SECTIONS.iter().enumerate() {
+        if i > 0 {
+            rows.push(Row::new(vec!["", ""]));
+        }
+        rows.push(Row::new(vec![Span::styled(*title, Style::default().add_modifier(Modifier::BOLD)), Span::raw("")]));
+        for (keys, context, description) in entries.iter() {
+            rows.push(Row::new(vec![key_label(app, keys, context), format!(" {}", description)]));
+        }
+    }
 
     let widths = [
         Constraint::Percentage(40),
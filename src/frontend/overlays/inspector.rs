@@ -0,0 +1,57 @@
+// --- File: src/frontend/overlays/inspector.rs ---
+// --- Purpose: Popup showing the exact I/Q, amplitude and phase of one subcarrier of the live packet ---
+//
+// The index is the position in the ESP buffer (as in exports and the Packet Diff); the
+// physical subcarrier number (DC = 0) is shown next to it.
+
+use ratatui::{prelude::*, widgets::*};
+use crate::App;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let area = crate::frontend::overlays::help::centered_rect(40, 40, area);
+    f.render_widget(Clear, area);
+
+    let theme = &app.theme;
+    let index = app.inspector_index;
+    let input = if app.inspector_input.is_empty() { String::new() } else { format!(" | Go to: {}_", app.inspector_input) };
+    let block = Block::default()
+        .title(format!(" Inspector: Subcarrier {}{} ", index, input))
+        .title_bottom(Line::from(" ←/→: Step | 0-9 + Enter: Jump | Esc: Close ").alignment(Alignment::Center))
+        .borders(Borders::ALL)
+        .border_style(theme.focused_border)
+        .style(theme.root);
+
+    // 1. Look up the subcarrier in the live packet
+    let csi = app.current_stats.csi.as_ref();
+    let count = csi.map_or(0, |c| c.csi_raw_data.len() / 2);
+    let Some((csi, (i_val, q_val))) = csi.and_then(|c| c.subcarrier_iq(index).map(|iq| (c, iq))) else {
+        let text = Paragraph::new(format!("\nNo data for subcarrier {} (packet has {} subcarriers).", index, count))
+            .style(theme.text_normal)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(block);
+        f.render_widget(text, area);
+        return;
+    };
+
+    // 2. Derived values
+    let amplitude = ((i_val * i_val + q_val * q_val) as f64).sqrt();
+    let phase = (q_val as f64).atan2(i_val as f64);
+
+    let row = |name: &str, value: String| Row::new(vec![
+        Cell::from(Span::styled(name.to_string(), theme.text_normal)),
+        Cell::from(Span::styled(value, theme.text_highlight)),
+    ]);
+    let rows = vec![
+        row(" Packet", format!("#{} @ {}", app.current_stats.id, app.stats_format.timestamp(app.current_stats.timestamp))),
+        row(" Index", format!("{} of {}", index, count)),
+        row(" Subcarrier No.", format!("{:+}", csi.subcarrier_frequency_index(index))),
+        row(" I", format!("{}", i_val)),
+        row(" Q", format!("{}", q_val)),
+        row(" Amplitude", format!("{:.2}", amplitude)),
+        row(" Phase", format!("{:+.3} rad ({:+.1}°)", phase, phase.to_degrees())),
+    ];
+
+    let table = Table::new(rows, [Constraint::Length(16), Constraint::Min(10)]).block(block);
+    f.render_widget(table, area);
+}
@@ -8,10 +8,12 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     let area = crate::frontend::overlays::help::centered_rect(40, 40, area);
     f.render_widget(Clear, area);
 
-    let items: Vec<ListItem> = app.available_templates
+    let visible = app.filtered_templates();
+    let items: Vec<ListItem> = visible
         .iter()
         .enumerate()
-        .map(|(i, (name, is_default))| {
+        .map(|(i, &index)| {
+            let (name, is_default) = &app.available_templates[index];
             let style = if i == app.load_selector_index {
                 app.theme.sidebar_selected
             } else {
@@ -30,13 +32,19 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
         .collect();
 
     let title = if app.available_templates.is_empty() {
-        " Load Template (None Found) "
+        " Load Template (None Found) ".to_string()
+    } else if visible.is_empty() {
+        " Load Template (No Match) ".to_string()
     } else {
-        " Load Template ([D] Set Default) "
+        format!(" Load Template ({}/{}) ", visible.len(), app.available_templates.len())
     };
 
+    // Filter line doubles as the key hint
+    let footer = format!(" Filter: {}_ | [Tab] Default | [Del] Delete | [Esc] Close ", app.template_filter);
+
     let block = Block::default()
         .title(title)
+        .title_bottom(Line::from(footer).alignment(Alignment::Center))
         .borders(Borders::ALL)
         .border_style(app.theme.focused_border)
         .style(app.theme.root);
@@ -45,4 +53,4 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
         .block(block);
 
     f.render_widget(list, area);
-}
\ No newline at end of file
+}
@@ -4,17 +4,32 @@
 use ratatui::{prelude::*, widgets::*};
 use crate::App;
 
-pub const MENU_ITEMS: [&str; 6] = [
+pub const MENU_ITEMS: [&str; 21] = [
     "Change Theme",
     "Save Template",
     "Load Template",
     "Export Data",
+    "Export Doppler",
+    "Averaging Mode",
+    "Outlier Rejection",
+    "Gauge Ranges",
+    "Rerun Stream",
+    "Screenshot",
+    "Canvas Marker",
+    "Time Unit",
+    "Decimals",
+    "Phase Colors",
+    "Amplitude Clamp",
+    "Flatten Subcarriers",
+    "PPS Smoothing",
+    "Header Badge",
+    "Capture Reference",
     "Reset ESP",
     "Close Menu"
 ];
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect) {
-    let area = crate::frontend::overlays::help::centered_rect(40, 30, area);
+    let area = crate::frontend::overlays::help::centered_rect(40, 65, area);
     f.render_widget(Clear, area);
 
     let items: Vec<ListItem> = MENU_ITEMS
@@ -27,11 +42,22 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
                 app.theme.text_normal
             };
 
-            // Display current theme next to the "Change Theme" option
-            let display_label = if i == 0 {
-                format!(" {} ({:?}) ", label, app.theme.variant)
-            } else {
-                format!(" {} ", label)
+            // Display current value next to the "Change Theme" / "Averaging Mode" / "Outlier Rejection" / "Rerun Stream" / "Canvas Marker" / number format / phase color / clamp / flatten / PPS / badge options
+            let display_label = match i {
+                0 => format!(" {} ({:?}) ", label, app.theme.variant),
+                5 => format!(" {} ({}) ", label, app.averaging_mode.as_str()),
+                6 => format!(" {} ({}) ", label, app.outlier_label()),
+                8 if app.is_rerun_connected() => format!(" {} (Disconnect {}) ", label, app.rerun_addr),
+                8 => format!(" {} (Connect...) ", label),
+                10 => format!(" {} ({}) ", label, app.canvas_marker.as_str()),
+                11 => format!(" {} ({}) ", label, app.stats_format.unit_label()),
+                12 => format!(" {} ({}) ", label, app.stats_format.decimals),
+                13 => format!(" {} ({}) ", label, app.phase_colormap.as_str()),
+                14 => format!(" {} ({}) ", label, app.amplitude_clamp.as_str()),
+                15 => format!(" {} ({}) ", label, if app.flatten_subcarriers { "On" } else { "Off" }),
+                16 => format!(" {} ({}) ", label, app.pps_smoothing.label()),
+                17 => format!(" {} ({}) ", label, app.header_metric.as_str()),
+                _ => format!(" {} ", label),
             };
 
             ListItem::new(display_label).style(style)
@@ -7,6 +7,14 @@ pub mod quit;
 pub mod view_selector;
 pub mod main_menu;
 pub mod save_template;
+pub mod overwrite_template;
+pub mod delete_template;
 pub mod load_template;
 pub mod theme_selector;
+pub mod gauge_settings;
+pub mod rerun_connect;
+pub mod rename_pane;
 pub mod export_data;
+pub mod packet_diff;
+pub mod inspector;
+pub mod onboarding;
@@ -0,0 +1,47 @@
+// --- File: src/frontend/overlays/onboarding.rs ---
+// --- Purpose: First-run guidance shown until the first packet arrives (see App::onboarding_visible) ---
+
+use ratatui::{prelude::*, widgets::*};
+use crate::App;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let area = crate::frontend::overlays::help::centered_rect(60, 50, area);
+
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Welcome to esp-csi-tui-rs ")
+        .borders(Borders::ALL)
+        .border_style(app.theme.focused_border)
+        .style(app.theme.root);
+
+    let heading = Style::default().add_modifier(Modifier::BOLD);
+    // Tell the user why nothing arrives if the serial source can't find a port
+    let status = match app.serial_status {
+        Some(ref status) => Line::from(Span::styled(status.as_str(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+        None => Line::from(Span::styled("Waiting for CSI data...", app.theme.text_highlight)),
+    };
+    let text = vec![
+        status,
+        Line::from(""),
+        Line::from(Span::styled("Serial", heading)),
+        Line::from("Plug in an ESP32 running csi-cli; the first USB serial port is used."),
+        Line::from("No packets? Shift+E resets the ESP and restarts capture."),
+        Line::from(""),
+        Line::from(Span::styled("Replay a capture", heading)),
+        Line::from("cargo run --release -- path/to/capture.csv"),
+        Line::from(""),
+        Line::from(Span::styled("Try it without hardware", heading)),
+        Line::from("cargo run --release -- --mock"),
+        Line::from(""),
+        Line::from(Span::styled("Press any key to continue", app.theme.text_normal)),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false })
+        .style(app.theme.text_normal);
+
+    f.render_widget(paragraph, area);
+}
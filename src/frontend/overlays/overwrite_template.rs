@@ -0,0 +1,25 @@
+// --- File: src/frontend/overlays/overwrite_template.rs ---
+// --- Purpose: Confirmation popup when saving over an existing template ---
+
+use ratatui::{prelude::*, widgets::*};
+use crate::App;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let area = crate::frontend::overlays::help::centered_rect(40, 20, area);
+
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Confirm Overwrite ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+        .style(app.theme.root);
+
+    let name = crate::config_manager::sanitize_template_name(&app.input_buffer);
+    let text = Paragraph::new(format!("Template '{}' already exists.\nOverwrite it?\n\n[Y] Yes    [N] No", name))
+        .block(block)
+        .alignment(Alignment::Center)
+        .style(app.theme.text_highlight);
+
+    f.render_widget(text, area);
+}
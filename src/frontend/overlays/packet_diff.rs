@@ -0,0 +1,102 @@
+// --- File: src/frontend/overlays/packet_diff.rs ---
+// --- Purpose: Fullscreen popup comparing the pane's anchored packet against the live one ---
+//
+// Rows are sorted by the size of the amplitude change, so the subcarriers that reacted
+// most to a physical change are at the top (the first few are highlighted).
+
+use ratatui::{prelude::*, widgets::*};
+use crate::App;
+use crate::backend::csi_data::SubcarrierDelta;
+
+// Snapshots back from live used as "before" when the pane isn't anchored
+pub const COMPARE_LOOKBACK: usize = 10;
+
+// Largest changes drawn highlighted
+const HIGHLIGHT_COUNT: usize = 5;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let area = crate::frontend::overlays::help::centered_rect(60, 70, area);
+    f.render_widget(Clear, area);
+
+    let theme = &app.theme;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(theme.focused_border)
+        .style(theme.root)
+        .title_bottom(Line::from(" Sorted by |Δ Amp| | Esc: Close ").alignment(Alignment::Center));
+
+    // 1. Pick the two packets
+    let Some((title, mut deltas)) = compare_packets(app) else {
+        let text = Paragraph::new(format!("\nNot enough history to compare (need {} snapshots).", COMPARE_LOOKBACK + 1))
+            .style(theme.text_normal)
+            .alignment(Alignment::Center)
+            .block(block.title(" Packet Diff "));
+        f.render_widget(text, area);
+        return;
+    };
+
+    // 2. Largest amplitude change first
+    deltas.sort_by(|a, b| b.amplitude_delta.abs().total_cmp(&a.amplitude_delta.abs()));
+    let mean_change = if deltas.is_empty() { 0.0 } else {
+        deltas.iter().map(|d| d.amplitude_delta.abs()).sum::<f64>() / deltas.len() as f64
+    };
+
+    // 3. Table
+    let rows: Vec<Row> = deltas
+        .iter()
+        .enumerate()
+        .map(|(rank, d)| {
+            let delta_color = if d.amplitude_delta >= 0.0 { Color::Green } else { Color::Red };
+            let row = Row::new(vec![
+                Cell::from(format!("{:>4}", d.index)),
+                Cell::from(format!("{:>8.1}", d.amplitude_before)),
+                Cell::from(format!("{:>8.1}", d.amplitude_after)),
+                Cell::from(Span::styled(format!("{:>+8.1}", d.amplitude_delta), Style::default().fg(delta_color))),
+                Cell::from(format!("{:>+7.0}°", d.phase_delta.to_degrees())),
+            ]);
+            if rank < HIGHLIGHT_COUNT {
+                row.style(theme.text_highlight.add_modifier(Modifier::BOLD))
+            } else {
+                row.style(theme.text_normal)
+            }
+        })
+        .collect();
+
+    let header = Row::new(vec!["  SC", "  Before", "   After", "   Δ Amp", "   Δ Phase"])
+        .style(theme.text_highlight.add_modifier(Modifier::UNDERLINED));
+
+    let table = Table::new(rows, [
+        Constraint::Length(5),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(10),
+    ])
+    .header(header)
+    .block(block.title(format!(" {} | Mean |Δ Amp|: {:.1} ", title, mean_change)));
+
+    f.render_widget(table, area);
+}
+
+/// ("before → after" label, per-subcarrier deltas) for the fullscreen pane.
+/// Before is the anchored packet, or COMPARE_LOOKBACK snapshots ago when following live.
+fn compare_packets(app: &App) -> Option<(String, Vec<SubcarrierDelta>)> {
+    let pane_id = app.fullscreen_pane_id?;
+    let source = app.source_for(pane_id);
+    let live = source.current_stats.csi.as_ref()?;
+    let anchor = app.pane_states.get(&pane_id).and_then(|s| s.anchor_packet_id);
+
+    let (label, before) = match anchor {
+        Some(id) => {
+            let packet = source.history.iter().find(|p| p.id == id)?;
+            (format!("#{}", id), packet)
+        }
+        None => {
+            let index = source.history.len().checked_sub(COMPARE_LOOKBACK + 1)?;
+            (format!("Live -{}", COMPARE_LOOKBACK), &source.history[index])
+        }
+    };
+
+    let deltas = before.csi.as_ref()?.diff(live);
+    Some((format!("Packet Diff: {} → Live #{}", label, source.live_id()), deltas))
+}
@@ -9,15 +9,25 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
 
     f.render_widget(Clear, area);
 
+    // An RRD recording gets its own warning: quitting stops it (the file is flushed, not lost)
+    let recording = app.is_rerun_recording();
+    let (title, message) = if recording {
+        let path = app.rerun_record_path.as_deref().unwrap_or("RRD file");
+        (" Quit While Recording ", format!("Recording to {} is in progress.\nQuitting stops and saves it.\n\n[Y] Stop & Quit    [N] Keep Recording", path))
+    } else {
+        (" Confirm Quit ", "Are you sure you want to quit?\n\n[Y] Yes    [N] No".to_string())
+    };
+
     let block = Block::default()
-        .title(" Confirm Quit ")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
         .style(app.theme.root);
 
-    let text = Paragraph::new("Are you sure you want to quit?\n\n[Y] Yes    [N] No")
+    let text = Paragraph::new(message)
         .block(block)
         .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false })
         .style(app.theme.text_highlight); // Highlighted text for emphasis
 
     f.render_widget(text, area);
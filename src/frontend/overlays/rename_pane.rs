@@ -0,0 +1,32 @@
+// --- File: src/frontend/overlays/rename_pane.rs ---
+// --- Purpose: Text input popup for naming the focused pane ---
+
+use ratatui::{prelude::*, widgets::*};
+use crate::App;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let area = crate::frontend::overlays::help::centered_rect(40, 20, area);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!(" Name Pane #{} ", app.tiling.focused_pane_id))
+        .borders(Borders::ALL)
+        .border_style(app.theme.focused_border)
+        .style(app.theme.root);
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let lines = vec![
+        Line::from(app.rename_input_buffer.as_str()),
+        Line::from(""),
+        Line::from(Span::styled("e.g. 'TX-left' (leave empty for the default title)", app.theme.text_normal)),
+        Line::from("[Enter] Apply  [Esc] Cancel"),
+    ];
+
+    let input = Paragraph::new(lines)
+        .style(app.theme.text_highlight)
+        .alignment(Alignment::Center);
+
+    f.render_widget(input, inner);
+}
@@ -0,0 +1,32 @@
+// --- File: src/frontend/overlays/rerun_connect.rs ---
+// --- Purpose: Text input popup for the Rerun viewer address ---
+
+use ratatui::{prelude::*, widgets::*};
+use crate::App;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect) {
+    let area = crate::frontend::overlays::help::centered_rect(40, 20, area);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Connect to Rerun Viewer ")
+        .borders(Borders::ALL)
+        .border_style(app.theme.focused_border)
+        .style(app.theme.root);
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let lines = vec![
+        Line::from(app.rerun_input_buffer.as_str()),
+        Line::from(""),
+        Line::from(Span::styled("host[:port] or rerun+http://... URL", app.theme.text_normal)),
+        Line::from("[Enter] Connect  [Esc] Cancel"),
+    ];
+
+    let input = Paragraph::new(lines)
+        .style(app.theme.text_highlight)
+        .alignment(Alignment::Center);
+
+    f.render_widget(input, inner);
+}
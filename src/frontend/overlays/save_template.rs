@@ -17,8 +17,17 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let text = format!("{}\n\n[Enter] Save  [Esc] Cancel", app.input_buffer);
-    let input = Paragraph::new(text)
+    let mut lines = vec![
+        Line::from(app.input_buffer.as_str()),
+        Line::from(""),
+        Line::from("[Enter] Save  [Esc] Cancel"),
+    ];
+    if let Some(err) = &app.save_input_error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(err.as_str(), Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))));
+    }
+
+    let input = Paragraph::new(lines)
         .style(app.theme.text_highlight)
         .alignment(Alignment::Center);
 
@@ -5,7 +5,7 @@ use ratatui::{prelude::*, widgets::*};
 use crate::App;
 use crate::layout_tree::ViewType;
 
-pub const AVAILABLE_VIEWS: [(ViewType, &str); 7] = [
+pub const AVAILABLE_VIEWS: [(ViewType, &str); 11] = [
     (ViewType::Dashboard, "Net Stats"),
     (ViewType::Polar, "Polar Scatter (Amp per SC)"),
     (ViewType::Isometric, "3D Isometric (Channel Impulse Response)"),
@@ -13,6 +13,10 @@ pub const AVAILABLE_VIEWS: [(ViewType, &str); 7] = [
     (ViewType::Phase, "Phase Plot (Phase per SC)"),
     (ViewType::Camera, "Camera Feed"),
     (ViewType::RawScatter, "Multipath Scatte (I/Q Distribution)"),
+    (ViewType::Waterfall, "Amplitude Waterfall (Amp per SC over time)"),
+    (ViewType::Ridgeline, "Amplitude Ridgeline (Amp per SC, stacked packets)"),
+    (ViewType::Constellation, "I/Q Constellation (one packet, SC colored)"),
+    (ViewType::Overview, "Overview (Stats, Amp, Phase, I/Q in one pane)"),
 ];
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect) {
@@ -22,19 +26,31 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect) {
     let items: Vec<ListItem> = AVAILABLE_VIEWS
         .iter()
         .enumerate()
-        .map(|(i, (_, label))| {
-            // Dynamic selection style based on theme
+        .map(|(i, (view, label))| {
+            // Dynamic selection style based on theme; the marker carries the view's accent color
             let style = if i == app.view_selector_index {
                 app.theme.sidebar_selected
             } else {
-                app.theme.text_normal
+                app.title_style(*view)
             };
-            ListItem::new(format!(" {} ", label)).style(style)
+            let marker = Span::styled(" ■", Style::default().fg(view.accent_color(&app.theme)));
+            ListItem::new(Line::from(vec![marker, Span::raw(format!(" {} ", label))])).style(style)
         })
         .collect();
 
+    // Target panes: the marked ones, or the focused pane when nothing is marked
+    let mut marks = app.view_selector_marks.clone();
+    marks.sort_unstable();
+    let targets = if marks.is_empty() {
+        format!(" Pane #{} | 0-9: Mark Panes ", app.tiling.focused_pane_id)
+    } else {
+        let ids: Vec<String> = marks.iter().map(|id| format!("#{}", id)).collect();
+        format!(" Panes {} | 0-9: Mark Panes ", ids.join(" "))
+    };
+
     let block = Block::default()
         .title(" Select View ")
+        .title_bottom(Line::from(targets).alignment(Alignment::Center))
         .borders(Borders::ALL)
         .border_style(app.theme.focused_border)
         .style(app.theme.root);
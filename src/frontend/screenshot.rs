@@ -0,0 +1,225 @@
+// --- File: src/frontend/screenshot.rs ---
+// --- Purpose: Saves the last rendered terminal frame as a PNG (feature "screenshot") or an ANSI text dump ---
+//
+// There is no font available to the rasterizer, so each cell is drawn from its symbol class:
+// Braille dots (canvas plots) and box-drawing lines are reproduced exactly, block elements are
+// filled, and any other glyph becomes a solid foreground box on the cell background.
+
+use std::fs;
+use std::io;
+#[cfg(not(feature = "screenshot"))]
+use std::io::Write;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+
+const SCREENSHOT_DIR: &str = "screenshots";
+
+// Pixel size of one terminal cell in the PNG
+#[cfg(feature = "screenshot")]
+const CELL_W: u32 = 8;
+#[cfg(feature = "screenshot")]
+const CELL_H: u32 = 16;
+
+// Colors used for Color::Reset (the terminal's own defaults are unknown)
+const DEFAULT_FG: [u8; 3] = [229, 229, 229];
+const DEFAULT_BG: [u8; 3] = [0, 0, 0];
+
+/// Writes the buffer to `screenshots/screenshot_<unix secs>.png` (or `.ans` without the feature).
+/// Returns the path that was written.
+pub fn save_snapshot(buffer: &Buffer) -> io::Result<String> {
+    if !std::path::Path::new(SCREENSHOT_DIR).exists() {
+        fs::create_dir(SCREENSHOT_DIR)?;
+    }
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    #[cfg(feature = "screenshot")]
+    {
+        let path = format!("{}/screenshot_{}.png", SCREENSHOT_DIR, timestamp);
+        write_png(buffer, &path)?;
+        Ok(path)
+    }
+
+    #[cfg(not(feature = "screenshot"))]
+    {
+        let path = format!("{}/screenshot_{}.ans", SCREENSHOT_DIR, timestamp);
+        write_ansi(buffer, &path)?;
+        Ok(path)
+    }
+}
+
+/// Resolves a cell's (fg, bg) to RGB, honouring the REVERSED modifier
+fn cell_colors(cell: &Cell) -> ([u8; 3], [u8; 3]) {
+    let fg = to_rgb(cell.fg).unwrap_or(DEFAULT_FG);
+    let bg = to_rgb(cell.bg).unwrap_or(DEFAULT_BG);
+    if cell.modifier.contains(Modifier::REVERSED) { (bg, fg) } else { (fg, bg) }
+}
+
+/// Maps a ratatui color to RGB using the xterm palette. None = terminal default.
+fn to_rgb(color: Color) -> Option<[u8; 3]> {
+    let rgb = match color {
+        Color::Reset => return None,
+        Color::Black => [0, 0, 0],
+        Color::Red => [205, 0, 0],
+        Color::Green => [0, 205, 0],
+        Color::Yellow => [205, 205, 0],
+        Color::Blue => [0, 0, 238],
+        Color::Magenta => [205, 0, 205],
+        Color::Cyan => [0, 205, 205],
+        Color::Gray => [229, 229, 229],
+        Color::DarkGray => [127, 127, 127],
+        Color::LightRed => [255, 0, 0],
+        Color::LightGreen => [0, 255, 0],
+        Color::LightYellow => [255, 255, 0],
+        Color::LightBlue => [92, 92, 255],
+        Color::LightMagenta => [255, 0, 255],
+        Color::LightCyan => [0, 255, 255],
+        Color::White => [255, 255, 255],
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Indexed(i) => indexed_to_rgb(i),
+    };
+    Some(rgb)
+}
+
+fn indexed_to_rgb(i: u8) -> [u8; 3] {
+    const BASIC: [Color; 16] = [
+        Color::Black, Color::Red, Color::Green, Color::Yellow,
+        Color::Blue, Color::Magenta, Color::Cyan, Color::Gray,
+        Color::DarkGray, Color::LightRed, Color::LightGreen, Color::LightYellow,
+        Color::LightBlue, Color::LightMagenta, Color::LightCyan, Color::White,
+    ];
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match i {
+        0..=15 => to_rgb(BASIC[i as usize]).unwrap_or(DEFAULT_FG),
+        16..=231 => {
+            let n = i - 16;
+            [LEVELS[(n / 36) as usize], LEVELS[((n / 6) % 6) as usize], LEVELS[(n % 6) as usize]]
+        }
+        _ => {
+            let v = 8 + 10 * (i - 232);
+            [v, v, v]
+        }
+    }
+}
+
+// --- ANSI Fallback ---
+
+#[cfg(not(feature = "screenshot"))]
+fn write_ansi(buffer: &Buffer, path: &str) -> io::Result<()> {
+    let mut out = io::BufWriter::new(fs::File::create(path)?);
+    let area = buffer.area;
+
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            let (fg, bg) = cell_colors(cell);
+            write!(
+                out,
+                "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m{}",
+                fg[0], fg[1], fg[2], bg[0], bg[1], bg[2], cell.symbol()
+            )?;
+        }
+        writeln!(out, "\x1b[0m")?;
+    }
+
+    out.flush()
+}
+
+// --- PNG Rasterizer ---
+
+#[cfg(feature = "screenshot")]
+fn write_png(buffer: &Buffer, path: &str) -> io::Result<()> {
+    let area = buffer.area;
+    let mut img = image::RgbImage::new(area.width as u32 * CELL_W, area.height as u32 * CELL_H);
+
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            let (fg, bg) = cell_colors(cell);
+            let px = (x - area.left()) as u32 * CELL_W;
+            let py = (y - area.top()) as u32 * CELL_H;
+
+            // 1. Background
+            fill(&mut img, px, py, CELL_W, CELL_H, bg);
+
+            // 2. Foreground by symbol class
+            let ch = cell.symbol().chars().next().unwrap_or(' ');
+            draw_glyph(&mut img, px, py, ch, fg);
+        }
+    }
+
+    img.save(path).map_err(io::Error::other)
+}
+
+#[cfg(feature = "screenshot")]
+fn draw_glyph(img: &mut image::RgbImage, px: u32, py: u32, ch: char, fg: [u8; 3]) {
+    let half_w = CELL_W / 2;
+    let half_h = CELL_H / 2;
+
+    match ch {
+        ' ' => {}
+        // Braille: 2x4 dot matrix, bit order per Unicode (dots 1-3/4-6 down the columns, 7/8 at the bottom)
+        '\u{2800}'..='\u{28FF}' => {
+            const DOTS: [(u32, u32); 8] = [(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2), (0, 3), (1, 3)];
+            let bits = ch as u32 - 0x2800;
+            for (bit, &(col, row)) in DOTS.iter().enumerate() {
+                if bits & (1 << bit) != 0 {
+                    fill(img, px + col * half_w + 1, py + row * (CELL_H / 4) + 1, half_w - 2, CELL_H / 4 - 2, fg);
+                }
+            }
+        }
+        '█' => fill(img, px, py, CELL_W, CELL_H, fg),
+        '▀' => fill(img, px, py, CELL_W, half_h, fg),
+        '▄' => fill(img, px, py + half_h, CELL_W, half_h, fg),
+        '▌' => fill(img, px, py, half_w, CELL_H, fg),
+        '▐' => fill(img, px + half_w, py, half_w, CELL_H, fg),
+        '\u{2580}'..='\u{259F}' => fill(img, px, py, CELL_W, CELL_H, fg),
+        _ => {
+            // Box drawing: connect the cell center to the edges the glyph reaches
+            if let Some((left, right, up, down)) = box_edges(ch) {
+                let cx = px + half_w - 1;
+                let cy = py + half_h - 1;
+                if left { fill(img, px, cy, half_w, 2, fg); }
+                if right { fill(img, cx, cy, CELL_W - half_w + 1, 2, fg); }
+                if up { fill(img, cx, py, 2, half_h, fg); }
+                if down { fill(img, cx, cy, 2, CELL_H - half_h + 1, fg); }
+            } else {
+                // Any other glyph: a solid box stands in for the text
+                fill(img, px + 1, py + 4, CELL_W - 2, CELL_H - 7, fg);
+            }
+        }
+    }
+}
+
+/// (left, right, up, down) connections of the common box-drawing characters
+#[cfg(feature = "screenshot")]
+fn box_edges(ch: char) -> Option<(bool, bool, bool, bool)> {
+    let edges = match ch {
+        '─' | '━' | '═' => (true, true, false, false),
+        '│' | '┃' | '║' => (false, false, true, true),
+        '┌' | '┏' | '╭' | '╔' => (false, true, false, true),
+        '┐' | '┓' | '╮' | '╗' => (true, false, false, true),
+        '└' | '┗' | '╰' | '╚' => (false, true, true, false),
+        '┘' | '┛' | '╯' | '╝' => (true, false, true, false),
+        '├' | '┣' => (false, true, true, true),
+        '┤' | '┫' => (true, false, true, true),
+        '┬' | '┳' => (true, true, false, true),
+        '┴' | '┻' => (true, true, true, false),
+        '┼' | '╋' => (true, true, true, true),
+        _ => return None,
+    };
+    Some(edges)
+}
+
+#[cfg(feature = "screenshot")]
+fn fill(img: &mut image::RgbImage, x: u32, y: u32, w: u32, h: u32, color: [u8; 3]) {
+    for yy in y..(y + h).min(img.height()) {
+        for xx in x..(x + w).min(img.width()) {
+            img.put_pixel(xx, yy, image::Rgb(color));
+        }
+    }
+}
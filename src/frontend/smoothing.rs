@@ -0,0 +1,300 @@
+// --- File: src/frontend/smoothing.rs ---
+// --- Purpose: Display-side exponential smoothing of per-subcarrier I/Q values ---
+
+use crate::{App, NetworkStats};
+use crate::backend::csi_data::{self, CsiData};
+use crate::frontend::view_state::ViewState;
+
+/// Amplitude drawn at the edge of fixed-scale plots (I/Q components are 8-bit signed).
+/// Views that normally auto-scale switch to this once App::amplitude_gain is not 1.0,
+/// so the same gain boosts every pane by the same amount.
+pub const FULL_SCALE_AMPLITUDE: f64 = 128.0;
+
+/// Auto-scale ceiling for amplitude views (Main Menu -> Amplitude Clamp). A few huge outliers
+/// otherwise set the maximum and leave the bulk of the data uniformly dim.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AmplitudeClamp {
+    #[default]
+    Off,
+    P99,
+    P95,
+}
+
+impl AmplitudeClamp {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AmplitudeClamp::Off => "Off",
+            AmplitudeClamp::P99 => "99th pct",
+            AmplitudeClamp::P95 => "95th pct",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            AmplitudeClamp::Off => AmplitudeClamp::P99,
+            AmplitudeClamp::P99 => AmplitudeClamp::P95,
+            AmplitudeClamp::P95 => AmplitudeClamp::Off,
+        }
+    }
+
+    /// Percentile used as the maximum (None = the plain maximum)
+    pub fn percentile(&self) -> Option<f64> {
+        match self {
+            AmplitudeClamp::Off => None,
+            AmplitudeClamp::P99 => Some(99.0),
+            AmplitudeClamp::P95 => Some(95.0),
+        }
+    }
+}
+
+/// Maximum for auto-scaling: the given percentile of `values`, never below their median
+/// (and the plain maximum if that would be 0, so sparse data still shows up).
+/// Values above it are meant to be clamped by the caller. 0.0 for no values.
+pub fn robust_max(values: impl Iterator<Item = f64>, percentile: Option<f64>) -> f64 {
+    let mut sorted: Vec<f64> = values.filter(|v| v.is_finite()).collect();
+    let Some(percentile) = percentile else {
+        return sorted.into_iter().fold(0.0, f64::max);
+    };
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let last = sorted.len() - 1;
+    let rank = ((percentile.clamp(0.0, 100.0) / 100.0) * last as f64).round() as usize;
+    let clamped = sorted[rank].max(sorted[last / 2]);
+    if clamped > 0.0 { clamped } else { sorted[last] }
+}
+
+/// Extracts (I, Q) pairs per subcarrier from each snapshot, applying an EMA across time.
+/// Smoothing is done on the complex value so amplitude and phase are blended together
+/// without wrap-around artifacts. `alpha = 1.0` returns the raw data.
+/// Packets without CSI yield an empty row (and reset the filter).
+pub fn ema_iq(packets: &[NetworkStats], alpha: f32) -> Vec<Vec<(f64, f64)>> {
+    let alpha = (alpha as f64).clamp(0.0, 1.0);
+    let mut out: Vec<Vec<(f64, f64)>> = Vec::with_capacity(packets.len());
+
+    for packet in packets {
+        let raw: Vec<(f64, f64)> = match &packet.csi {
+            Some(csi) => csi.csi_raw_data
+                .chunks_exact(2)
+                .map(|pair| (pair[0] as f64, pair[1] as f64))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let row = match out.last() {
+            Some(prev) if alpha < 1.0 => raw
+                .iter()
+                .enumerate()
+                .map(|(s, &(i, q))| match prev.get(s) {
+                    Some(&(pi, pq)) => (alpha * i + (1.0 - alpha) * pi, alpha * q + (1.0 - alpha) * pq),
+                    None => (i, q),
+                })
+                .collect(),
+            _ => raw,
+        };
+        out.push(row);
+    }
+
+    out
+}
+
+/// Replaces every frame with its complex difference from `reference` (H - H_ref).
+/// Returns false (frames untouched) if any frame's subcarrier count differs from the reference.
+pub fn subtract_reference(frames: &mut [Vec<(f64, f64)>], reference: &CsiData) -> bool {
+    let reference: Vec<(f64, f64)> = reference.csi_raw_data
+        .chunks_exact(2)
+        .map(|pair| (pair[0] as f64, pair[1] as f64))
+        .collect();
+    if frames.iter().any(|row| !row.is_empty() && row.len() != reference.len()) {
+        return false;
+    }
+
+    for row in frames.iter_mut() {
+        for ((i, q), &(ri, rq)) in row.iter_mut().zip(reference.iter()) {
+            *i -= ri;
+            *q -= rq;
+        }
+    }
+    true
+}
+
+/// Applies App::reference to a pane's frames if the pane has subtraction enabled.
+/// Returns a footer tag describing what happened ("" when subtraction is off).
+pub fn apply_reference(app: &App, state: &ViewState, frames: &mut [Vec<(f64, f64)>]) -> &'static str {
+    if !state.subtract_reference {
+        return "";
+    }
+    match app.reference {
+        None => "| Ref: none [Shift+B] ",
+        Some(ref reference) if subtract_reference(frames, reference) => "| Δ Ref ",
+        Some(_) => "| Ref: SC mismatch ",
+    }
+}
+
+/// Puts every frame into frequency order (DC centred) using the newest packet's bandwidth.
+/// Returns the subcarrier numbers for the positions of the longest frame (axis labels).
+/// Call after reference subtraction, which works in buffer order.
+pub fn center_dc(frames: &mut [Vec<(f64, f64)>], newest: &NetworkStats) -> Vec<i32> {
+    let cwb = newest.csi.as_ref().map_or(0, |csi| csi.cwb);
+    for row in frames.iter_mut() {
+        *row = csi_data::to_frequency_order(row, cwb);
+    }
+    let count = frames.iter().map(|row| row.len()).max().unwrap_or(0);
+    csi_data::frequency_axis(count, cwb)
+}
+
+/// Zeroes the DC subcarrier (number 0 of every LTF block) in buffer-order frames, using the
+/// newest packet's bandwidth. Its carrier-leakage value otherwise biases the whole CIR.
+pub fn null_dc(frames: &mut [Vec<(f64, f64)>], newest: &NetworkStats) {
+    let cwb = newest.csi.as_ref().map_or(0, |csi| csi.cwb);
+    for row in frames.iter_mut() {
+        let count = row.len();
+        for (s, iq) in row.iter_mut().enumerate() {
+            if csi_data::frequency_index(s, count, cwb) == 0 {
+                *iq = (0.0, 0.0);
+            }
+        }
+    }
+}
+
+/// Snapshots the running mean settles over: a plain average of the first ones, then an EMA
+/// with this time constant (10 s of snapshots), slow enough that motion still stands out.
+pub const FLATTEN_WINDOW: usize = 100;
+
+/// Means below this amplitude (DC / guard nulls, dead subcarriers) are not divided by
+const MIN_FLATTEN_MEAN: f64 = 1.0;
+
+/// Running mean amplitude of every subcarrier (buffer order) of the primary source,
+/// the frequency response that Main Menu -> Flatten Subcarriers divides out
+#[derive(Clone, Debug, Default)]
+pub struct SubcarrierMean {
+    pub mean: Vec<f64>,
+    pub count: usize, // Snapshots averaged so far (capped at FLATTEN_WINDOW)
+}
+
+impl SubcarrierMean {
+    /// Adds a snapshot; a different subcarrier count starts over
+    pub fn update(&mut self, csi: &CsiData) {
+        let amplitudes: Vec<f64> = csi.csi_raw_data
+            .chunks_exact(2)
+            .map(|pair| (pair[0] as f64).hypot(pair[1] as f64))
+            .collect();
+        if amplitudes.is_empty() {
+            return;
+        }
+        if amplitudes.len() != self.mean.len() {
+            self.clear();
+            self.mean = vec![0.0; amplitudes.len()];
+        }
+
+        self.count = (self.count + 1).min(FLATTEN_WINDOW);
+        let weight = 1.0 / self.count as f64;
+        for (mean, amplitude) in self.mean.iter_mut().zip(amplitudes) {
+            *mean += (amplitude - *mean) * weight;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.mean.clear();
+        self.count = 0;
+    }
+}
+
+/// Divides every subcarrier by its running mean, scaled back to the mean of all usable
+/// subcarriers so the overall level (and fixed-gain scales) stay put. Subcarriers with a
+/// near-zero mean are left alone. Returns false (frames untouched) if nothing is averaged yet
+/// or any frame's subcarrier count differs from the mean's.
+pub fn flatten(frames: &mut [Vec<(f64, f64)>], mean: &SubcarrierMean) -> bool {
+    let usable: Vec<f64> = mean.mean.iter().copied().filter(|&m| m >= MIN_FLATTEN_MEAN).collect();
+    if usable.is_empty() || frames.iter().any(|row| !row.is_empty() && row.len() != mean.mean.len()) {
+        return false;
+    }
+    let level = usable.iter().sum::<f64>() / usable.len() as f64;
+
+    for row in frames.iter_mut() {
+        for ((i, q), &m) in row.iter_mut().zip(mean.mean.iter()) {
+            if m >= MIN_FLATTEN_MEAN {
+                *i *= level / m;
+                *q *= level / m;
+            }
+        }
+    }
+    true
+}
+
+/// Applies App::subcarrier_mean to pane `id`'s frames (buffer order) while flattening is on.
+/// The means follow the primary source only, so panes showing the --overlay source are left
+/// as they are. Returns a footer tag describing what happened ("" when it is off).
+pub fn apply_flatten(app: &App, id: usize, frames: &mut [Vec<(f64, f64)>]) -> &'static str {
+    if !app.flatten_subcarriers {
+        return "";
+    }
+    if app.source_for(id).overlay_label.is_some() {
+        return "| Flat: n/a ";
+    }
+    if flatten(frames, &app.subcarrier_mean) { "| Flat " } else { "| Flat: n/a " }
+}
+
+/// Multiplies every I/Q pair by `gain` (scales amplitude, leaves phase alone). 1.0 is a no-op.
+pub fn apply_gain(frames: &mut [Vec<(f64, f64)>], gain: f32) {
+    if gain == 1.0 {
+        return;
+    }
+    let gain = gain as f64;
+    for (i, q) in frames.iter_mut().flatten() {
+        *i *= gain;
+        *q *= gain;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flattening_divides_each_subcarrier_by_its_running_mean() {
+        let csi = |values: Vec<i32>| CsiData { csi_raw_data: values, ..CsiData::default() };
+        let mut mean = SubcarrierMean::default();
+
+        // Nothing averaged yet: frames are left alone
+        let mut frames = vec![vec![(10.0, 0.0), (40.0, 0.0), (0.0, 0.0)]];
+        assert!(!flatten(&mut frames, &mean));
+
+        // Strong and weak subcarrier come out at the common level; the null one is not divided by zero
+        mean.update(&csi(vec![10, 0, 40, 0, 0, 0]));
+        assert!(flatten(&mut frames, &mean));
+        assert_eq!(frames[0], vec![(25.0, 0.0), (25.0, 0.0), (0.0, 0.0)]);
+
+        // Plain average at first, capped to the window, and a new subcarrier count starts over
+        mean.update(&csi(vec![20, 0, 40, 0, 0, 0]));
+        assert_eq!(mean.mean, vec![15.0, 40.0, 0.0]);
+        for _ in 0..2 * FLATTEN_WINDOW {
+            mean.update(&csi(vec![20, 0, 40, 0, 0, 0]));
+        }
+        assert_eq!(mean.count, FLATTEN_WINDOW);
+        mean.update(&csi(vec![3, 4]));
+        assert_eq!((mean.mean.clone(), mean.count), (vec![5.0], 1));
+
+        // Mismatched frames are skipped; clearing drops the means
+        let mut frames = vec![vec![(1.0, 1.0), (2.0, 2.0)]];
+        assert!(!flatten(&mut frames, &mean));
+        mean.clear();
+        assert!(mean.mean.is_empty());
+    }
+
+    #[test]
+    fn amplitude_clamp_ignores_outliers_but_not_the_bulk() {
+        let mut values: Vec<f64> = (1..=99).map(|v| v as f64 / 10.0).collect();
+        values.push(1000.0);
+
+        assert_eq!(robust_max(values.iter().copied(), AmplitudeClamp::Off.percentile()), 1000.0);
+        assert_eq!(robust_max(values.iter().copied(), AmplitudeClamp::P95.percentile()), 9.5);
+        assert_eq!(robust_max(std::iter::empty(), AmplitudeClamp::P95.percentile()), 0.0);
+
+        // Never below the median, and the maximum if the percentile lands on zero
+        assert_eq!(robust_max([1.0, 5.0, 5.0, 5.0].into_iter(), Some(10.0)), 5.0);
+        assert_eq!(robust_max([0.0, 0.0, 0.0, 7.0].into_iter(), Some(50.0)), 7.0);
+    }
+}
@@ -2,6 +2,7 @@
 // --- Purpose: Defines color palettes (Dark, Light, Nordic, Gruvbox, Catppuccin) and styling logic ---
 
 use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols::Marker;
 use serde::{Serialize, Deserialize};
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -13,6 +14,194 @@ pub enum ThemeType {
     Catppuccin,
 }
 
+impl ThemeType {
+    /// Dark or Light to match the terminal background, read from COLORFGBG ("fg;bg", set by
+    /// rxvt, Konsole, iTerm2 and others). Falls back to Dark when unset or unrecognized.
+    pub fn detect_terminal_default() -> Self {
+        std::env::var("COLORFGBG")
+            .ok()
+            .and_then(|value| Self::from_colorfgbg(&value))
+            .unwrap_or(ThemeType::Dark)
+    }
+
+    fn from_colorfgbg(value: &str) -> Option<Self> {
+        // The background is the last field (some terminals insert a middle "default" field)
+        let bg: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+        // ANSI 7 (light gray) and 9-15 (bright colors, 15 = white) are light backgrounds; 8 is dark gray
+        match bg {
+            7 | 9..=15 => Some(ThemeType::Light),
+            0..=6 | 8 => Some(ThemeType::Dark),
+            _ => None,
+        }
+    }
+}
+
+/// Character set the Canvas views draw with. Braille has the finest resolution but some
+/// fonts lack the glyphs, which leaves the 3D views blank; the others trade detail for coverage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum CanvasMarker {
+    #[default]
+    Braille,
+    HalfBlock,
+    Block,
+    Dot,
+}
+
+impl CanvasMarker {
+    pub fn as_str(&self) -> &str {
+        match self {
+            CanvasMarker::Braille => "Braille",
+            CanvasMarker::HalfBlock => "Half Block",
+            CanvasMarker::Block => "Block",
+            CanvasMarker::Dot => "Dot",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            CanvasMarker::Braille => CanvasMarker::HalfBlock,
+            CanvasMarker::HalfBlock => CanvasMarker::Block,
+            CanvasMarker::Block => CanvasMarker::Dot,
+            CanvasMarker::Dot => CanvasMarker::Braille,
+        }
+    }
+
+    pub fn marker(&self) -> Marker {
+        match self {
+            CanvasMarker::Braille => Marker::Braille,
+            CanvasMarker::HalfBlock => Marker::HalfBlock,
+            CanvasMarker::Block => Marker::Block,
+            CanvasMarker::Dot => Marker::Dot,
+        }
+    }
+}
+
+/// Colors for a phase angle, shared by the TUI views and the Rerun export so both agree.
+/// Hue is cyclic (-π and +π get the same color); RedBlue is the original blue-to-red ramp.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PhaseColormap {
+    #[default]
+    Hue,
+    RedBlue,
+}
+
+impl PhaseColormap {
+    pub fn as_str(&self) -> &str {
+        match self {
+            PhaseColormap::Hue => "Hue",
+            PhaseColormap::RedBlue => "Red/Blue",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            PhaseColormap::Hue => PhaseColormap::RedBlue,
+            PhaseColormap::RedBlue => PhaseColormap::Hue,
+        }
+    }
+
+    /// RGB for a phase in radians; any angle is wrapped into -π..π first
+    pub fn rgb(&self, phase: f64) -> [u8; 3] {
+        use std::f64::consts::{PI, TAU};
+        let norm = (phase + PI).rem_euclid(TAU) / TAU; // 0.0 at -π, towards 1.0 at +π
+        match self {
+            PhaseColormap::Hue => hue_to_rgb(norm),
+            PhaseColormap::RedBlue => {
+                let c = (norm * 255.0).clamp(0.0, 255.0) as u8;
+                [c, 100, 255 - c]
+            }
+        }
+    }
+}
+
+// Fully saturated, full-value HSV color for a hue in 0.0..1.0 (red at 0, green at 1/3, blue at 2/3)
+fn hue_to_rgb(hue: f64) -> [u8; 3] {
+    let h = hue.rem_euclid(1.0) * 6.0;
+    let x = 1.0 - (h % 2.0 - 1.0).abs();
+    let (r, g, b) = match h as u8 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    [(r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8]
+}
+
+/// Terminal color for a phase. Light themes get a darker shade so thin lines stay readable on white.
+pub fn phase_to_color(phase: f64, colormap: PhaseColormap, theme: &Theme) -> Color {
+    let [r, g, b] = colormap.rgb(phase);
+    if theme.variant == ThemeType::Light {
+        let dim = |c: u8| (c as f64 * 0.7) as u8;
+        return Color::Rgb(dim(r), dim(g), dim(b));
+    }
+    Color::Rgb(r, g, b)
+}
+
+/// Unit of the packet timestamps shown in view footers and the Dashboard
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum TimeUnit {
+    #[default]
+    Millis,
+    Seconds,
+}
+
+/// How timestamps and fractional stats are printed (saved with templates).
+/// Past a minute the timestamp switches to HH:MM:SS elapsed in either unit.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatsFormat {
+    pub time_unit: TimeUnit,
+    pub decimals: usize, // Fraction digits for seconds and fractional stats (0-3)
+}
+
+// Most fraction digits StatsFormat::decimals cycles through
+pub const MAX_DECIMALS: usize = 3;
+
+impl Default for StatsFormat {
+    fn default() -> Self {
+        Self { time_unit: TimeUnit::Millis, decimals: 1 }
+    }
+}
+
+impl StatsFormat {
+    pub fn unit_label(&self) -> &str {
+        match self.time_unit {
+            TimeUnit::Millis => "ms",
+            TimeUnit::Seconds => "s",
+        }
+    }
+
+    pub fn next_unit(&mut self) {
+        self.time_unit = match self.time_unit {
+            TimeUnit::Millis => TimeUnit::Seconds,
+            TimeUnit::Seconds => TimeUnit::Millis,
+        };
+    }
+
+    pub fn next_decimals(&mut self) {
+        self.decimals = (self.decimals + 1) % (MAX_DECIMALS + 1);
+    }
+
+    /// Timestamp (ms since start): "1234ms" / "1.2s", or "01:02:05" once past a minute
+    pub fn timestamp(&self, ms: u64) -> String {
+        if ms >= 60_000 {
+            let secs = ms / 1000;
+            return format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60);
+        }
+        match self.time_unit {
+            TimeUnit::Millis => format!("{}ms", ms),
+            TimeUnit::Seconds => format!("{:.*}s", self.decimals.min(MAX_DECIMALS), ms as f64 / 1000.0),
+        }
+    }
+
+    /// A fractional stat with the configured number of decimals
+    pub fn number(&self, value: f64) -> String {
+        format!("{:.*}", self.decimals.min(MAX_DECIMALS), value)
+    }
+}
+
 pub struct Theme {
     pub variant: ThemeType,
     pub root: Style,
@@ -26,6 +215,19 @@ pub struct Theme {
 }
 
 impl Theme {
+    /// Border color for a signal level: green at or above `strong`, red below `weak`, yellow between.
+    /// Carries no modifiers, so patching it onto focused_border keeps the focus bold.
+    pub fn signal_border(&self, rssi: i32, strong: i32, weak: i32) -> Style {
+        let color = if rssi >= strong {
+            Color::Green
+        } else if rssi >= weak {
+            Color::Yellow
+        } else {
+            Color::Red
+        };
+        Style::default().fg(color)
+    }
+
     pub fn new(variant: ThemeType) -> Self {
         match variant {
             ThemeType::Dark => Self {
@@ -89,4 +291,111 @@ impl Theme {
             },
         }
     }
-}
\ No newline at end of file
+
+    /// Six-step intensity ramp shared by the heatmap views and their colorbar.
+    /// `intensity` is expected in 0.0..=1.0.
+    pub fn heatmap_color(&self, intensity: f64) -> Color {
+        if intensity > 0.8 {
+            Color::Magenta
+        } else if intensity > 0.6 {
+            Color::Red
+        } else if intensity > 0.4 {
+            Color::Yellow
+        } else if intensity > 0.2 {
+            Color::Green
+        } else if intensity > 0.05 {
+            Color::Cyan
+        } else {
+            Color::DarkGray
+        }
+    }
+
+    /// Age fade for the stacked-line views (Phase wireframe, Polar trails, Ridgeline).
+    /// `age` runs from 0.0 (oldest) to 1.0 (newest) and steps from the inactive border
+    /// color towards `gauge_color`; the newest fifth is always exactly `gauge_color`.
+    pub fn age_color(&self, age: f64) -> Color {
+        let weight = if age > 0.8 {
+            return self.gauge_color;
+        } else if age > 0.5 {
+            2.0 / 3.0
+        } else if age > 0.2 {
+            1.0 / 3.0
+        } else {
+            0.0
+        };
+
+        blend(self.normal_border.fg.unwrap_or(Color::DarkGray), self.gauge_color, weight)
+    }
+
+    /// Phosphor fade for points that keep their own color (persistence views).
+    /// `age` runs from 0.0 (oldest) to 1.0 (newest); the oldest points sit near the
+    /// inactive border color and the newest are drawn in `color` itself.
+    pub fn fade(&self, color: Color, age: f64) -> Color {
+        blend(self.normal_border.fg.unwrap_or(Color::DarkGray), color, 0.15 + 0.85 * age.clamp(0.0, 1.0))
+    }
+}
+
+// Mix of two colors (weight 0.0 = `from`, 1.0 = `to`); colors without RGB snap to the nearer end
+fn blend(from: Color, to: Color, weight: f64) -> Color {
+    match (ansi_rgb(from), ansi_rgb(to)) {
+        (Some(a), Some(b)) => {
+            let mix = |i: usize| (a[i] as f64 + (b[i] as f64 - a[i] as f64) * weight).round() as u8;
+            Color::Rgb(mix(0), mix(1), mix(2))
+        }
+        _ if weight > 0.5 => to,
+        _ => from,
+    }
+}
+
+/// RGB of a terminal color (xterm defaults for the 16 named colors); None for Reset / indexed
+fn ansi_rgb(color: Color) -> Option<[u8; 3]> {
+    Some(match color {
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Black => [0, 0, 0],
+        Color::Red => [205, 0, 0],
+        Color::Green => [0, 205, 0],
+        Color::Yellow => [205, 205, 0],
+        Color::Blue => [0, 0, 238],
+        Color::Magenta => [205, 0, 205],
+        Color::Cyan => [0, 205, 205],
+        Color::Gray => [229, 229, 229],
+        Color::DarkGray => [127, 127, 127],
+        Color::LightRed => [255, 0, 0],
+        Color::LightGreen => [0, 255, 0],
+        Color::LightYellow => [255, 255, 0],
+        Color::LightBlue => [92, 92, 255],
+        Color::LightMagenta => [255, 0, 255],
+        Color::LightCyan => [0, 255, 255],
+        Color::White => [255, 255, 255],
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_format_switches_to_elapsed_clock_after_a_minute() {
+        let mut format = StatsFormat::default();
+        assert_eq!(format.timestamp(1234), "1234ms");
+        format.time_unit = TimeUnit::Seconds;
+        format.decimals = 2;
+        assert_eq!(format.timestamp(1234), "1.23s");
+        assert_eq!(format.timestamp(3_725_000), "01:02:05");
+    }
+
+    #[test]
+    fn age_fade_follows_the_theme() {
+        let themes = [ThemeType::Dark, ThemeType::Light, ThemeType::Nordic, ThemeType::Gruvbox, ThemeType::Catppuccin];
+        for variant in themes {
+            let theme = Theme::new(variant);
+            // Newest is always exactly the accent, older lines are dimmer
+            assert_eq!(theme.age_color(1.0), theme.gauge_color, "{:?}", variant);
+            assert_ne!(theme.age_color(0.0), theme.gauge_color, "{:?}", variant);
+            assert_ne!(theme.age_color(0.6), theme.age_color(0.3), "{:?}", variant);
+        }
+        // No more fixed cyan/blue: the middle of the fade differs between themes
+        assert_ne!(Theme::new(ThemeType::Nordic).age_color(0.6), Theme::new(ThemeType::Gruvbox).age_color(0.6));
+    }
+}
@@ -3,7 +3,10 @@
 
 use ratatui::prelude::*;
 use ratatui::widgets::*;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
 use crate::App;
+use crate::keybindings::Action;
 use crate::layout_tree::{LayoutNode, ViewType, SplitDirection};
 use crate::frontend::views::*;
 use crate::frontend::overlays::*;
@@ -27,7 +30,8 @@ pub fn ui(f: &mut Frame, app: &App) {
     draw_header(f, app, chunks[0]);
 
     // 3. Draw Main Area
-    if let Some(fs_id) = app.fullscreen_pane_id {
+    // A fullscreen pane that was closed falls back to the layout (App::clear_stale_fullscreen)
+    if let Some(fs_id) = app.fullscreen_pane_id.filter(|&id| app.tiling.pane_exists(id)) {
         let view_type = find_view_type(&app.tiling.root, fs_id).unwrap_or(ViewType::Empty);
         render_pane(f, app, chunks[1], fs_id, view_type, true);
     } else {
@@ -35,41 +39,107 @@ pub fn ui(f: &mut Frame, app: &App) {
         draw_tree(f, app, &app.tiling.root, chunks[1], Vec::new());
     }
 
-    // 4. Draw Footer
+    // Stale data shouldn't look live: dim every pane while no packets arrive
+    if app.gauge_ranges.dim_on_signal_loss && app.signal_lost() {
+        f.buffer_mut().set_style(chunks[1], Style::default().add_modifier(Modifier::DIM));
+    }
+
+    // 4. Draw Footer (and any toast just above it)
     draw_footer(f, app, chunks[2]);
+    draw_toast(f, app, chunks[1]);
 
     // 5. Draw Overlays
+    if app.onboarding_visible() { onboarding::draw(f, app, f.area()); }
     if app.show_help { help::draw(f, app, f.area()); }
     if app.show_view_selector { view_selector::draw(f, app, f.area()); }
     if app.show_main_menu { main_menu::draw(f, app, f.area()); }
     if app.show_save_input { save_template::draw(f, app, f.area()); }
+    if app.show_overwrite_confirm { overwrite_template::draw(f, app, f.area()); }
     if app.show_load_selector { load_template::draw(f, app, f.area()); }
+    if app.confirm_delete_template.is_some() { delete_template::draw(f, app, f.area()); }
     if app.show_export_input { export_data::draw(f, app, f.area()); }
+    if app.show_rerun_input { rerun_connect::draw(f, app, f.area()); }
+    if app.show_rename_input { rename_pane::draw(f, app, f.area()); }
     if app.show_theme_selector { theme_selector::draw(f, app, f.area()); }
+    if app.show_gauge_settings { gauge_settings::draw(f, app, f.area()); }
+    if app.show_packet_diff { packet_diff::draw(f, app, f.area()); }
+    if app.show_inspector { inspector::draw(f, app, f.area()); }
     if app.show_quit_popup { quit::draw(f, app, f.area()); }
 }
 
+/// Renders one full frame (tiling + overlays) off-screen and returns its cells.
+/// Lets tests compare against golden buffers without a real terminal. Everything drawn
+/// comes from `app` alone, except a toast, which disappears once TOAST_DURATION has passed.
+pub fn render_to_buffer(app: &App, area: Rect) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(area.width, area.height))
+        .expect("TestBackend never fails to initialize");
+    let frame = terminal
+        .draw(|f| ui(f, app))
+        .expect("TestBackend never fails to draw");
+    frame.buffer.clone()
+}
+
 fn draw_header(f: &mut Frame, app: &App, area: Rect) {
     // Build status indicators
     let mut status_parts = Vec::new();
 
+    // Chosen key metric first (Main Menu -> Header Badge)
+    if let Some((text, color)) = app.header_badge() {
+        status_parts.push(Span::styled(format!(" {} ", text), Style::default().fg(color).add_modifier(Modifier::BOLD)));
+    }
+
     // Rerun status
-    if let Some(ref streamer) = app.rerun_streamer {
-        if let Ok(s) = streamer.lock() {
-            if s.is_connected() {
-                status_parts.push(Span::styled(" 🔴LIVE ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)));
-            }
-            if s.is_recording() {
-                status_parts.push(Span::styled(" ⏺REC ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
-            }
+    if let Some(ref streamer) = app.rerun_streamer && let Ok(s) = streamer.lock() {
+        if s.is_connected() {
+            status_parts.push(Span::styled(" 🔴LIVE ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)));
+        } else if s.last_error().is_some() {
+            // Full message is in the footer notice / report; the badge just flags it
+            status_parts.push(Span::styled(" ⚠RERUN ERR ", Style::default().fg(Color::Red)));
+        }
+        if s.is_recording() {
+            status_parts.push(Span::styled(" ⏺REC ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
         }
     }
 
-    let hotkeys = if app.fullscreen_pane_id.is_some() {
-        " [Space] Exit Fullscreen | [Arrows] Playback | [WASD] Move Camera | [R] Reset Live | [Q] Quit "
-    } else {
-        " [Shift+Arrow] Split | [Del] Close | [Drag] Resize | [0-9] Focus | [Enter] View | [M] Menu | [Shift+R] Stream | [Shift+L] Record "
-    };
+    // Serial source still looking for a port (details in the onboarding overlay)
+    if app.serial_reconnecting {
+        status_parts.push(Span::styled(" ⟳SERIAL RECONNECTING ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK)));
+    } else if app.serial_status.is_some() {
+        status_parts.push(Span::styled(" ⚠NO SERIAL DEVICE ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)));
+    }
+
+    // No packets for a while (unplugged / crashed ESP)
+    if app.signal_lost() {
+        status_parts.push(Span::styled(" ⚠SIGNAL LOST ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK)));
+    }
+
+    // Amplitude spread above the alarm threshold (someone moving)
+    if app.motion_detected() {
+        status_parts.push(Span::styled(format!(" ◉MOTION {} ", app.stats_format.number(app.motion_energy as f64)), Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)));
+    }
+
+    // Focused pane playing through history
+    let active_pane = app.fullscreen_pane_id.unwrap_or(app.tiling.focused_pane_id);
+    if let Some(label) = app.pane_states.get(&active_pane).and_then(|s| s.auto_advance_label()) {
+        status_parts.push(Span::styled(format!(" {} ", label), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    }
+
+    // Global amplitude gain (only flagged when it isn't identity)
+    if app.amplitude_gain != 1.0 {
+        status_parts.push(Span::styled(format!(" GAIN {}x ", app.amplitude_gain), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+    }
+
+    // Auto-scale ignoring the strongest outliers
+    if let Some(percentile) = app.amplitude_clamp.percentile() {
+        status_parts.push(Span::styled(format!(" CLAMP P{} ", percentile), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+    }
+
+    // Per-subcarrier normalization of the amplitude views
+    if app.flatten_subcarriers {
+        status_parts.push(Span::styled(" FLAT ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)));
+    }
+
+    let hotkeys = hotkey_line(app, if app.fullscreen_pane_id.is_some() { FULLSCREEN_HOTKEYS } else { LAYOUT_HOTKEYS });
 
     // Use theme colors for the header
     // Background: Normal Border Color (usually a muted gray)
@@ -77,23 +147,140 @@ fn draw_header(f: &mut Frame, app: &App, area: Rect) {
     let bg_color = app.theme.normal_border.fg.unwrap_or(Color::DarkGray);
     let fg_color = app.theme.root.fg.unwrap_or(Color::White);
 
-    let header = Paragraph::new(hotkeys)
+    status_parts.push(Span::raw(hotkeys));
+    let header = Paragraph::new(Line::from(status_parts))
         .style(Style::default().bg(bg_color).fg(fg_color).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center);
     f.render_widget(header, area);
 }
 
-fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
-    let text = "esp-csi-tui-rs,DDN@2025";
+/// One-line notice on the bottom row of the tiling area, e.g. "Saved template lab"
+fn draw_toast(f: &mut Frame, app: &App, area: Rect) {
+    let Some((ref message, at)) = app.toast else { return };
+    if at.elapsed() >= crate::app::TOAST_DURATION || area.height == 0 {
+        return;
+    }
 
+    let text = format!(" {} ", message);
+    let width = (text.chars().count() as u16).min(area.width);
+    let toast_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.bottom() - 1,
+        width,
+        height: 1,
+    };
+
+    let bg_color = app.theme.root.bg.unwrap_or(Color::Reset);
+    let toast = Paragraph::new(text)
+        .style(Style::default().bg(bg_color).fg(app.theme.gauge_color).add_modifier(Modifier::BOLD));
+    f.render_widget(Clear, toast_area);
+    f.render_widget(toast, toast_area);
+}
+
+/// First keys currently bound to `actions`, joined with "/" (None if none of them is bound)
+fn bound_keys(app: &App, actions: &[Action]) -> Option<String> {
+    let keys: Vec<String> = actions.iter().filter_map(|&action| app.keybindings.first_key_for(action)).collect();
+    (!keys.is_empty()).then(|| keys.join("/"))
+}
+
+enum HeaderKey {
+    Bound(&'static [Action]), // Looked up in App::keybindings, like the footer hints
+    Fixed(&'static str),      // Not remappable (mouse, pane digits)
+}
+
+// Hotkeys in the header while a pane is fullscreen, and over the layout
+const FULLSCREEN_HOTKEYS: &[(HeaderKey, &str)] = &[
+    (HeaderKey::Bound(&[Action::ExitFullscreen]), "Exit Fullscreen"),
+    (HeaderKey::Bound(&[Action::StepBack, Action::StepForward]), "Playback"),
+    (HeaderKey::Bound(&[Action::CameraUp, Action::CameraLeft, Action::CameraDown, Action::CameraRight]), "Move Camera"),
+    (HeaderKey::Bound(&[Action::CycleSmoothing]), "Smoothing"),
+    (HeaderKey::Bound(&[Action::ResetLive]), "Reset Live"),
+    (HeaderKey::Bound(&[Action::Quit]), "Quit"),
+];
+const LAYOUT_HOTKEYS: &[(HeaderKey, &str)] = &[
+    (HeaderKey::Bound(&[Action::SplitHorizontal, Action::SplitVertical]), "Split"),
+    (HeaderKey::Bound(&[Action::ClosePane]), "Close"),
+    (HeaderKey::Fixed("Drag"), "Resize"),
+    (HeaderKey::Fixed("0-9"), "Focus"),
+    (HeaderKey::Bound(&[Action::ViewSelector]), "View"),
+    (HeaderKey::Bound(&[Action::MainMenu]), "Menu"),
+    (HeaderKey::Bound(&[Action::ToggleStream]), "Stream"),
+    (HeaderKey::Bound(&[Action::ToggleRecord]), "Record"),
+];
+
+/// Header hotkeys with the keys currently bound (" [Space] Exit Fullscreen | ... "); unbound ones are left out
+fn hotkey_line(app: &App, hotkeys: &[(HeaderKey, &str)]) -> String {
+    let parts: Vec<String> = hotkeys.iter()
+        .filter_map(|(key, what)| {
+            let key = match key {
+                HeaderKey::Bound(actions) => bound_keys(app, actions)?,
+                HeaderKey::Fixed(key) => key.to_string(),
+            };
+            Some(format!("[{}] {}", key, what))
+        })
+        .collect();
+    format!(" {} ", parts.join(" | "))
+}
+
+/// Footer hints of a view with the keys currently bound to each action ("w/a/s/d rotate, +/- depth").
+/// Hints whose actions are all unbound are left out.
+pub fn hint_text(app: &App, view: ViewType) -> String {
+    let hints: Vec<String> = view.hint_line().iter()
+        .filter_map(|&(actions, what)| {
+            if actions.is_empty() {
+                return Some(what.to_string());
+            }
+            bound_keys(app, actions).map(|keys| format!("{} {}", keys, what))
+        })
+        .collect();
+    hints.join(", ")
+}
+
+fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
     // Dimmer, not highlighted: Use root background and DarkGray text
     let bg_color = app.theme.root.bg.unwrap_or(Color::Reset);
     let fg_color = Color::DarkGray;
+    let style = Style::default().bg(bg_color).fg(fg_color).add_modifier(Modifier::ITALIC);
 
-    let footer = Paragraph::new(text)
-        .style(Style::default().bg(bg_color).fg(fg_color).add_modifier(Modifier::ITALIC))
-        .alignment(Alignment::Center);
-    f.render_widget(footer, area);
+    let Some(ref file) = app.replay_file else {
+        // Keys of the focused view on the left, credit on the right
+        const CREDIT: &str = "esp-csi-tui-rs,DDN@2025 ";
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(CREDIT.len() as u16)])
+            .split(area);
+
+        let active_pane = app.fullscreen_pane_id.unwrap_or(app.tiling.focused_pane_id);
+        let view = find_view_type(&app.tiling.root, active_pane).unwrap_or(ViewType::Empty);
+        let hint = format!(" #{} {}: {}", active_pane, view.as_str(), hint_text(app, view));
+        f.render_widget(Paragraph::new(hint).style(style), chunks[0]);
+        f.render_widget(Paragraph::new(CREDIT).style(style).alignment(Alignment::Right), chunks[1]);
+        return;
+    };
+
+    // Replay: status text on the left, scrub/progress bar on the right
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Percentage(40)])
+        .split(area);
+
+    let text = format!(
+        "Replay: {} | {}x [ {{ / }} ] | {} [O] | Seek [ , / . ]",
+        file,
+        app.replay_speed,
+        if app.replay_loop { "Loop" } else { "Once" },
+    );
+    f.render_widget(Paragraph::new(text).style(style).alignment(Alignment::Center), chunks[0]);
+
+    let (index, total) = app.replay_position;
+    let ratio = if total > 1 { index as f64 / (total - 1) as f64 } else { 0.0 };
+    let progress = LineGauge::default()
+        .ratio(ratio.clamp(0.0, 1.0))
+        .label(format!("{}/{} ", (index + 1).min(total), total))
+        .style(Style::default().bg(bg_color))
+        .filled_style(Style::default().fg(app.theme.gauge_color))
+        .unfilled_style(Style::default().fg(Color::DarkGray));
+    f.render_widget(progress, chunks[1]);
 }
 
 fn draw_tree(f: &mut Frame, app: &App, node: &LayoutNode, area: Rect, path: Vec<usize>) {
@@ -146,12 +333,22 @@ fn draw_tree(f: &mut Frame, app: &App, node: &LayoutNode, area: Rect, path: Vec<
 }
 
 fn render_pane(f: &mut Frame, app: &App, area: Rect, id: usize, view: ViewType, is_focused: bool) {
+    let (min_width, min_height) = view.min_size();
+    if area.width < min_width || area.height < min_height {
+        draw_too_small(f, app, area, is_focused, id);
+        return;
+    }
+
     match view {
         ViewType::Dashboard => stats::draw(f, app, area, is_focused, id),
         ViewType::Phase => phase::draw(f, app, area, is_focused, id),
         ViewType::RawScatter => raw_scatter::draw(f, app, area, is_focused, id),
         ViewType::Polar => polar::draw(f, app, area, is_focused, id),
         ViewType::Spectrogram => spectrogram::draw(f, app, area, is_focused, id),
+        ViewType::Waterfall => waterfall::draw(f, app, area, is_focused, id),
+        ViewType::Overview => overview::draw(f, app, area, is_focused, id),
+        ViewType::Ridgeline => ridgeline::draw(f, app, area, is_focused, id),
+        ViewType::Constellation => constellation::draw(f, app, area, is_focused, id),
         ViewType::Isometric => time_domain_iso::draw(f, app, area, is_focused, id),
         _ => draw_empty(f, app, area, is_focused, &view, id),
     }
@@ -173,10 +370,23 @@ fn find_view_type(node: &LayoutNode, target_id: usize) -> Option<ViewType> {
     }
 }
 
+/// Placeholder for panes below ViewType::min_size (drops the border when there's no room for it)
+fn draw_too_small(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
+    let border_style = if is_focused { app.theme.focused_border } else { app.theme.normal_border };
+    let mut text = Paragraph::new(format!("#{} — too small", id))
+        .style(app.theme.text_normal)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    if area.height >= 3 {
+        text = text.block(Block::default().borders(Borders::ALL).border_style(border_style).style(app.theme.root));
+    }
+    f.render_widget(text, area);
+}
+
 fn draw_empty(f: &mut Frame, app: &App, area: Rect, is_focused: bool, view_type: &ViewType, id: usize) {
     let border_style = if is_focused { app.theme.focused_border } else { app.theme.normal_border };
     let block = Block::default()
-        .title(format!(" #{} Empty ", id))
+        .title(Span::styled(format!(" #{} {} ", id, app.pane_label(id, "Empty")), app.title_style(*view_type)))
         .borders(Borders::ALL)
         .border_style(border_style)
         .style(app.theme.root);
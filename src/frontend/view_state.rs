@@ -1,26 +1,203 @@
 // --- File: src/frontend/view_state.rs ---
 // --- Purpose: Stores persistent state for each pane (Camera, Playback, Pause) ---
 
-#[derive(Clone, Debug)]
+use serde::{Serialize, Deserialize};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ViewState {
     // Temporal State
     // If Some(id), we are locked to that specific packet ID (Paused/Replay).
     // If None, we are following the Live head.
+    // Not saved in templates: packet IDs are meaningless across sessions.
+    #[serde(skip)]
     pub anchor_packet_id: Option<u64>,
 
+    // Auto-Advance (play through history)
+    // If Some(interval), the anchor moves one packet forward every interval (App::on_tick).
+    // At the newest packet it loops back to the oldest (advance_loop) or returns to Live.
+    #[serde(skip)]
+    pub auto_advance: Option<Duration>,
+    #[serde(skip)]
+    pub advance_loop: bool,
+    #[serde(skip)]
+    pub last_advance: Option<Instant>,
+
     // Spatial State (3D / Camera)
     pub camera_x: f64,
     pub camera_y: f64,
     pub zoom: f64,
+
+    // Display Filter
+    // EMA weight of the newest packet (1.0 = raw, lower = smoother)
+    pub smoothing_alpha: f32,
+
+    // History Depth
+    // Packets drawn behind the newest one. None = the view's default (ViewType::default_depth).
+    pub history_depth: Option<usize>,
+
+    // Subcarrier Zoom (Phase view)
+    // Visible [start, end) subcarrier range. None = show all.
+    pub subcarrier_range: Option<(usize, usize)>,
+
+    // Baseline Subtraction
+    // Show live CSI minus App::reference (amplitude/phase views)
+    pub subtract_reference: bool,
+
+    // Phase Unwrapping
+    // Remove ±PI jumps along the subcarrier axis (Phase / Doppler views)
+    pub unwrap_phase: bool,
+
+    // Amplitude Coloring (Polar tunnel)
+    // Color segments by amplitude (theme heatmap) instead of by packet age
+    pub color_by_amplitude: bool,
+    // Or by phase (App::phase_colormap); only one of the two is set at a time
+    pub color_by_phase: bool,
+
+    // DC Null (CIR view)
+    // Zero the DC subcarrier before the IDFT
+    pub null_dc: bool,
+
+    // Smooth Heatmap (Spectrogram)
+    // Resample the cells to the canvas dots (bilinear) instead of one block per cell
+    pub smooth_heatmap: bool,
+
+    // Tail Threshold (I/Q Distribution)
+    // Hide bins closer than this amplitude to the origin and rescale to the rest. 0 = show all.
+    pub iq_threshold: f64,
+
+    // Persistence (I/Q Constellation / Distribution)
+    // Packets drawn together, older ones fading out (phosphor). 1 = the shown packet only;
+    // 0 = off (Constellation: one packet, Distribution: the whole cumulative history)
+    pub persistence: usize,
+
+    // Coarse History (Waterfall)
+    // Draw App::history_coarse (one point per second) instead of every snapshot
+    pub coarse_history: bool,
+
+    // Metadata Table (Dashboard)
+    // Show every CsiData field of the shown packet instead of the gauges
+    pub show_metadata: bool,
+
+    // Grid & Labels (Polar / Isometric / I/Q Distribution)
+    // Hide reference rings, boxes, axes and their labels; one reference marker always stays
+    pub hide_grid: bool,
+
+    // Data Source
+    // Draw the overlay source (App::overlay, --overlay <csv>) instead of the primary one
+    pub overlay_source: bool,
+
+    // 3D Projection (Polar / Isometric)
+    // None = the view's default (ViewType::default_projection)
+    pub projection: Option<Projection>,
+
+    // User Label
+    // Shown in the pane title next to the view name (e.g. "TX-left"). None = view name only.
+    pub label: Option<String>,
+}
+
+// Auto-advance intervals cycled by Action::AutoAdvance (2, 5 and 10 packets/s; 10/s = recorded speed)
+pub const AUTO_ADVANCE_RATES: [Duration; 3] = [
+    Duration::from_millis(500),
+    Duration::from_millis(200),
+    Duration::from_millis(100),
+];
+
+/// How the 3D views flatten their scene onto the canvas
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Projection {
+    Orthographic, // Parallel, depth only shows through camera rotation
+    Perspective,  // Older packets shrink toward the vanishing point
+    Oblique,      // Depth drawn as a fixed diagonal offset (shear)
+}
+
+// Perspective camera distance, as a multiple of the scene depth (the farthest packet is drawn at 60%)
+const PERSPECTIVE_DISTANCE: f64 = 1.5;
+// Keeps the camera off the scene when it has no depth (a single packet)
+const MIN_PERSPECTIVE_DEPTH: f64 = 1.0;
+
+impl Projection {
+    pub fn next(self) -> Self {
+        match self {
+            Projection::Orthographic => Projection::Perspective,
+            Projection::Perspective => Projection::Oblique,
+            Projection::Oblique => Projection::Orthographic,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Projection::Orthographic => "Ortho",
+            Projection::Perspective => "Persp",
+            Projection::Oblique => "Oblique",
+        }
+    }
+
+    /// Maps a camera-space point to 2D. `z` is 0 at the front and negative into the screen.
+    /// `shear` is the Oblique screen offset per unit of depth; `scene_depth` is the largest |z|
+    /// and sets the Perspective camera distance (vanishing point at the origin).
+    pub fn project(self, x: f64, y: f64, z: f64, shear: (f64, f64), scene_depth: f64) -> (f64, f64) {
+        match self {
+            Projection::Orthographic => (x, y),
+            Projection::Oblique => (x - z * shear.0, y - z * shear.1),
+            Projection::Perspective => {
+                let distance = scene_depth.max(MIN_PERSPECTIVE_DEPTH) * PERSPECTIVE_DISTANCE;
+                let factor = distance / (distance - z.min(0.0));
+                (x * factor, y * factor)
+            }
+        }
+    }
+}
+
+// Camera zoom limits and per-wheel-notch factor (3D views)
+const MIN_ZOOM: f64 = 0.25;
+const MAX_ZOOM: f64 = 8.0;
+const ZOOM_STEP: f64 = 1.25;
+
+// I/Q tail threshold step and limit (the grid spans ±128, so its corners are at ~181)
+const IQ_THRESHOLD_STEP: f64 = 16.0;
+const MAX_IQ_THRESHOLD: f64 = 176.0;
+
+// Longest persistence, in packets
+const MAX_PERSISTENCE: usize = 200;
+
+// Narrowest subcarrier window the zoom will go down to
+const MIN_SUBCARRIER_SPAN: usize = 8;
+
+impl Default for ViewState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ViewState {
     pub fn new() -> Self {
         Self {
             anchor_packet_id: None,
+            auto_advance: None,
+            advance_loop: false,
+            last_advance: None,
             camera_x: 0.0,
             camera_y: 0.0,
             zoom: 1.0,
+            smoothing_alpha: 1.0,
+            history_depth: None,
+            subcarrier_range: None,
+            subtract_reference: false,
+            unwrap_phase: true,
+            color_by_amplitude: false,
+            color_by_phase: false,
+            null_dc: false,
+            smooth_heatmap: false,
+            iq_threshold: 0.0,
+            persistence: 0,
+            coarse_history: false,
+            show_metadata: false,
+            overlay_source: false,
+            hide_grid: false,
+            projection: None,
+            label: None,
         }
     }
 
@@ -63,6 +240,186 @@ impl ViewState {
 
     pub fn reset_live(&mut self) {
         self.anchor_packet_id = None;
+        self.auto_advance = None;
+    }
+
+    /// Cycles auto-advance: Off -> 2/s -> 5/s -> 10/s -> Off.
+    /// Starting from Live rewinds to the oldest packet so there is something to play through.
+    pub fn cycle_auto_advance(&mut self, min_id: u64) {
+        let next = match self.auto_advance {
+            None => Some(AUTO_ADVANCE_RATES[0]),
+            Some(current) => AUTO_ADVANCE_RATES
+                .iter()
+                .position(|&rate| rate == current)
+                .and_then(|i| AUTO_ADVANCE_RATES.get(i + 1).copied()),
+        };
+        self.auto_advance = next;
+        self.last_advance = Some(Instant::now());
+        if next.is_some() && self.anchor_packet_id.is_none() {
+            self.anchor_packet_id = Some(min_id);
+        }
+    }
+
+    /// One auto-advance tick: steps forward once the interval has passed. Past the newest packet
+    /// it loops to the oldest or stops and goes Live; an expired anchor restarts at the oldest.
+    pub fn advance(&mut self, current_live_id: u64, min_id: u64) {
+        let Some(interval) = self.auto_advance else { return };
+        if self.last_advance.is_some_and(|at| at.elapsed() < interval) {
+            return;
+        }
+        self.last_advance = Some(Instant::now());
+
+        match self.anchor_packet_id {
+            Some(target) if target < min_id => self.anchor_packet_id = Some(min_id),
+            Some(target) if target < current_live_id => self.anchor_packet_id = Some(target + 1),
+            _ if self.advance_loop => self.anchor_packet_id = Some(min_id),
+            _ => self.reset_live(),
+        }
+    }
+
+    /// Footer/status text while auto-advancing, e.g. "▶ 5/s ⟲"
+    pub fn auto_advance_label(&self) -> Option<String> {
+        self.auto_advance.map(|interval| {
+            let rate = (1.0 / interval.as_secs_f64()).round();
+            format!("▶ {}/s{}", rate, if self.advance_loop { " ⟲" } else { "" })
+        })
+    }
+
+    // --- Spatial Logic ---
+
+    /// Scales the 3D camera by one wheel notch, clamped to 0.25x - 8x
+    pub fn zoom_camera(&mut self, zoom_in: bool) {
+        let factor = if zoom_in { ZOOM_STEP } else { 1.0 / ZOOM_STEP };
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    // --- History Depth ---
+
+    /// Effective depth, clamped to [1, available] (available = packets in history)
+    pub fn history_depth(&self, default: usize, available: usize) -> usize {
+        self.history_depth.unwrap_or(default).clamp(1, available.max(1))
+    }
+
+    /// Grows/shrinks the depth by ~20% (at least one packet). The stored depth isn't clamped to
+    /// the history, so growing it while the history is still short keeps it for later; shrinking
+    /// starts from what is drawn (at most `available`) so it takes effect right away.
+    pub fn adjust_history_depth(&mut self, increase: bool, default: usize, available: usize) {
+        let stored = self.history_depth.unwrap_or(default).max(1);
+        let current = if increase { stored } else { stored.min(available.max(1)) };
+        let step = (current / 5).max(1);
+        let next = if increase { current + step } else { current.saturating_sub(step) };
+        self.history_depth = Some(next.max(1));
+    }
+
+    // --- Persistence ---
+
+    /// Grows/shrinks the persistence by ~20% (at least one packet); below 1 it turns off
+    pub fn adjust_persistence(&mut self, increase: bool) {
+        let step = (self.persistence / 5).max(1);
+        let next = if increase { self.persistence + step } else { self.persistence.saturating_sub(step) };
+        self.persistence = next.min(MAX_PERSISTENCE);
+    }
+
+    /// Footer text; `off` names what the view shows without persistence
+    pub fn persistence_label(&self, off: &str) -> String {
+        match self.persistence {
+            0 => off.to_string(),
+            1 => "1 packet".to_string(),
+            n => format!("{} packets", n),
+        }
+    }
+
+    // --- Projection ---
+
+    pub fn projection(&self, default: Projection) -> Projection {
+        self.projection.unwrap_or(default)
+    }
+
+    /// Ortho -> Persp -> Oblique -> Ortho, starting from the view's default
+    pub fn cycle_projection(&mut self, default: Projection) {
+        self.projection = Some(self.projection(default).next());
+    }
+
+    // --- Display Filter ---
+
+    /// Cycles the Polar ring coloring: Age -> Amplitude -> Phase -> Age
+    pub fn cycle_ring_color(&mut self) {
+        (self.color_by_amplitude, self.color_by_phase) = match (self.color_by_amplitude, self.color_by_phase) {
+            (false, false) => (true, false),
+            (true, _) => (false, true),
+            (false, true) => (false, false),
+        };
+    }
+
+    /// Cycles smoothing: Off (1.0) -> Light (0.5) -> Heavy (0.2) -> Off
+    pub fn cycle_smoothing(&mut self) {
+        self.smoothing_alpha = if self.smoothing_alpha >= 1.0 {
+            0.5
+        } else if self.smoothing_alpha >= 0.5 {
+            0.2
+        } else {
+            1.0
+        };
+    }
+
+    pub fn smoothing_label(&self) -> String {
+        if self.smoothing_alpha >= 1.0 {
+            "Off".to_string()
+        } else {
+            format!("α={:.1}", self.smoothing_alpha)
+        }
+    }
+
+    /// Raises/lowers the I/Q tail threshold by one step (0 = off)
+    pub fn adjust_iq_threshold(&mut self, increase: bool) {
+        let step = if increase { IQ_THRESHOLD_STEP } else { -IQ_THRESHOLD_STEP };
+        self.iq_threshold = (self.iq_threshold + step).clamp(0.0, MAX_IQ_THRESHOLD);
+    }
+
+    // --- Subcarrier Zoom ---
+
+    /// Resolves the visible [start, end) range against the actual subcarrier count
+    pub fn subcarrier_window(&self, total: usize) -> (usize, usize) {
+        if total == 0 {
+            return (0, 0);
+        }
+        let (start, end) = self.subcarrier_range.unwrap_or((0, total));
+        let end = end.clamp(1, total);
+        (start.min(end - 1), end)
+    }
+
+    /// Halves/doubles the visible span around its center. Zooming out to the full width clears the range.
+    pub fn zoom_subcarriers(&mut self, zoom_in: bool, total: usize) {
+        if total == 0 {
+            return;
+        }
+        let (start, end) = self.subcarrier_window(total);
+        let span = end - start;
+        let center = start + span / 2;
+
+        let new_span = if zoom_in {
+            (span / 2).max(MIN_SUBCARRIER_SPAN.min(total))
+        } else {
+            span * 2
+        };
+        if new_span >= total {
+            self.subcarrier_range = None;
+            return;
+        }
+
+        let new_start = center.saturating_sub(new_span / 2).min(total - new_span);
+        self.subcarrier_range = Some((new_start, new_start + new_span));
+    }
+
+    /// Slides a zoomed window left/right, keeping its width
+    pub fn pan_subcarriers(&mut self, delta: i64, total: usize) {
+        if self.subcarrier_range.is_none() || total == 0 {
+            return;
+        }
+        let (start, end) = self.subcarrier_window(total);
+        let span = end - start;
+        let new_start = (start as i64 + delta).clamp(0, (total - span) as i64) as usize;
+        self.subcarrier_range = Some((new_start, new_start + span));
     }
 
     // --- Spatial Logic ---
@@ -85,4 +442,40 @@ impl ViewState {
             self.camera_x += period;
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn depth_raised_on_a_short_history_is_kept_for_later() {
+        let mut state = ViewState::new();
+
+        // 5 packets so far, default 20: + grows from the default, drawing stays at 5
+        state.adjust_history_depth(true, 20, 5);
+        assert_eq!(state.history_depth, Some(24));
+        assert_eq!(state.history_depth(20, 5), 5);
+        assert_eq!(state.history_depth(20, 300), 24);
+
+        // - starts from what is drawn, so it shows right away
+        state.adjust_history_depth(false, 20, 5);
+        assert_eq!(state.history_depth, Some(4));
+        for _ in 0..10 {
+            state.adjust_history_depth(false, 20, 5);
+        }
+        assert_eq!(state.history_depth(20, 5), 1);
+    }
+
+    #[test]
+    fn projections_treat_depth_differently() {
+        // A point 10 units into the screen, in a scene 10 units deep
+        assert_eq!(Projection::Orthographic.project(4.0, 2.0, -10.0, (0.5, 0.3), 10.0), (4.0, 2.0));
+        assert_eq!(Projection::Oblique.project(4.0, 2.0, -10.0, (0.5, 0.3), 10.0), (9.0, 5.0));
+        let (x, y) = Projection::Perspective.project(4.0, 2.0, -10.0, (0.5, 0.3), 10.0);
+        assert!((x - 2.4).abs() < 1e-9 && (y - 1.2).abs() < 1e-9, "far end drawn at 60%");
+        // A flat scene still has a camera distance
+        assert!(Projection::Perspective.project(4.0, 2.0, 0.0, (0.0, 0.0), 0.0).0.is_finite());
+        assert_eq!(Projection::Oblique.next().next(), Projection::Perspective);
+    }
+}
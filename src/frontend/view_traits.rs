@@ -1,31 +1,51 @@
 // --- File: src/frontend/view_traits.rs ---
 // --- Purpose: Traits to categorize views (Temporal vs Spatial) ---
+//
+// The two categories are independent; a view can be both.
+//
+// | View          | Temporal (Left/Right step) | Spatial (WASD / Up/Down tilt / Wheel zoom) |
+// |---------------|----------------------------|--------------------------------------------|
+// | Dashboard     | yes                        | no                                         |
+// | Polar         | yes                        | yes                                        |
+// | Isometric     | yes                        | yes                                        |
+// | Spectrogram   | yes                        | no                                         |
+// | Phase         | yes                        | no                                         |
+// | RawScatter    | yes                        | yes                                        |
+// | Waterfall     | yes                        | no                                         |
+// | Overview      | yes                        | no                                         |
+// | Ridgeline     | yes                        | no                                         |
+// | Constellation | yes                        | no                                         |
+// | Camera        | no                         | no                                         |
+// | Empty         | no                         | no                                         |
 
 use crate::layout_tree::ViewType;
 
 pub trait ViewBehavior {
+    /// Reads ViewState::anchor_packet_id, so it can be paused and stepped through history
     fn is_temporal(&self) -> bool;
+    /// Reads ViewState::camera_x/camera_y/zoom, so it can be rotated, tilted and zoomed
     fn is_spatial(&self) -> bool;
 }
 
 impl ViewBehavior for ViewType {
     fn is_temporal(&self) -> bool {
-        match self {
+        matches!(self,
             ViewType::Dashboard |
+            ViewType::Polar |
+            ViewType::Isometric |
             ViewType::Spectrogram |
             ViewType::Phase |
-            ViewType::RawScatter => true,
-            _ => false,
-        }
+            ViewType::RawScatter |
+            ViewType::Waterfall |
+            ViewType::Overview |
+            ViewType::Ridgeline |
+            ViewType::Constellation)
     }
 
     fn is_spatial(&self) -> bool {
-        match self {
+        matches!(self,
             ViewType::Polar |
             ViewType::Isometric |
-            ViewType::Phase |
-            ViewType::RawScatter => true,
-            _ => false,
-        }
+            ViewType::RawScatter)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,133 @@
+// --- File: src/frontend/views/constellation.rs ---
+// --- Purpose: Per-packet I/Q constellation (every subcarrier of one packet as a point) ---
+//
+// [Graph Description]
+// One dot per subcarrier of the live (or anchored) packet in the complex plane.
+// X-Axis: In-phase component (I)
+// Y-Axis: Quadrature component (Q)
+// Color: Subcarrier number around the hue circle (lowest red, through green and blue, highest magenta)
+//
+// [Plotting Logic]
+// The packet's I/Q pairs are put into frequency order (DC centred) and plotted as Canvas points.
+// Both axes are scaled to the largest |I| or |Q| of the packet (plus a margin) and stretched to the
+// pane's aspect ratio, so one unit of I is as long as one unit of Q and circles stay round.
+// With persistence (ViewState::persistence) the previous packets are drawn first, faded toward
+// the border color by age (Theme::fade), so the shown packet is always on top in full color.
+//
+// [Concepts & Application]
+// The instantaneous counterpart of the I/Q Distribution histogram. With a steady channel the
+// points trace a smooth curve as the phase rotates across the band (the slope is the packet
+// delay); multipath bends and loops the curve, and noise smears it. Neighbouring subcarriers
+// have neighbouring colors, so the order of the curve can be followed.
+//
+// [Demo]
+// Step through history (Left/Right) and watch the curve rotate as the receiver's timing drifts.
+// Raise the persistence (+/-) to let the last packets linger: the trail shows the cluster each
+// subcarrier wanders in, between this instantaneous view and the cumulative I/Q Distribution.
+//
+use ratatui::{prelude::*, widgets::*};
+use ratatui::widgets::canvas::{Canvas, Line as CanvasLine, Points};
+use crate::App;
+use crate::frontend::theme::{phase_to_color, PhaseColormap};
+
+// Headroom around the outermost point
+const MARGIN: f64 = 1.1;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
+    let theme = &app.theme;
+    let state = app.pane_states.get(&id).cloned().unwrap_or_else(crate::frontend::view_state::ViewState::new);
+
+    let border_style = app.pane_border(is_focused, crate::layout_tree::ViewType::Constellation);
+    let source = app.source_for(id);
+    let history_len = source.history.len();
+
+    // 1. Determine Status & Target Packet
+    let mut status_label = " [LIVE] ".to_string();
+    let mut status_style = Style::default().fg(Color::Green).add_modifier(Modifier::BOLD);
+    let mut target_index = history_len.saturating_sub(1);
+
+    if let Some(anchor) = state.anchor_packet_id {
+        if let Some(idx) = source.index_of(anchor) {
+            target_index = idx;
+            status_label = format!(" [REPLAY ID:{}] ", anchor);
+            status_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        } else {
+            status_label = " [EXPIRED] ".to_string();
+            status_style = Style::default().fg(Color::Red);
+        }
+    }
+
+    let title_top = Line::from(vec![
+        Span::styled(format!(" #{} {} ", id, app.pane_label(id, "I/Q Constellation")), app.title_style(crate::layout_tree::ViewType::Constellation)),
+        Span::styled(status_label, status_style),
+    ]);
+
+    // Handle empty history
+    if history_len == 0 {
+        let block = Block::default()
+            .title(title_top)
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .style(theme.root);
+        f.render_widget(block, area);
+        return;
+    }
+
+    // 2. I/Q pairs in frequency order, the shown packet last (plus the persistence trail before it)
+    let lingering = state.persistence.saturating_sub(1);
+    let window = source.history_window(target_index, lingering);
+    let Some(stats) = window.last() else { return };
+    let mut frames = crate::frontend::smoothing::ema_iq(window, 1.0);
+    let sc_labels = crate::frontend::smoothing::center_dc(&mut frames, stats);
+    let sc_count = frames.last().map_or(0, |points| points.len());
+
+    // 3. Auto-scale to the data extent, square units
+    let extent = frames.iter().flatten().map(|&(i, q)| i.abs().max(q.abs())).fold(0.0, f64::max).max(1.0) * MARGIN;
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let aspect = inner.width as f64 / (2.0 * inner.height.max(1) as f64); // Cells are about twice as tall as wide
+    let (x_half, y_half) = if aspect >= 1.0 { (extent * aspect, extent) } else { (extent, extent / aspect) };
+
+    // 4. Build Block
+    let first = sc_labels.first().copied().unwrap_or(0);
+    let last = sc_labels.last().copied().unwrap_or(0);
+    let footer = format!(" SC: {} points ({}..{}) | Extent: ±{:.0} | Persist: {} [+/-] | Time: {} ", sc_count, first, last, extent / MARGIN, state.persistence_label("Off"), app.stats_format.timestamp(stats.timestamp));
+    let block = Block::default()
+        .title(title_top)
+        .title_bottom(Line::from(Span::styled(footer, theme.text_highlight)).alignment(Alignment::Right))
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .style(theme.root);
+
+    // 5. Render Canvas
+    let count = sc_count.max(1) as f64;
+    let packets = frames.len();
+    let canvas = Canvas::default()
+        .marker(app.canvas_marker.marker())
+        .block(block)
+        .background_color(theme.root.bg.unwrap_or(Color::Reset))
+        .x_bounds([-x_half, x_half])
+        .y_bounds([-y_half, y_half])
+        .paint(move |ctx| {
+            let axis_color = theme.normal_border.fg.unwrap_or(Color::DarkGray);
+
+            // I and Q axes through the origin
+            ctx.draw(&CanvasLine { x1: -x_half, y1: 0.0, x2: x_half, y2: 0.0, color: axis_color });
+            ctx.draw(&CanvasLine { x1: 0.0, y1: -y_half, x2: 0.0, y2: y_half, color: axis_color });
+            ctx.print(x_half * 0.92, 0.0, "I");
+            ctx.print(0.0, y_half * 0.92, "Q");
+            ctx.layer();
+
+            // Oldest packet first; subcarrier number -> hue (lowest red ... highest magenta)
+            for (t, points) in frames.iter().enumerate() {
+                let age = (t + 1) as f64 / packets as f64;
+                for (s, &(i, q)) in points.iter().enumerate() {
+                    let hue = -std::f64::consts::PI + std::f64::consts::TAU * s as f64 / count;
+                    let color = phase_to_color(hue, PhaseColormap::Hue, theme);
+                    let color = if t + 1 == packets { color } else { theme.fade(color, age) };
+                    ctx.draw(&Points { coords: &[(i, q)], color });
+                }
+            }
+        });
+
+    f.render_widget(canvas, area);
+}
@@ -8,4 +8,8 @@ pub mod polar;
 pub mod time_domain_iso;
 pub mod spectrogram;
 pub mod phase;
-pub mod raw_scatter;
\ No newline at end of file
+pub mod raw_scatter;
+pub mod waterfall;
+pub mod overview;
+pub mod ridgeline;
+pub mod constellation;
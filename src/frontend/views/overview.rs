@@ -0,0 +1,62 @@
+// --- File: src/frontend/views/overview.rs ---
+// --- Purpose: At-a-glance grid of scaled-down views in a single pane ---
+//
+// [Graph Description]
+// A 2x2 grid inside one pane:
+//   Dashboard (link stats)   | Amplitude Waterfall
+//   Phase Wireframe          | I/Q Distribution
+//
+// [Plotting Logic]
+// Each cell is drawn by the existing view with the Overview pane's id, so the thumbnails share
+// its ViewState (pause/step, smoothing, reference subtraction). They are drawn unfocused;
+// the outer border carries the focus.
+//
+// [Concepts & Application]
+// Situational awareness when screen space is tight: signal health, amplitude over time,
+// phase stability and the I/Q spread all in one pane.
+// If the pane is too small for four readable thumbnails, only the Dashboard is shown.
+//
+use ratatui::{prelude::*, widgets::*};
+use crate::App;
+use crate::layout_tree::ViewType;
+use crate::frontend::views::{phase, raw_scatter, stats, waterfall};
+
+// Smallest inner area that still gives each thumbnail room for its border, title and a plot
+const MIN_GRID_WIDTH: u16 = 60;
+const MIN_GRID_HEIGHT: u16 = 16;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
+    let block = Block::default()
+        .title(Span::styled(format!(" #{} {} ", id, app.pane_label(id, "Overview")), app.title_style(crate::layout_tree::ViewType::Overview)))
+        .borders(Borders::ALL)
+        .border_style(app.pane_border(is_focused, ViewType::Overview))
+        .style(app.theme.root);
+    let inner = block.inner(area);
+
+    // 1. Too small for the grid: just the numbers
+    if inner.width < MIN_GRID_WIDTH || inner.height < MIN_GRID_HEIGHT {
+        stats::draw(f, app, area, is_focused, id);
+        return;
+    }
+    f.render_widget(block, area);
+
+    // 2. 2x2 grid
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[0]);
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[1]);
+
+    // 3. Thumbnails
+    stats::draw(f, app, top[0], false, id);
+    waterfall::draw(f, app, top[1], false, id);
+    phase::draw(f, app, bottom[0], false, id);
+    raw_scatter::draw(f, app, bottom[1], false, id);
+}
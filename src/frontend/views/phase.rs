@@ -3,8 +3,8 @@
 //
 // [Graph Description]
 // A 2.5D wireframe waterfall plot visualizing the Phase Angle of the CSI data.
-// X-Axis: Subcarrier Index (Frequency)
-// Y-Axis: Phase (Radians, -PI to +PI)
+// X-Axis: Subcarrier number (Frequency), DC centred (-32..31 at 20 MHz)
+// Y-Axis: Phase (Radians), unwrapped along the subcarriers by default ([U] shows the raw -PI..+PI)
 // Z-Axis (Depth): Time (Packet History)
 //
 // [Plotting Logic]
@@ -26,13 +26,18 @@
 use ratatui::{prelude::*, widgets::*};
 use ratatui::widgets::canvas::{Canvas, Line as CanvasLine};
 use crate::App;
+use std::f64::consts::PI;
+
+// Packets drawn behind the newest one (per-pane override: ViewState::history_depth)
+pub const DEFAULT_DEPTH: usize = 15;
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let theme = &app.theme;
     let state = app.pane_states.get(&id).cloned().unwrap_or_else(crate::frontend::view_state::ViewState::new);
 
-    let border_style = if is_focused { theme.focused_border } else { theme.normal_border };
-    let history_len = app.history.len();
+    let border_style = app.pane_border(is_focused, crate::layout_tree::ViewType::Phase);
+    let source = app.source_for(id);
+    let history_len = source.history.len();
 
     // 1. Determine Status & Target Packet
     let mut status_label = " [LIVE] ".to_string();
@@ -40,7 +45,7 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let mut target_index = history_len.saturating_sub(1);
 
     if let Some(anchor) = state.anchor_packet_id {
-        if let Some(idx) = app.history.iter().position(|p| p.id == anchor) {
+        if let Some(idx) = source.history.iter().position(|p| p.id == anchor) {
             target_index = idx;
             status_label = format!(" [REPLAY ID:{}] ", anchor);
             status_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
@@ -53,7 +58,7 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     // Handle empty history
     if history_len == 0 {
         let block = Block::default()
-            .title(format!(" #{} Phase Wireframe ", id))
+            .title(Span::styled(format!(" #{} {} ", id, app.pane_label(id, "Phase Wireframe")), app.title_style(crate::layout_tree::ViewType::Phase)))
             .borders(Borders::ALL)
             .border_style(border_style)
             .style(theme.root);
@@ -61,19 +66,29 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
         return;
     }
 
-    let stats = &app.history[target_index];
-
     // 2. Setup Waterfall Constants
-    const DEPTH_STEPS: usize = 15; // How many packets to show
-    let start_index = target_index.saturating_sub(DEPTH_STEPS);
-    let slice = &app.history[start_index..=target_index];
+    let depth = state.history_depth(DEFAULT_DEPTH, source.history.len());
+
+    // Smoothed I/Q per packet (extra leading packets let the EMA settle before the visible slice)
+    let window = source.history_window(target_index, 2 * depth);
+    let Some(stats) = window.last() else { return };
+    let mut frames = crate::frontend::smoothing::ema_iq(window, state.smoothing_alpha);
+    let ref_tag = crate::frontend::smoothing::apply_reference(app, &state, &mut frames);
+    let sc_labels = crate::frontend::smoothing::center_dc(&mut frames, stats);
+    let frames = &frames[frames.len() - (depth + 1).min(frames.len())..];
 
     // 3. Build Block
     let title_top = Line::from(vec![
-        Span::styled(format!(" #{} Phase Wireframe ", id), theme.text_normal),
+        Span::styled(format!(" #{} {} ", id, app.pane_label(id, "Phase Wireframe")), app.title_style(crate::layout_tree::ViewType::Phase)),
         Span::styled(status_label, status_style),
     ]);
-    let timestamp_text = format!(" Time: {}ms ", stats.timestamp);
+    // Visible subcarrier window (per-pane zoom), clamped to the subcarriers actually present
+    let data_subcarriers = frames.iter().map(|iq| iq.len()).max().unwrap_or(0);
+    let (sc_start, sc_end) = state.subcarrier_window(if data_subcarriers > 0 { data_subcarriers } else { 64 });
+
+    let sc_label = |s: usize| sc_labels.get(s).copied().unwrap_or(s as i32);
+    let unwrap_label = if state.unwrap_phase { "On" } else { "Off" };
+    let timestamp_text = format!(" SC: {}..{} [Z/X/</>] | Depth: {} | Smooth: {} | Unwrap: {} [U] | Time: {} {}", sc_label(sc_start), sc_label(sc_end.saturating_sub(1)), depth, state.smoothing_label(), unwrap_label, app.stats_format.timestamp(stats.timestamp), ref_tag);
     let title_bottom = Line::from(Span::styled(timestamp_text, theme.text_highlight));
 
     let block = Block::default()
@@ -93,43 +108,40 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let offset_y = 0.4;  // Shift up as we go back
     let scale_y = 2.0;   // Stretch phase for visibility
 
-    // Pass 1: Find global max subcarriers in the current slice to ensure rectangular grid
-    let mut max_subcarriers = 64.0;
-    for packet in slice.iter() {
-        if let Some(csi) = &packet.csi {
-            let sc = (csi.csi_raw_data.len() / 2) as f64;
-            if sc > max_subcarriers { max_subcarriers = sc; }
-        }
-    }
+    // Width of the visible window; x is measured from sc_start
+    let max_subcarriers = (sc_end - sc_start) as f64;
+
+    // Phase per frame along the (DC-centred) subcarrier axis
+    let phases: Vec<Vec<f64>> = frames
+        .iter()
+        .map(|iq| if state.unwrap_phase {
+            crate::backend::csi_data::unwrap_phase(iq)
+        } else {
+            iq.iter().map(|&(i_val, q_val)| q_val.atan2(i_val)).collect() // -PI to PI
+        })
+        .collect();
+
+    // Vertical range: -PI..PI, widened to whole multiples of PI when unwrapped values leave it
+    let (phase_min, phase_max) = phases
+        .iter()
+        .flat_map(|row| row.iter().take(sc_end).skip(sc_start))
+        .fold((-PI, PI), |(lo, hi), &p| (lo.min((p / PI).floor() * PI), hi.max((p / PI).ceil() * PI)));
 
-    let mut grid: Vec<Vec<(f64, f64)>> = Vec::with_capacity(slice.len());
+    let mut grid: Vec<Vec<(f64, f64)>> = Vec::with_capacity(frames.len());
 
-    for (i, packet) in slice.iter().enumerate() {
-        // 0 is furthest back (oldest in slice), slice.len() is newest
+    for (i, row_phases) in phases.iter().enumerate() {
+        // 0 is furthest back (oldest in slice), frames.len() is newest
         // We want newest to be at the "front" (no offset), oldest at the "back" (max offset)
-        let reverse_depth = (slice.len() - 1 - i) as f64;
+        let reverse_depth = (frames.len() - 1 - i) as f64;
 
         let mut row = Vec::new();
-        let mut current_sc_count = 0;
-
-        if let Some(csi) = &packet.csi {
-            current_sc_count = csi.csi_raw_data.len() / 2;
-            for s in 0..current_sc_count {
-                let i_val = csi.csi_raw_data.get(s * 2).copied().unwrap_or(0) as f64;
-                let q_val = csi.csi_raw_data.get(s * 2 + 1).copied().unwrap_or(0) as f64;
-                let phase = q_val.atan2(i_val); // -PI to PI
-
-                // Project
-                let sx = (s as f64) + (reverse_depth * offset_x);
-                let sy = (phase * scale_y) + (reverse_depth * offset_y);
-                row.push((sx, sy));
-            }
-        }
 
-        // Pad missing subcarriers with 0.0 phase to maintain wireframe structure
-        for s in current_sc_count..(max_subcarriers as usize) {
-            let phase = 0.0;
-            let sx = (s as f64) + (reverse_depth * offset_x);
+        for s in sc_start..sc_end {
+            // Pad missing subcarriers with 0.0 phase to maintain wireframe structure
+            let phase = row_phases.get(s).copied().unwrap_or(0.0);
+
+            // Project
+            let sx = ((s - sc_start) as f64) + (reverse_depth * offset_x);
             let sy = (phase * scale_y) + (reverse_depth * offset_y);
             row.push((sx, sy));
         }
@@ -139,11 +151,12 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
 
     // 5. Render Canvas
     // Calculate bounds with extra padding for labels
-    let max_x_bound = max_subcarriers + (DEPTH_STEPS as f64 * offset_x) + 10.0; // +10 for right padding
-    let min_y_bound = (-std::f64::consts::PI * scale_y) - 2.0; // -2.0 for bottom axis labels
-    let max_y_bound = (std::f64::consts::PI * scale_y) + (DEPTH_STEPS as f64 * offset_y) + 4.0; // +4.0 for top padding
+    let max_x_bound = max_subcarriers + (depth as f64 * offset_x) + 10.0; // +10 for right padding
+    let min_y_bound = (phase_min * scale_y) - 2.0; // -2.0 for bottom axis labels
+    let max_y_bound = (phase_max * scale_y) + (depth as f64 * offset_y) + 4.0; // +4.0 for top padding
 
     let canvas = Canvas::default()
+        .marker(app.canvas_marker.marker())
         .block(block)
         .background_color(theme.root.bg.unwrap_or(Color::Reset))
         .x_bounds([0.0, max_x_bound])
@@ -160,19 +173,8 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
                 // Calculate normalized position (0.0 = Oldest, 1.0 = Newest)
                 let normalized_age = t as f64 / (grid.len() - 1) as f64;
 
-                // Interpolate color based on age
-                // Newest: Theme Gauge Color (e.g., Cyan/Magenta)
-                // Middle: Yellow/Green
-                // Oldest: Dark Blue/Gray
-                let color = if normalized_age > 0.8 {
-                    theme.gauge_color // Brightest (Front)
-                } else if normalized_age > 0.5 {
-                    Color::Cyan // Mid-High
-                } else if normalized_age > 0.2 {
-                    Color::Blue // Mid-Low
-                } else {
-                    Color::DarkGray // Oldest (Back)
-                };
+                // Oldest: the theme's border color, newest: its accent (gauge_color)
+                let color = theme.age_color(normalized_age);
 
                 for s in 0..row.len() {
                     let (x1, y1) = row[s];
@@ -199,15 +201,17 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
             }
 
             // 2. Draw Axes & Labels (Relative to the "Front" / Newest Packet)
-            // Y-Axis (Phase) at X=0
-            let phase_ticks = vec![
-                (-std::f64::consts::PI, "-π"),
-                (0.0, "0"),
-                (std::f64::consts::PI, "+π"),
-            ];
-
-            for (val, label) in phase_ticks {
-                let y_screen = val * scale_y;
+            // Y-Axis (Phase) at X=0: multiples of PI, at most ~6 of them
+            let (k_min, k_max) = ((phase_min / PI).round() as i32, (phase_max / PI).round() as i32);
+            let k_step = ((k_max - k_min) as usize).div_ceil(6).max(1);
+            for k in (k_min..=k_max).step_by(k_step) {
+                let label = match k {
+                    0 => "0".to_string(),
+                    1 => "+π".to_string(),
+                    -1 => "-π".to_string(),
+                    _ => format!("{:+}π", k),
+                };
+                let y_screen = k as f64 * PI * scale_y;
                 // Tick line
                 ctx.draw(&CanvasLine {
                     x1: 0.0, y1: y_screen,
@@ -218,8 +222,8 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
                 ctx.print(0.0, y_screen, label);
             }
 
-            // X-Axis (Subcarrier) at Phase = -PI (Bottom of the front packet)
-            let bottom_y = -std::f64::consts::PI * scale_y;
+            // X-Axis (Subcarrier) at the lowest phase (Bottom of the front packet)
+            let bottom_y = phase_min * scale_y;
 
             // Draw Axis Line
             ctx.draw(&CanvasLine {
@@ -228,15 +232,16 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
                 color: axis_color,
             });
 
-            // Ticks every 16 subcarriers
-            for s in (0..=max_subcarriers as usize).step_by(16) {
-                let x_screen = s as f64;
+            // Ticks every 16 subcarriers (denser when zoomed in), labelled with the subcarrier number
+            let tick_step = if max_subcarriers <= 16.0 { 2 } else if max_subcarriers <= 32.0 { 4 } else { 16 };
+            for s in (sc_start..sc_end).filter(|&s| sc_label(s) % tick_step == 0) {
+                let x_screen = (s - sc_start) as f64;
                 ctx.draw(&CanvasLine {
                     x1: x_screen, y1: bottom_y,
                     x2: x_screen, y2: bottom_y - 0.5,
                     color: axis_color,
                 });
-                ctx.print(x_screen, bottom_y - 1.5, format!("{}", s));
+                ctx.print(x_screen, bottom_y - 1.5, format!("{}", sc_label(s)));
             }
 
             // Axis Titles
@@ -3,7 +3,7 @@
 //
 // [Graph Description]
 // A 3D "Tunnel" or Conic plot representing the signal amplitude profile over time.
-// Angle (Theta): Subcarrier Index (0 to 64 mapped to 0 to 2PI)
+// Angle (Theta): Subcarrier number, DC centred (-32..31 mapped to 0 to 2PI)
 // Radius (R): Signal Amplitude (Magnitude)
 // Depth (Z): Time (Packet History)
 //
@@ -11,6 +11,8 @@
 // Data is mapped to cylindrical coordinates (r, theta, z) and projected to 2D.
 // The "Tunnel" extends into the screen, with the newest data at the front (Z=0).
 // The shape of the ring represents the frequency selective fading profile of the channel.
+// Rings are colored by age (default) or, with [V], by amplitude on the theme heatmap ramp or by
+// phase (Main Menu -> Phase Colors, the same colors as the Rerun scatter; legend on the right).
 //
 // [Concepts & Application]
 // This visualization helps identify the "shape" of the multipath channel.
@@ -25,13 +27,35 @@
 use ratatui::{prelude::*, widgets::*};
 use ratatui::widgets::canvas::{Canvas, Line as CanvasLine};
 use crate::App;
+use crate::frontend::view_state::Projection;
+use crate::frontend::widgets::colorbar;
+
+// Packets drawn behind the newest one (per-pane override: ViewState::history_depth)
+pub const DEFAULT_DEPTH: usize = 20;
+
+// Distance between rings (packets) along the tunnel axis
+const Z_STEP: f64 = 15.0;
+
+// Screen offset per unit of depth in the Oblique projection
+const OBLIQUE_SHEAR: (f64, f64) = (0.5, 0.3);
+
+// One subcarrier of one packet on the tunnel: 3D position plus what it's colored by
+#[derive(Clone, Copy)]
+struct TunnelPoint {
+    x: f64,
+    y: f64,
+    z: f64,
+    amp: f64,
+    phase: f64,
+}
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let theme = &app.theme;
     let state = app.pane_states.get(&id).cloned().unwrap_or_else(crate::frontend::view_state::ViewState::new);
 
-    let border_style = if is_focused { theme.focused_border } else { theme.normal_border };
-    let history_len = app.history.len();
+    let border_style = app.pane_border(is_focused, crate::layout_tree::ViewType::Polar);
+    let source = app.source_for(id);
+    let history_len = source.history.len();
 
     // 1. Determine Status & Target Packet
     let mut status_label = " [LIVE] ".to_string();
@@ -39,7 +63,7 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let mut target_index = history_len.saturating_sub(1);
 
     if let Some(anchor) = state.anchor_packet_id {
-        if let Some(idx) = app.history.iter().position(|p| p.id == anchor) {
+        if let Some(idx) = source.history.iter().position(|p| p.id == anchor) {
             target_index = idx;
             status_label = format!(" [REPLAY ID:{}] ", anchor);
             status_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
@@ -51,7 +75,7 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
 
     if history_len == 0 {
         let block = Block::default()
-            .title(format!(" #{} Polar Amplitude Tunnel ", id))
+            .title(Span::styled(format!(" #{} {} ", id, app.pane_label(id, "Polar Amplitude Tunnel")), app.title_style(crate::layout_tree::ViewType::Polar)))
             .borders(Borders::ALL)
             .border_style(border_style)
             .style(theme.root);
@@ -59,23 +83,31 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
         return;
     }
 
-    let stats = &app.history[target_index];
-
     // 2. Setup Data Slice (Tunnel Depth)
-    const DEPTH_STEPS: usize = 20;
-    let start_index = target_index.saturating_sub(DEPTH_STEPS);
-    let slice = &app.history[start_index..=target_index];
+    let depth = state.history_depth(DEFAULT_DEPTH, source.history.len());
+
+    // Smoothed I/Q per packet (extra leading packets let the EMA settle before the visible slice)
+    let window = source.history_window(target_index, 2 * depth);
+    let Some(stats) = window.last() else { return };
+    let mut frames = crate::frontend::smoothing::ema_iq(window, state.smoothing_alpha);
+    let ref_tag = crate::frontend::smoothing::apply_reference(app, &state, &mut frames);
+    let flat_tag = crate::frontend::smoothing::apply_flatten(app, id, &mut frames);
+    let sc_labels = crate::frontend::smoothing::center_dc(&mut frames, stats);
+    crate::frontend::smoothing::apply_gain(&mut frames, app.amplitude_gain);
+    let frames = &frames[frames.len() - (depth + 1).min(frames.len())..];
 
     // 3. Build Block
     let title_top = Line::from(vec![
-        Span::styled(format!(" #{} Polar Amplitude Tunnel ", id), theme.text_normal),
+        Span::styled(format!(" #{} {} ", id, app.pane_label(id, "Polar Amplitude Tunnel")), app.title_style(crate::layout_tree::ViewType::Polar)),
         Span::styled(status_label, status_style),
     ]);
 
     // Footer Info
     let az_deg = (state.camera_x.to_degrees() % 360.0 + 360.0) % 360.0;
     let el_deg = state.camera_y.to_degrees();
-    let footer_text = format!(" Rot: {:.0}° | Tilt: {:.0}° | Time: {}ms ", az_deg, el_deg, stats.timestamp);
+    let color_label = if state.color_by_amplitude { "Amp" } else if state.color_by_phase { "Phase" } else { "Age" };
+    let projection = state.projection(Projection::Perspective);
+    let footer_text = format!(" Rot: {:.0}° | Tilt: {:.0}° | Zoom: {:.2}x | Depth: {} | Smooth: {} | Color: {} [V] | Proj: {} [K] | Time: {} {}{}", az_deg, el_deg, state.zoom, depth, state.smoothing_label(), color_label, projection.label(), app.stats_format.timestamp(stats.timestamp), ref_tag, flat_tag);
     let title_bottom = Line::from(Span::styled(footer_text, theme.text_highlight));
 
     let block = Block::default()
@@ -90,14 +122,22 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     // - Angle (Theta) = Subcarrier Index
     // - Radius (R) = Amplitude
     // - Depth (Z) = Time (Packet Index)
+    // Amplitude and phase are kept alongside for amplitude / phase coloring
+
+    let mut points: Vec<Vec<TunnelPoint>> = Vec::with_capacity(frames.len());
+    // Auto-scale to the window maximum (or a percentile of it, see App::amplitude_clamp),
+    // or a fixed full scale when a global gain is set. Louder points are clamped to the outer ring.
+    let max_amp = if app.amplitude_gain != 1.0 {
+        crate::frontend::smoothing::FULL_SCALE_AMPLITUDE
+    } else {
+        let amplitudes = frames.iter().flatten().map(|&(i, q)| (i * i + q * q).sqrt());
+        crate::frontend::smoothing::robust_max(amplitudes, app.amplitude_clamp.percentile()).max(1.0)
+    };
 
-    let mut points: Vec<Vec<(f64, f64, f64)>> = Vec::with_capacity(slice.len());
-    let mut max_amp: f64 = 1.0;
-
-    for (i, packet) in slice.iter().enumerate() {
+    for (i, iq) in frames.iter().enumerate() {
         let mut row = Vec::new();
         // Z-coordinate: 0 is newest (front), negative is older (back)
-        // slice.len()-1 is the newest packet.
+        // frames.len()-1 is the newest packet.
         // i goes from 0 (oldest) to len-1 (newest).
         // Let's make Z positive going into the screen? Or negative?
         // Let's say Z = 0 is center of rotation.
@@ -105,29 +145,22 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
 
         // Let's map i=0 (oldest) to Z = -Depth
         // i=len-1 (newest) to Z = 0
-        let z_step = 15.0; // Distance between rings
-        let z = (i as f64 - (slice.len() as f64 - 1.0)) * z_step;
-
-        if let Some(csi) = &packet.csi {
-            let sc_count = csi.csi_raw_data.len() / 2;
-            for s in 0..sc_count {
-                let i_val = csi.csi_raw_data.get(s * 2).copied().unwrap_or(0) as f64;
-                let q_val = csi.csi_raw_data.get(s * 2 + 1).copied().unwrap_or(0) as f64;
-                let amp = (i_val.powi(2) + q_val.powi(2)).sqrt();
+        let z = (i as f64 - (frames.len() as f64 - 1.0)) * Z_STEP;
 
-                if amp > max_amp { max_amp = amp; }
+        let sc_count = iq.len();
+        for (s, &(i_val, q_val)) in iq.iter().enumerate() {
+            let amp = (i_val.powi(2) + q_val.powi(2)).sqrt().min(max_amp);
 
-                // Map Subcarrier to Angle (0 to 2PI)
-                // We leave a small gap to distinguish start/end
-                let theta = (s as f64 / sc_count as f64) * 2.0 * std::f64::consts::PI;
+            // Map Subcarrier to Angle (0 to 2PI)
+            // We leave a small gap to distinguish start/end
+            let theta = (s as f64 / sc_count as f64) * 2.0 * std::f64::consts::PI;
 
-                // Convert Polar (r, theta) to Cartesian (x, y)
-                // r = amp
-                let x = amp * theta.cos();
-                let y = amp * theta.sin();
+            // Convert Polar (r, theta) to Cartesian (x, y)
+            // r = amp
+            let x = amp * theta.cos();
+            let y = amp * theta.sin();
 
-                row.push((x, y, z));
-            }
+            row.push(TunnelPoint { x, y, z, amp, phase: q_val.atan2(i_val) });
         }
         points.push(row);
     }
@@ -145,7 +178,12 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let sin_tx = tilt_x.sin();
     let cos_tx = tilt_x.cos();
 
-    let scale = 100.0 / max_amp; // Normalize to fit screen roughly
+    let color_by_amplitude = state.color_by_amplitude;
+    let color_by_phase = state.color_by_phase;
+    let show_grid = !state.hide_grid;
+    let phase_colormap = app.phase_colormap;
+    let scale = 100.0 / max_amp * state.zoom; // Normalize to fit screen roughly, then apply wheel zoom
+    let scene_depth = (frames.len() as f64 - 1.0) * Z_STEP;
 
     // Projection Helper
     let project = |x: f64, y: f64, z: f64| -> (f64, f64) {
@@ -161,23 +199,19 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
         let y2 = y1 * cos_tx - z1 * sin_tx;
         let z2 = y1 * sin_tx + z1 * cos_tx;
 
-        // 3. Perspective Projection
-        // Simple weak perspective: x_screen = x / (1 + z/depth)
-        // But for now, let's stick to Orthographic + Z-offset for "Isometric-like" look
-        // or just raw Orthographic (x2, y2).
-        // Since we want a "Tunnel", we need perspective.
-
-        let depth_offset = 500.0; // Push camera back
-        let factor = depth_offset / (depth_offset - z2); // z2 is negative for older packets
-
-        let sx = x2 * factor * scale;
-        let sy = y2 * factor * scale;
-
-        (sx, sy)
+        // 3. Project (Perspective by default: the "Tunnel")
+        // z2 is negative for older packets. Depth is in ring units, so it gets the wheel zoom but not
+        // the amplitude normalization; the oblique shear then works in screen units.
+        projection.project(x2 * scale, y2 * scale, z2 * state.zoom, OBLIQUE_SHEAR, scene_depth * state.zoom)
     };
 
+    // Phase legend on the right while coloring by phase (hidden when the pane is too narrow)
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let (plot_area, legend_area) = if color_by_phase { colorbar::split(inner) } else { (inner, None) };
+
     let canvas = Canvas::default()
-        .block(block)
+        .marker(app.canvas_marker.marker())
         .background_color(theme.root.bg.unwrap_or(Color::Reset))
         .x_bounds([-180.0, 180.0])
         .y_bounds([-140.0, 140.0])
@@ -192,27 +226,27 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
 
                 // Color based on age (t=0 is oldest, t=len-1 is newest)
                 let age = t as f64 / (points.len() as f64 - 1.0);
-                let color = if age > 0.9 {
-                    theme.gauge_color
-                } else if age > 0.7 {
-                    Color::Cyan
-                } else if age > 0.5 {
-                    Color::Blue
-                } else if age > 0.3 {
-                    Color::DarkGray
+                let color = theme.age_color(age);
+
+                // Amplitude coloring: each segment takes the mean amplitude of its two ends;
+                // phase coloring takes the phase of its start
+                let segment_color = |a: f64, b: f64, phase: f64| if color_by_amplitude {
+                    theme.heatmap_color(((a + b) / 2.0 / max_amp).clamp(0.0, 1.0))
+                } else if color_by_phase {
+                    crate::frontend::theme::phase_to_color(phase, phase_colormap, theme)
                 } else {
-                    Color::Black
+                    color
                 };
 
                 for s in 0..row.len() {
-                    let (x, y, z) = row[s];
+                    let TunnelPoint { x, y, z, amp, phase } = row[s];
                     let (sx, sy) = project(x, y, z);
 
                     // 1. Draw Ring (Frequency Domain)
                     if s + 1 < row.len() {
-                        let (nx, ny, nz) = row[s+1];
+                        let TunnelPoint { x: nx, y: ny, z: nz, amp: next_amp, .. } = row[s+1];
                         let (nsx, nsy) = project(nx, ny, nz);
-                        ctx.draw(&CanvasLine { x1: sx, y1: sy, x2: nsx, y2: nsy, color });
+                        ctx.draw(&CanvasLine { x1: sx, y1: sy, x2: nsx, y2: nsy, color: segment_color(amp, next_amp, phase) });
                     }
 
                     // 2. Draw Spine (Time Domain)
@@ -220,14 +254,19 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
                     if t + 1 < points.len() {
                         let next_row = &points[t+1];
                         if s < next_row.len() {
-                            let (nx, ny, nz) = next_row[s];
+                            let TunnelPoint { x: nx, y: ny, z: nz, amp: next_amp, .. } = next_row[s];
                             let (nsx, nsy) = project(nx, ny, nz);
-                            ctx.draw(&CanvasLine { x1: sx, y1: sy, x2: nsx, y2: nsy, color });
+                            ctx.draw(&CanvasLine { x1: sx, y1: sy, x2: nsx, y2: nsy, color: segment_color(amp, next_amp, phase) });
                         }
                     }
                 }
             }
 
+            // Grid and labels hidden ([Shift+A]): the origin cross is the only reference left
+            if !show_grid {
+                return;
+            }
+
             // 3. Draw Reference Rings (Amplitude Orbits)
             // Draw concentric circles at fixed amplitude intervals to serve as a scale
             let ring_count = 4;
@@ -266,8 +305,8 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
             // Draw lines radiating from center to max radius at specific subcarrier intervals
             let max_radius = max_amp * 1.1; // Extend slightly beyond max amplitude
             let subcarrier_step = 8;
-            // Assuming 64 subcarriers for standard WiFi CSI
-            let total_subcarriers = 64;
+            // 64 subcarriers for standard 20 MHz CSI when no data has arrived yet
+            let total_subcarriers = if sc_labels.is_empty() { 64 } else { sc_labels.len() };
 
             for s in (0..total_subcarriers).step_by(subcarrier_step) {
                 let theta = (s as f64 / total_subcarriers as f64) * 2.0 * std::f64::consts::PI;
@@ -282,7 +321,8 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
                 ctx.draw(&CanvasLine { x1: sx_start, y1: sy_start, x2: sx_end, y2: sy_end, color: Color::DarkGray });
 
                 // Label at the end
-                ctx.print(sx_end, sy_end, format!("SC{}", s));
+                let number = sc_labels.get(s).copied().unwrap_or(s as i32);
+                ctx.print(sx_end, sy_end, format!("SC{}", number));
             }
 
             // Draw Labels
@@ -290,5 +330,8 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
             ctx.print(-170.0, -138.0, "Angle: Subcarrier | Radius: Amplitude | Depth: Time");
         });
 
-    f.render_widget(canvas, area);
+    f.render_widget(canvas, plot_area);
+    if let Some(legend_area) = legend_area {
+        colorbar::draw_phase(f, theme, legend_area, phase_colormap);
+    }
 }
\ No newline at end of file
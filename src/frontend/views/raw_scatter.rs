@@ -11,6 +11,11 @@
 // The app accumulates a 2D histogram (24x24 grid) of all (I, Q) pairs received over time.
 // This grid is rendered as a 3D terrain/wireframe.
 // Higher peaks indicate "stable" complex values that occur frequently.
+// With a tail threshold (Shift+Up/Down) bins whose amplitude |I + jQ| is below it are flattened
+// and the height/colors are rescaled to the remaining bins, so rare strong values stand out.
+// The threshold is drawn as a ring on the base box.
+// With persistence (+/-, ViewState::persistence) the grid is rebuilt from the last N packets only,
+// each weighted by recency (the newest counts fully, older ones fade out), instead of everything so far.
 //
 // [Concepts & Application]
 // This view visualizes the stability and distribution of the channel state.
@@ -25,13 +30,25 @@
 use ratatui::{prelude::*, widgets::*};
 use ratatui::widgets::canvas::{Canvas, Line as CanvasLine};
 use crate::App;
+use crate::frontend::widgets::colorbar;
+
+// Histogram grid, as accumulated in App::on_tick
+const GRID_SIZE: usize = 24;
+const GRID_MIN: f64 = -128.0;
+const BIN_WIDTH: f64 = 256.0 / GRID_SIZE as f64;
+
+/// I or Q value at the center of bin `index`
+fn bin_center(index: usize) -> f64 {
+    GRID_MIN + (index as f64 + 0.5) * BIN_WIDTH
+}
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let theme = &app.theme;
     let state = app.pane_states.get(&id).cloned().unwrap_or_else(crate::frontend::view_state::ViewState::new);
 
-    let border_style = if is_focused { theme.focused_border } else { theme.normal_border };
-    let history_len = app.history.len();
+    let border_style = app.pane_border(is_focused, crate::layout_tree::ViewType::RawScatter);
+    let source = app.source_for(id);
+    let history_len = source.history.len();
 
     // 1. Determine Status & Target Packet
     let mut status_label = " [LIVE] ".to_string();
@@ -39,7 +56,7 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
 
     // Determine the end index for our data window
     let end_index = if let Some(anchor) = state.anchor_packet_id {
-        if let Some(idx) = app.history.iter().position(|p| p.id == anchor) {
+        if let Some(idx) = source.history.iter().position(|p| p.id == anchor) {
             status_label = format!(" [REPLAY ID:{}] ", anchor);
             status_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
             idx
@@ -54,7 +71,7 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
 
     if history_len == 0 {
         let block = Block::default()
-            .title(format!(" #{} I/Q Distribution ", id))
+            .title(Span::styled(format!(" #{} {} ", id, app.pane_label(id, "I/Q Distribution")), app.title_style(crate::layout_tree::ViewType::RawScatter)))
             .borders(Borders::ALL)
             .border_style(border_style)
             .style(theme.root);
@@ -62,20 +79,35 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
         return;
     }
 
-    let stats = &app.history[end_index];
+    let window = source.history_window(end_index, state.persistence.saturating_sub(1));
+    let Some(stats) = window.last() else { return };
 
-    // 2. Data Processing: 2D Histogram
-    // Grid: 24x24 bins covering -128 to 128
-    const GRID_SIZE: usize = 24;
+    // 2. Data Processing: 2D Histogram (GRID_SIZE x GRID_SIZE bins covering -128 to 128)
 
     // Use the pre-calculated cumulative grid from the target packet
     // This allows "rewinding" to see the distribution state at that point in time.
-    let grid = stats.distribution_grid;
+    // With persistence, only the recent packets count, older ones with less weight.
+    let grid = if state.persistence == 0 {
+        stats.distribution_grid
+    } else {
+        let mut grid = [[0.0; GRID_SIZE]; GRID_SIZE];
+        for (t, packet) in window.iter().enumerate() {
+            if let Some(csi) = &packet.csi {
+                crate::app::accumulate_grid(&mut grid, csi, (t + 1) as f32 / window.len() as f32);
+            }
+        }
+        grid
+    };
+
+    // Bins inside the threshold ring count as empty; the scale follows the bins that remain
+    let threshold = state.iq_threshold;
+    let show_grid = !state.hide_grid;
+    let visible = move |x: usize, y: usize| threshold <= 0.0 || bin_center(x).hypot(bin_center(y)) >= threshold;
 
     let mut max_count: f32 = 1.0; // Avoid div by zero
-    for row in grid.iter() {
-        for &val in row.iter() {
-            if val > max_count { max_count = val; }
+    for (x, row) in grid.iter().enumerate() {
+        for (y, &val) in row.iter().enumerate() {
+            if visible(x, y) && val > max_count { max_count = val; }
         }
     }
 
@@ -94,13 +126,14 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
 
     // 4. Build Block
     let title_top = Line::from(vec![
-        Span::styled(format!(" #{} I/Q Distribution (Wireframe) ", id), theme.text_normal),
+        Span::styled(format!(" #{} {} ", id, app.pane_label(id, "I/Q Distribution (Wireframe)")), app.title_style(crate::layout_tree::ViewType::RawScatter)),
         Span::styled(status_label, status_style),
     ]);
 
     let az_deg = (azimuth.to_degrees() % 360.0 + 360.0) % 360.0;
     let el_deg = elevation.to_degrees();
-    let footer_text = format!(" Rot: {:.0}° | Tilt: {:.0}° | Max: {:.0} | Time: {}ms ", az_deg, el_deg, max_count, stats.timestamp);
+    let tail_label = if threshold > 0.0 { format!("|H| ≥ {:.0}", threshold) } else { "Off".to_string() };
+    let footer_text = format!(" Rot: {:.0}° | Tilt: {:.0}° | Zoom: {:.2}x | Max: {:.0} | Tail: {} | Persist: {} | Time: {} ", az_deg, el_deg, state.zoom, max_count, tail_label, state.persistence_label("All"), app.stats_format.timestamp(stats.timestamp));
     let title_bottom = Line::from(Span::styled(footer_text, theme.text_highlight));
 
     let block = Block::default()
@@ -113,7 +146,7 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     // 5. Render Canvas (Isometric Wireframe)
 
     // Scale factors
-    let scale = 5.0;
+    let scale = 5.0 * state.zoom; // Wheel zoom
     let z_scale = 80.0 / max_count as f64;
 
     // Helper to project (grid_x, grid_y, count) -> (screen_x, screen_y)
@@ -136,8 +169,13 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
         (sx, sy)
     };
 
+    // Side strip for the colorbar (hidden when the pane is too narrow)
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let (plot_area, bar_area) = colorbar::split(inner);
+
     let canvas = Canvas::default()
-        .block(block)
+        .marker(app.canvas_marker.marker())
         .background_color(theme.root.bg.unwrap_or(Color::Reset))
         .x_bounds([-100.0, 100.0])
         .y_bounds([-80.0, 80.0])
@@ -154,56 +192,68 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
             let c11 = project(max_idx, max_idx, 0.0); // 128, 128
             let c01 = project(min_idx, max_idx, 0.0); // -128, 128
 
-            // Draw Base Box
-            ctx.draw(&CanvasLine { x1: c00.0, y1: c00.1, x2: c10.0, y2: c10.1, color: axis_color });
-            ctx.draw(&CanvasLine { x1: c10.0, y1: c10.1, x2: c11.0, y2: c11.1, color: axis_color });
-            ctx.draw(&CanvasLine { x1: c11.0, y1: c11.1, x2: c01.0, y2: c01.1, color: axis_color });
-            ctx.draw(&CanvasLine { x1: c01.0, y1: c01.1, x2: c00.0, y2: c00.1, color: axis_color });
-
-            // Axis Labels
-            // Real (I) Axis: along y=min_idx (c00 -> c10)
-            ctx.print(c00.0, c00.1 - 5.0, "-128");
-            ctx.print(c10.0, c10.1 - 5.0, "128");
-            let mid_i = project(GRID_SIZE as f64 / 2.0, min_idx, 0.0);
-            ctx.print(mid_i.0, mid_i.1 - 8.0, "Real (I)");
-
-            // Imaginary (Q) Axis: along x=min_idx (c00 -> c01)
-            ctx.print(c01.0, c01.1 - 5.0, "128");
-            let mid_q = project(min_idx, GRID_SIZE as f64 / 2.0, 0.0);
-            ctx.print(mid_q.0 - 15.0, mid_q.1, "Imag (Q)");
-
-            // Draw Grid Lines
+            // Base box and axis labels, or only a cross at I = Q = 0 when hidden ([Shift+A])
+            if show_grid {
+                ctx.draw(&CanvasLine { x1: c00.0, y1: c00.1, x2: c10.0, y2: c10.1, color: axis_color });
+                ctx.draw(&CanvasLine { x1: c10.0, y1: c10.1, x2: c11.0, y2: c11.1, color: axis_color });
+                ctx.draw(&CanvasLine { x1: c11.0, y1: c11.1, x2: c01.0, y2: c01.1, color: axis_color });
+                ctx.draw(&CanvasLine { x1: c01.0, y1: c01.1, x2: c00.0, y2: c00.1, color: axis_color });
+
+                // Axis Labels
+                // Real (I) Axis: along y=min_idx (c00 -> c10)
+                ctx.print(c00.0, c00.1 - 5.0, "-128");
+                ctx.print(c10.0, c10.1 - 5.0, "128");
+                let mid_i = project(GRID_SIZE as f64 / 2.0, min_idx, 0.0);
+                ctx.print(mid_i.0, mid_i.1 - 8.0, "Real (I)");
+
+                // Imaginary (Q) Axis: along x=min_idx (c00 -> c01)
+                ctx.print(c01.0, c01.1 - 5.0, "128");
+                let mid_q = project(min_idx, GRID_SIZE as f64 / 2.0, 0.0);
+                ctx.print(mid_q.0 - 15.0, mid_q.1, "Imag (Q)");
+            } else {
+                let center = (GRID_SIZE - 1) as f64 / 2.0;
+                let origin = project(center, center, 0.0);
+                ctx.print(origin.0, origin.1, "+");
+            }
+
+            // Tail threshold ring on the base (grid coordinates of the bin centers)
+            if threshold > 0.0 {
+                const RING_SEGMENTS: usize = 48;
+                let to_grid = |v: f64| (v - GRID_MIN) / BIN_WIDTH - 0.5;
+                let ring: Vec<(f64, f64)> = (0..=RING_SEGMENTS)
+                    .map(|k| {
+                        let angle = std::f64::consts::TAU * k as f64 / RING_SEGMENTS as f64;
+                        let gx = to_grid(threshold * angle.cos()).clamp(min_idx, max_idx);
+                        let gy = to_grid(threshold * angle.sin()).clamp(min_idx, max_idx);
+                        project(gx, gy, 0.0)
+                    })
+                    .collect();
+                for pair in ring.windows(2) {
+                    ctx.draw(&CanvasLine { x1: pair[0].0, y1: pair[0].1, x2: pair[1].0, y2: pair[1].1, color: axis_color });
+                }
+            }
+
+            // Draw Grid Lines (segments between two hidden bins are left out)
+            let height = |x: usize, y: usize| if visible(x, y) { grid[x][y] as f64 } else { 0.0 };
             for x in 0..GRID_SIZE {
                 for y in 0..GRID_SIZE {
-                    let z = grid[x][y] as f64;
+                    let z = height(x, y);
                     let (sx, sy) = project(x as f64, y as f64, z);
 
                     // Color based on height (Heatmap) - Vibrant Gradient
-                    let intensity = (z / max_count as f64) as f32;
-                    let color = if intensity > 0.8 {
-                        Color::Magenta
-                    } else if intensity > 0.6 {
-                        Color::Red
-                    } else if intensity > 0.4 {
-                        Color::Yellow
-                    } else if intensity > 0.2 {
-                        Color::Green
-                    } else if intensity > 0.05 {
-                        Color::Cyan
-                    } else {
-                        Color::DarkGray
-                    };
+                    let intensity = z / max_count as f64;
+                    let color = theme.heatmap_color(intensity);
 
                     // Draw line to X+1
-                    if x + 1 < GRID_SIZE {
-                        let z_next = grid[x+1][y] as f64;
+                    if x + 1 < GRID_SIZE && (visible(x, y) || visible(x + 1, y)) {
+                        let z_next = height(x + 1, y);
                         let (sx2, sy2) = project((x+1) as f64, y as f64, z_next);
                         ctx.draw(&CanvasLine { x1: sx, y1: sy, x2: sx2, y2: sy2, color });
                     }
 
                     // Draw line to Y+1
-                    if y + 1 < GRID_SIZE {
-                        let z_next = grid[x][y+1] as f64;
+                    if y + 1 < GRID_SIZE && (visible(x, y) || visible(x, y + 1)) {
+                        let z_next = height(x, y + 1);
                         let (sx2, sy2) = project(x as f64, (y+1) as f64, z_next);
                         ctx.draw(&CanvasLine { x1: sx, y1: sy, x2: sx2, y2: sy2, color });
                     }
@@ -211,5 +261,9 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
             }
         });
 
-    f.render_widget(canvas, area);
+    f.render_widget(canvas, plot_area);
+
+    if let Some(bar_area) = bar_area {
+        colorbar::draw(f, theme, bar_area, 0.0, max_count as f64, "count");
+    }
 }
\ No newline at end of file
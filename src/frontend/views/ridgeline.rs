@@ -0,0 +1,169 @@
+// --- File: src/frontend/views/ridgeline.rs ---
+// --- Purpose: Stacked amplitude curves of successive packets (Ridgeline / Joyplot) ---
+//
+// [Graph Description]
+// One amplitude-vs-subcarrier curve per packet, stacked on top of each other.
+// X-Axis: Subcarrier number (Frequency), DC centred (-32..31 at 20 MHz)
+// Y-Axis: Time (oldest packet at the top, newest at the bottom), each curve's own height is amplitude
+//
+// [Plotting Logic]
+// Every packet gets a baseline; baselines are one "offset" apart. The offset is derived from the
+// pane height (the canvas always fits all lines), and each curve may rise to RIDGE_HEIGHT offsets
+// so neighbouring ridges overlap a little, which is what makes the shape readable as a surface.
+// Curves are drawn from back (oldest) to front (newest) and colored by age (Theme::age_color) like the Phase wireframe.
+//
+// [Concepts & Application]
+// Shows the same information as the Waterfall, but as shapes instead of colors: a notch
+// (frequency-selective fading) that drifts across the band shows up as a diagonal valley,
+// and a sudden change in the environment as a ridge that looks different from the ones above it.
+//
+// [Demo]
+// Walk slowly through the room and watch the dips move from ridge to ridge.
+//
+use ratatui::{prelude::*, widgets::*};
+use ratatui::widgets::canvas::{Canvas, Line as CanvasLine};
+use crate::App;
+
+// Packets stacked above the newest one (per-pane override: ViewState::history_depth)
+pub const DEFAULT_DEPTH: usize = 20;
+
+// Tallest curve, in baseline offsets (> 1.0 lets ridges overlap the ones behind them)
+const RIDGE_HEIGHT: f64 = 2.5;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
+    let theme = &app.theme;
+    let state = app.pane_states.get(&id).cloned().unwrap_or_else(crate::frontend::view_state::ViewState::new);
+
+    let border_style = app.pane_border(is_focused, crate::layout_tree::ViewType::Ridgeline);
+    let source = app.source_for(id);
+    let history_len = source.history.len();
+
+    // 1. Determine Status & Target Packet
+    let mut status_label = " [LIVE] ".to_string();
+    let mut status_style = Style::default().fg(Color::Green).add_modifier(Modifier::BOLD);
+    let mut target_index = history_len.saturating_sub(1);
+
+    if let Some(anchor) = state.anchor_packet_id {
+        if let Some(idx) = source.history.iter().position(|p| p.id == anchor) {
+            target_index = idx;
+            status_label = format!(" [REPLAY ID:{}] ", anchor);
+            status_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        } else {
+            status_label = " [EXPIRED] ".to_string();
+            status_style = Style::default().fg(Color::Red);
+        }
+    }
+
+    let title_top = Line::from(vec![
+        Span::styled(format!(" #{} {} ", id, app.pane_label(id, "Amplitude Ridgeline")), app.title_style(crate::layout_tree::ViewType::Ridgeline)),
+        Span::styled(status_label, status_style),
+    ]);
+
+    // Handle empty history
+    if history_len == 0 {
+        let block = Block::default()
+            .title(title_top)
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .style(theme.root);
+        f.render_widget(block, area);
+        return;
+    }
+
+    // 2. Collect Frames
+    let depth = state.history_depth(DEFAULT_DEPTH, history_len);
+
+    // Smoothed I/Q per packet (extra leading packets let the EMA settle before the visible slice)
+    let window = source.history_window(target_index, 2 * depth);
+    let Some(stats) = window.last() else { return };
+    let mut frames = crate::frontend::smoothing::ema_iq(window, state.smoothing_alpha);
+    let ref_tag = crate::frontend::smoothing::apply_reference(app, &state, &mut frames);
+    let flat_tag = crate::frontend::smoothing::apply_flatten(app, id, &mut frames);
+    let sc_labels = crate::frontend::smoothing::center_dc(&mut frames, stats);
+    crate::frontend::smoothing::apply_gain(&mut frames, app.amplitude_gain);
+    let frames = &frames[frames.len() - (depth + 1).min(frames.len())..];
+
+    let data_subcarriers = frames.iter().map(|iq| iq.len()).max().unwrap_or(0);
+    let (sc_start, sc_end) = state.subcarrier_window(if data_subcarriers > 0 { data_subcarriers } else { 64 });
+    let sc_label = |s: usize| sc_labels.get(s).copied().unwrap_or(s as i32);
+
+    let amplitudes: Vec<Vec<f64>> = frames
+        .iter()
+        .map(|iq| iq.iter().map(|&(i, q)| (i * i + q * q).sqrt()).collect())
+        .collect();
+
+    // Shared scale so ridges can be compared with each other (fixed once a gain is set;
+    // outliers above an App::amplitude_clamp percentile are cut off)
+    let max_amp = if app.amplitude_gain != 1.0 {
+        crate::frontend::smoothing::FULL_SCALE_AMPLITUDE
+    } else {
+        let visible = amplitudes.iter().flat_map(|row| row.iter().take(sc_end).skip(sc_start));
+        crate::frontend::smoothing::robust_max(visible.copied(), app.amplitude_clamp.percentile()).max(1.0)
+    };
+
+    // 3. Build Block
+    let timestamp_text = format!(" SC: {}..{} [Z/X/</>] | Lines: {} | Smooth: {} | Time: {} {}{}", sc_label(sc_start), sc_label(sc_end.saturating_sub(1)), frames.len(), state.smoothing_label(), app.stats_format.timestamp(stats.timestamp), ref_tag, flat_tag);
+    let block = Block::default()
+        .title(title_top)
+        .title_bottom(Line::from(Span::styled(timestamp_text, theme.text_highlight)).alignment(Alignment::Right))
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .style(theme.root);
+
+    // 4. Stack Layout
+    // Baselines are 1.0 apart in canvas units: the newest at 0, the oldest at lines - 1.
+    // The canvas maps the whole stack onto the pane, so the offset follows the pane height.
+    let lines = frames.len();
+    let width = (sc_end - sc_start) as f64;
+    let top = (lines - 1) as f64 + RIDGE_HEIGHT;
+    let label_space = 2.0 * top / area.height.saturating_sub(2).max(1) as f64; // ~2 rows for the axis
+
+    let ridges: Vec<Vec<(f64, f64)>> = amplitudes
+        .iter()
+        .enumerate()
+        .map(|(t, row)| {
+            let baseline = (lines - 1 - t) as f64;
+            (sc_start..sc_end)
+                .map(|s| {
+                    let amp = row.get(s).copied().unwrap_or(0.0).min(max_amp);
+                    ((s - sc_start) as f64, baseline + amp / max_amp * RIDGE_HEIGHT)
+                })
+                .collect()
+        })
+        .collect();
+
+    // 5. Render Canvas
+    let canvas = Canvas::default()
+        .marker(app.canvas_marker.marker())
+        .block(block)
+        .background_color(theme.root.bg.unwrap_or(Color::Reset))
+        .x_bounds([-4.0, width + 2.0])
+        .y_bounds([-label_space, top])
+        .paint(move |ctx| {
+            let axis_color = theme.text_normal.fg.unwrap_or(Color::White);
+
+            // Back (oldest, top) to front (newest, bottom)
+            for (t, ridge) in ridges.iter().enumerate() {
+                let normalized_age = if lines > 1 { t as f64 / (lines - 1) as f64 } else { 1.0 };
+                let color = theme.age_color(normalized_age);
+
+                for pair in ridge.windows(2) {
+                    let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+                    ctx.draw(&CanvasLine { x1, y1, x2, y2, color });
+                }
+            }
+
+            // X-Axis under the newest ridge, ticks every 16 subcarriers (denser when zoomed in)
+            ctx.draw(&CanvasLine { x1: 0.0, y1: 0.0, x2: width, y2: 0.0, color: axis_color });
+            let tick_step = if width <= 16.0 { 2 } else if width <= 32.0 { 4 } else { 16 };
+            for s in (sc_start..sc_end).filter(|&s| sc_label(s) % tick_step == 0) {
+                ctx.print((s - sc_start) as f64, -label_space, format!("{}", sc_label(s)));
+            }
+
+            // Time direction on the left edge
+            ctx.print(-4.0, top, "old");
+            ctx.print(-4.0, 0.0, "new");
+        });
+
+    f.render_widget(canvas, area);
+}
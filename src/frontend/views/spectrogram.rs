@@ -3,7 +3,7 @@
 //
 // [Graph Description]
 // A 2D Heatmap (Spectrogram) showing the rate of change of the signal phase.
-// X-Axis: Subcarrier Index
+// X-Axis: Subcarrier number, DC centred
 // Y-Axis: Time (History)
 // Color: Magnitude of Phase Difference (Delta Phi) between consecutive packets.
 //
@@ -11,12 +11,12 @@
 // Calculates the phase difference between packet[t] and packet[t-1] for each subcarrier.
 // |Phase[t] - Phase[t-1]| is plotted as color intensity.
 // Hot colors (Red/Magenta) indicate rapid phase change.
-// Cool colors (Blue/Black) indicate static phase.
+// Cool colors (Cyan/Gray) indicate static phase.
 //
 // [Concepts & Application]
 // The rate of phase change is directly proportional to the relative velocity of objects
 // in the environment (Doppler Shift).
-// This view effectively highlights *motion*. Static objects disappear (black/blue),
+// This view effectively highlights *motion*. Static objects disappear (gray/cyan),
 // while moving objects create bright streaks.
 //
 // [Demo]
@@ -24,16 +24,33 @@
 // Wave your hand quickly: You will see bright "hot" streaks appearing, corresponding
 // to the Doppler shift induced by your hand's motion.
 //
+// [Smooth Heatmap]
+// `f` (fullscreen) resamples the matrix to the canvas dots instead of drawing one block per
+// cell: bilinear where the pane has more dots than cells, averaged where it has fewer
+// (widgets::heatmap), so the heatmap has no gaps or steps at any pane size.
+//
+// [Peak Readout]
+// The footer also shows the strongest bin of the live amplitude FFT (backend::doppler, DC
+// excluded) in Hz and as the radial speed it corresponds to at the packet's channel.
+// Bins are converted with the effective sample rate (App::effective_sample_rate_hz, "fs"),
+// which drops below the tick rate when fewer packets arrive than there are ticks.
+// It always follows the live stream, also while the pane replays history.
+//
 use ratatui::{prelude::*, widgets::*};
-use ratatui::widgets::canvas::{Canvas, Rectangle};
+use ratatui::widgets::canvas::{Canvas, Points, Rectangle};
 use crate::App;
+use crate::frontend::widgets::{colorbar, heatmap};
+
+// Packets in the Doppler window (per-pane override: ViewState::history_depth)
+pub const DEFAULT_DEPTH: usize = 60;
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let theme = &app.theme;
     let state = app.pane_states.get(&id).cloned().unwrap_or_else(crate::frontend::view_state::ViewState::new);
 
-    let border_style = if is_focused { theme.focused_border } else { theme.normal_border };
-    let history_len = app.history.len();
+    let border_style = app.pane_border(is_focused, crate::layout_tree::ViewType::Spectrogram);
+    let source = app.source_for(id);
+    let history_len = source.history.len();
 
     // 1. Determine Status & Target Packet
     let mut status_label = " [LIVE] ".to_string();
@@ -41,7 +58,7 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let mut target_index = history_len.saturating_sub(1);
 
     if let Some(anchor) = state.anchor_packet_id {
-        if let Some(idx) = app.history.iter().position(|p| p.id == anchor) {
+        if let Some(idx) = source.history.iter().position(|p| p.id == anchor) {
             target_index = idx;
             status_label = format!(" [REPLAY ID:{}] ", anchor);
             status_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
@@ -53,7 +70,7 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
 
     if history_len < 2 {
         let block = Block::default()
-            .title(format!(" #{} Doppler Spectrogram ", id))
+            .title(Span::styled(format!(" #{} {} ", id, app.pane_label(id, "Doppler Spectrogram")), app.title_style(crate::layout_tree::ViewType::Spectrogram)))
             .borders(Borders::ALL)
             .border_style(border_style)
             .style(theme.root);
@@ -61,22 +78,31 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
         return;
     }
 
-    let stats = &app.history[target_index];
-
     // 2. Setup Data Slice
     // We need pairs of packets to calculate phase difference (Doppler).
     // Show last N packets.
-    const WINDOW_SIZE: usize = 60;
-    let start_index = target_index.saturating_sub(WINDOW_SIZE);
-    let slice = &app.history[start_index..=target_index];
+    let depth = state.history_depth(DEFAULT_DEPTH, source.history.len());
+    let slice = source.history_window(target_index, depth);
+    let Some(stats) = slice.last() else { return };
 
     // 3. Build Block
     let title_top = Line::from(vec![
-        Span::styled(format!(" #{} Doppler Spectrogram (Phase Variance) ", id), theme.text_normal),
+        Span::styled(format!(" #{} {} ", id, app.pane_label(id, "Doppler Spectrogram (Phase Variance)")), app.title_style(crate::layout_tree::ViewType::Spectrogram)),
         Span::styled(status_label, status_style),
     ]);
 
-    let footer_text = format!(" Time: {}ms | Window: {} pkts ", stats.timestamp, slice.len());
+    // Real time spanned by the window (the axis is packets, the label is seconds)
+    let span_secs = slice.first().map(|first| app.seconds_between(first, stats)).unwrap_or(0.0);
+    let span_label = crate::app::format_seconds_ago(span_secs);
+
+    let unwrap_label = if state.unwrap_phase { "On" } else { "Off" };
+    let channel = stats.csi.as_ref().map_or(0, |csi| csi.channel);
+    let peak_label = match app.doppler_peak() {
+        Some((bin, hz)) => format!("{:.2} Hz (bin {}) ≈ {:.2} m/s", hz, bin, crate::backend::doppler::velocity_mps(hz, channel)),
+        None => "—".to_string(),
+    };
+    let smooth_label = if state.smooth_heatmap { "On" } else { "Off" };
+    let footer_text = format!(" Time: {} | Window: {} pkts ({}) | Unwrap: {} [U] | Smooth: {} [F] | Live Peak: {} | fs: {:.1} Hz ", app.stats_format.timestamp(stats.timestamp), slice.len(), span_label.trim_start_matches('-'), unwrap_label, smooth_label, peak_label, app.effective_sample_rate_hz());
     let title_bottom = Line::from(Span::styled(footer_text, theme.text_highlight));
 
     let block = Block::default()
@@ -101,29 +127,29 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
             let sc_count = csi_curr.csi_raw_data.len() / 2;
             if sc_count > max_subcarriers { max_subcarriers = sc_count; }
 
-            for s in 0..sc_count {
-                // Current Phase
-                let i_c = csi_curr.csi_raw_data.get(s * 2).copied().unwrap_or(0) as f64;
-                let q_c = csi_curr.csi_raw_data.get(s * 2 + 1).copied().unwrap_or(0) as f64;
-                let phase_c = q_c.atan2(i_c);
-
-                // Previous Phase
-                let i_p = csi_prev.csi_raw_data.get(s * 2).copied().unwrap_or(0) as f64;
-                let q_p = csi_prev.csi_raw_data.get(s * 2 + 1).copied().unwrap_or(0) as f64;
-                let phase_p = q_p.atan2(i_p);
+            // Unwrapped phases give the DC/guard nulls their neighbours' motion instead of a flat 0
+            let (phases_curr, phases_prev) = if state.unwrap_phase {
+                (csi_curr.unwrapped_phase(), csi_prev.unwrapped_phase())
+            } else {
+                (wrapped_phase(csi_curr), wrapped_phase(csi_prev))
+            };
 
-                // Phase Difference (Doppler Proxy)
-                let mut diff = phase_c - phase_p;
+            for s in 0..sc_count {
+                let phase_c = phases_curr.get(s).copied().unwrap_or(0.0) as f64;
+                let phase_p = phases_prev.get(s).copied().unwrap_or(0.0) as f64;
 
-                // Unwrap phase
-                if diff > std::f64::consts::PI { diff -= 2.0 * std::f64::consts::PI; }
-                if diff < -std::f64::consts::PI { diff += 2.0 * std::f64::consts::PI; }
+                // Phase Difference (Doppler Proxy), wrapped to -PI..PI
+                // (unwrapped phases can differ by several turns between packets)
+                let diff = (phase_c - phase_p + std::f64::consts::PI).rem_euclid(std::f64::consts::TAU) - std::f64::consts::PI;
 
                 row.push(diff.abs());
             }
+            row = crate::backend::csi_data::to_frequency_order(&row, csi_curr.cwb);
         }
         matrix.push(row);
     }
+    let cwb = stats.csi.as_ref().map_or(0, |csi| csi.cwb);
+    let sc_labels = crate::backend::csi_data::frequency_axis(max_subcarriers, cwb);
 
     // 5. Render Canvas (Heatmap)
     // Use the actual matrix height for bounds to ensure it fills the area or scales correctly
@@ -133,42 +159,65 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let x_padding = 8.0;
     let y_padding = 4.0;
 
+    // Side strip for the colorbar (hidden when the pane is too narrow)
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let (plot_area, bar_area) = colorbar::split(inner);
+    let show_text_legend = bar_area.is_none();
+
+    // Smooth mode: one value per canvas dot of the data region (the padding holds the labels)
+    let smoothed = state.smooth_heatmap.then(|| {
+        let (dots_x, dots_y) = heatmap::dots_per_cell(app.canvas_marker);
+        let x_range = max_subcarriers as f64 + 2.0 * x_padding;
+        let y_range = height + 2.0 * y_padding;
+        let out_cols = (plot_area.width as f64 * dots_x as f64 * max_subcarriers as f64 / x_range).round().max(1.0) as usize;
+        let out_rows = (plot_area.height as f64 * dots_y as f64 * height / y_range).round().max(1.0) as usize;
+        let padded: Vec<Vec<f64>> = matrix.iter().map(|row| {
+            let mut row = row.clone();
+            row.resize(max_subcarriers, 0.0);
+            row
+        }).collect();
+        heatmap::resample(&padded, out_rows, out_cols)
+    });
+
     let canvas = Canvas::default()
-        .block(block)
+        .marker(app.canvas_marker.marker())
         .background_color(theme.root.bg.unwrap_or(Color::Reset))
         .x_bounds([-x_padding, max_subcarriers as f64 + x_padding])
         .y_bounds([-y_padding, height + y_padding])
         .paint(move |ctx| {
-            // Draw Heatmap
-            for (t, row) in matrix.iter().enumerate() {
-                for (s, &val) in row.iter().enumerate() {
-                    // Normalize value for color
-                    // Max theoretical phase diff is PI.
-                    // Saturate at PI/2 for better visibility of subtle motions
-                    let intensity = (val / (std::f64::consts::PI / 2.0)).clamp(0.0, 1.0);
-
-                    let color = if intensity > 0.8 {
-                        Color::Red
-                    } else if intensity > 0.6 {
-                        Color::Magenta
-                    } else if intensity > 0.4 {
-                        Color::Yellow
-                    } else if intensity > 0.2 {
-                        Color::Green
-                    } else if intensity > 0.05 {
-                        Color::Blue
-                    } else {
-                        Color::DarkGray
-                    };
-
-                    if intensity > 0.05 {
-                        ctx.draw(&Rectangle {
-                            x: s as f64,
-                            y: t as f64,
-                            width: 1.0,
-                            height: 1.0,
-                            color,
-                        });
+            // Draw Heatmap (smooth: one point per canvas dot, same color scale as the cells)
+            if let Some(ref grid) = smoothed {
+                let cell_w = max_subcarriers as f64 / grid.first().map_or(1, |row| row.len()).max(1) as f64;
+                let cell_h = height / grid.len().max(1) as f64;
+                for (r, row) in grid.iter().enumerate() {
+                    for (c, &val) in row.iter().enumerate() {
+                        let intensity = (val / (std::f64::consts::PI / 2.0)).clamp(0.0, 1.0);
+                        if intensity > 0.05 {
+                            let point = ((c as f64 + 0.5) * cell_w, (r as f64 + 0.5) * cell_h);
+                            ctx.draw(&Points { coords: &[point], color: theme.heatmap_color(intensity) });
+                        }
+                    }
+                }
+            } else {
+                for (t, row) in matrix.iter().enumerate() {
+                    for (s, &val) in row.iter().enumerate() {
+                        // Normalize value for color
+                        // Max theoretical phase diff is PI.
+                        // Saturate at PI/2 for better visibility of subtle motions
+                        let intensity = (val / (std::f64::consts::PI / 2.0)).clamp(0.0, 1.0);
+
+                        let color = theme.heatmap_color(intensity);
+
+                        if intensity > 0.05 {
+                            ctx.draw(&Rectangle {
+                                x: s as f64,
+                                y: t as f64,
+                                width: 1.0,
+                                height: 1.0,
+                                color,
+                            });
+                        }
                     }
                 }
             }
@@ -176,25 +225,25 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
             // Draw Axes Labels & Ticks
             let axis_color = Color::White;
 
-            // X-Axis Ticks (Subcarriers)
-            for s in (0..=max_subcarriers).step_by(16) {
+            // X-Axis Ticks (Subcarrier numbers, every 16)
+            for (s, &number) in sc_labels.iter().enumerate().filter(|(_, n)| *n % 16 == 0) {
                 let x = s as f64;
-                ctx.print(x, -2.0, format!("{}", s));
+                ctx.print(x, -2.0, format!("{}", number));
                 ctx.draw(&ratatui::widgets::canvas::Line {
                     x1: x, y1: -0.5,
                     x2: x, y2: 0.5,
                     color: axis_color,
                 });
             }
-            ctx.print(max_subcarriers as f64 / 2.0 - 5.0, -3.5, "Subcarrier Index");
+            ctx.print(max_subcarriers as f64 / 2.0 - 5.0, -3.5, "Subcarrier");
 
             // Y-Axis Ticks (Time)
             // Top is Newest (0ms ago), Bottom is Oldest
-            ctx.print(-x_padding + 1.0, height, "0ms");
-            ctx.print(-x_padding + 1.0, 0.0, format!("-{}pkts", height));
+            ctx.print(-x_padding + 1.0, height, "now");
+            ctx.print(-x_padding + 1.0, 0.0, span_label.clone());
 
-            // DC Null Marker (Approximate center)
-            let dc_idx = max_subcarriers as f64 / 2.0;
+            // DC Null Marker (first DC when the buffer holds several LTF blocks)
+            let dc_idx = sc_labels.iter().position(|&n| n == 0).unwrap_or(max_subcarriers / 2) as f64;
             ctx.print(dc_idx - 1.0, height + 1.0, "DC");
             ctx.draw(&ratatui::widgets::canvas::Line {
                 x1: dc_idx, y1: 0.0,
@@ -202,7 +251,23 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
                 color: Color::DarkGray,
             });
 
-            // Legend
-            ctx.print(max_subcarriers as f64 - 20.0, height + 2.0, "Color: Phase Delta (rad)");
-        });    f.render_widget(canvas, area);
-}
\ No newline at end of file
+            // Legend (only when there is no room for the colorbar)
+            if show_text_legend {
+                ctx.print(max_subcarriers as f64 - 20.0, height + 2.0, "Color: Phase Delta (rad)");
+            }
+        });
+    f.render_widget(canvas, plot_area);
+
+    // Intensity saturates at PI/2 (see normalization above)
+    if let Some(bar_area) = bar_area {
+        colorbar::draw(f, theme, bar_area, 0.0, std::f64::consts::FRAC_PI_2, "rad");
+    }
+}
+
+/// Per-subcarrier atan2 phase (buffer order), -PI to PI
+fn wrapped_phase(csi: &crate::backend::csi_data::CsiData) -> Vec<f32> {
+    csi.csi_raw_data
+        .chunks_exact(2)
+        .map(|pair| (pair[1] as f32).atan2(pair[0] as f32))
+        .collect()
+}
@@ -3,7 +3,8 @@
 //
 // [Graph Description]
 // A textual and gauge-based dashboard for monitoring link health.
-// Displays: PPS (Packets Per Second), SNR (Signal-to-Noise Ratio), RSSI (Signal Strength).
+// Displays: PPS (Packets Per Second), SNR (Signal-to-Noise Ratio), RSSI (Signal Strength),
+// Channel Stability (phase coherence between consecutive snapshots) and Motion Energy.
 //
 // [Plotting Logic]
 // Uses standard UI widgets (Gauges, Paragraphs) to display scalar telemetry data.
@@ -13,6 +14,12 @@
 // - RSSI: How loud the signal is.
 // - SNR: How clear the signal is (Signal vs Background Noise).
 // - PPS: Data throughput/stability.
+// - Stability: How similar the subcarrier pattern is to the previous one (steady channel = ~100%).
+// - Motion: How much the subcarrier amplitudes fluctuated over the last ~2 s (presence detection).
+// - Channel line: Wi-Fi channel, bandwidth and PHY mode; the subcarrier count turns yellow if it
+//   doesn't fit the mode (firmware configured for other LTFs, so the plots would be mislabeled).
+// - Metadata (Shift+D in fullscreen): every field the firmware reported for the shown packet
+//   (rate, MCS, SGI, STBC, antenna, ...) as a table instead of the gauges.
 // Essential for verifying that the hardware is functioning and the link is established
 // before analyzing complex CSI data.
 //
@@ -22,32 +29,41 @@
 //
 use ratatui::{prelude::*, widgets::*};
 use crate::App;
+use crate::backend::csi_data::CsiData;
+
+// Placeholder for fields of a snapshot without CSI
+const NO_VALUE: &str = "—";
 
 pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
-    let border_style = if is_focused { app.theme.focused_border } else { app.theme.normal_border };
+    let border_style = app.pane_border(is_focused, crate::layout_tree::ViewType::Dashboard);
 
     // 1. Determine Data Source (Live vs History)
-    let mut stats = &app.current_stats;
+    let source = app.source_for(id);
+    let mut stats = source.current_stats;
     let mut status_label = " [LIVE] ".to_string();
     let mut status_style = Style::default().fg(Color::Green).add_modifier(Modifier::BOLD);
 
-    if let Some(state) = app.pane_states.get(&id) {
-        if let Some(anchor_id) = state.anchor_packet_id {
-            // REFACTOR: Changed packet_count to id in finding logic
-            if let Some(found_packet) = app.history.iter().find(|p| p.id == anchor_id) {
-                stats = found_packet;
-                status_label = format!(" [REPLAY ID:{}] ", anchor_id);
-                status_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
-            } else {
-                status_label = " [EXPIRED] ".to_string();
-                status_style = Style::default().fg(Color::Red);
-            }
+    // The numbers below are the last ones received, not live ones
+    if app.signal_lost() && source.overlay_label.is_none() {
+        status_label = " [SIGNAL LOST] ".to_string();
+        status_style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    }
+
+    if let Some(state) = app.pane_states.get(&id) && let Some(anchor_id) = state.anchor_packet_id {
+        // REFACTOR: Changed packet_count to id in finding logic
+        if let Some(found_packet) = source.history.iter().find(|p| p.id == anchor_id) {
+            stats = found_packet;
+            status_label = format!(" [REPLAY ID:{}] ", anchor_id);
+            status_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        } else {
+            status_label = " [EXPIRED] ".to_string();
+            status_style = Style::default().fg(Color::Red);
         }
     }
 
     // 2. Build Title with Status
     let title = Line::from(vec![
-        Span::styled(format!(" [Pane {}] Network Stats", id), app.theme.text_normal),
+        Span::styled(format!(" [Pane {}] {}", id, app.pane_label(id, "Network Stats")), app.title_style(crate::layout_tree::ViewType::Dashboard)),
         Span::styled(status_label, status_style),
     ]);
 
@@ -60,6 +76,11 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let inner_area = block.inner(area);
     f.render_widget(block, area);
 
+    if app.pane_states.get(&id).is_some_and(|s| s.show_metadata) {
+        draw_metadata(f, app, stats.csi.as_ref(), inner_area);
+        return;
+    }
+
     // Layout
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -71,29 +92,35 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
             Constraint::Length(1),
             Constraint::Length(3), // RSSI Gauge
             Constraint::Length(1),
+            Constraint::Length(3), // Stability Gauge
+            Constraint::Length(1),
+            Constraint::Length(3), // Motion Gauge
+            Constraint::Length(1),
+            Constraint::Length(1), // Channel / Bandwidth
             Constraint::Length(1), // Footer
             Constraint::Min(0),
         ])
         .split(inner_area);
 
-    // Meters
-    let pps_percent = (stats.pps as f64 / 1000.0 * 100.0).clamp(0.0, 100.0) as u16;
+    // Meters (scales from Main Menu -> Gauge Ranges)
+    let ranges = &app.gauge_ranges;
     let pps_gauge = Gauge::default()
-        .block(Block::default().title(" Packets Per Second ").borders(Borders::BOTTOM))
+        .block(Block::default().title(format!(" Packets Per Second (max {:.0}) ", ranges.pps_max)).borders(Borders::BOTTOM))
         .gauge_style(Style::default().fg(Color::Cyan))
-        .percent(pps_percent)
+        .percent(ranges.pps_percent(stats.pps))
         .label(format!("{} PPS", stats.pps));
     f.render_widget(pps_gauge, chunks[1]);
 
-    let snr_percent = (stats.snr as f64 / 60.0 * 100.0).clamp(0.0, 100.0) as u16;
+    // Below the floor the link is too noisy to trust the CSI views
+    let snr_color = if stats.snr < ranges.snr_floor { Color::Red } else { Color::Green };
     let snr_gauge = Gauge::default()
-        .block(Block::default().title(" Signal-to-Noise Ratio (SNR) ").borders(Borders::BOTTOM))
-        .gauge_style(Style::default().fg(Color::Green))
-        .percent(snr_percent)
+        .block(Block::default().title(format!(" Signal-to-Noise Ratio (SNR, floor {} dB) ", ranges.snr_floor)).borders(Borders::BOTTOM))
+        .gauge_style(Style::default().fg(snr_color))
+        .percent(ranges.snr_percent(stats.snr))
         .label(format!("{} dB", stats.snr));
     f.render_widget(snr_gauge, chunks[3]);
 
-    let rssi_percent = ((stats.rssi + 100).max(0) as u16).min(100);
+    let rssi_percent = ranges.rssi_percent(stats.rssi);
     let rssi_gauge = Gauge::default()
         .block(Block::default().title(" RSSI (Signal Strength) ").borders(Borders::BOTTOM))
         .gauge_style(Style::default().fg(app.theme.gauge_color))
@@ -101,13 +128,118 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
         .label(format!("{} dBm", stats.rssi));
     f.render_widget(rssi_gauge, chunks[5]);
 
+    let stability_percent = (stats.coherence as f64 * 100.0).clamp(0.0, 100.0) as u16;
+    let stability_gauge = Gauge::default()
+        .block(Block::default().title(" Channel Stability (Phase Coherence) ").borders(Borders::BOTTOM))
+        .gauge_style(Style::default().fg(Color::Yellow))
+        .percent(stability_percent)
+        .label(format!("{}%", stability_percent));
+    f.render_widget(stability_gauge, chunks[7]);
+
+    // Motion energy is a live window metric, so replayed packets don't change it
+    let motion_color = if app.motion_detected() { Color::Magenta } else { Color::Blue };
+    let motion_gauge = Gauge::default()
+        .block(Block::default().title(format!(" Motion Energy (alarm {:.1}) ", ranges.motion_threshold)).borders(Borders::BOTTOM))
+        .gauge_style(Style::default().fg(motion_color))
+        .percent(ranges.motion_percent(app.motion_energy))
+        .label(app.stats_format.number(app.motion_energy as f64));
+    f.render_widget(motion_gauge, chunks[9]);
+
+    // Channel line (highlighted when the subcarrier count doesn't fit the mode)
+    let channel_text = match stats.csi.as_ref() {
+        Some(csi) => {
+            let sc_count = csi.csi_raw_data.len() / 2;
+            let secondary = csi.secondary_channel_label().map(|s| format!(" (+{})", s)).unwrap_or_default();
+            let mut spans = vec![
+                Span::raw("Ch "),
+                Span::styled(format!("{}{}", csi.channel, secondary), app.theme.text_highlight),
+                Span::raw(" | "),
+                Span::styled(format!("{} MHz", csi.bandwidth_mhz()), app.theme.text_highlight),
+                Span::raw(" | "),
+                Span::styled(csi.mode_label(), app.theme.text_highlight),
+                Span::raw(" | "),
+            ];
+            if csi.subcarrier_count_ok() {
+                spans.push(Span::styled(format!("{} SC", sc_count), app.theme.text_highlight));
+            } else {
+                let expected: Vec<String> = csi.expected_subcarrier_counts().iter().map(|n| n.to_string()).collect();
+                spans.push(Span::styled(
+                    format!("{} SC ⚠ expected {}", sc_count, expected.join("/")),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                ));
+            }
+            Line::from(spans)
+        }
+        None => Line::from(Span::styled("Ch -- | No CSI", app.theme.text_normal)),
+    };
+    f.render_widget(Paragraph::new(channel_text).alignment(Alignment::Center), chunks[11]);
+
     // Footer
     let mac_str = stats.csi.as_ref().map(|c| c.mac.as_str()).unwrap_or("Waiting...");
     let meta_text = Line::from(vec![
         Span::raw("Time: "),
-        Span::styled(format!("{}ms", stats.timestamp), app.theme.text_highlight),
+        Span::styled(app.stats_format.timestamp(stats.timestamp), app.theme.text_highlight),
         Span::raw(" | Source: "),
         Span::styled(mac_str, app.theme.text_highlight),
     ]);
-    f.render_widget(Paragraph::new(meta_text).alignment(Alignment::Center), chunks[7]);
-}
\ No newline at end of file
+    f.render_widget(Paragraph::new(meta_text).alignment(Alignment::Center), chunks[12]);
+}
+
+/// Field name / value pairs of one packet, in firmware print order. Values are NO_VALUE without CSI.
+fn metadata_fields(csi: Option<&CsiData>) -> Vec<(&'static str, String)> {
+    let value = |f: fn(&CsiData) -> String| csi.map(f).unwrap_or_else(|| NO_VALUE.to_string());
+    vec![
+        ("MAC", value(|c| c.mac.clone())),
+        ("RSSI", value(|c| format!("{} dBm", c.rssi))),
+        ("Rate", value(|c| c.rate.to_string())),
+        ("Noise Floor", value(|c| format!("{} dBm", c.noise_floor))),
+        ("Channel", value(|c| c.channel.to_string())),
+        ("Secondary Ch", value(|c| c.secondary_channel_label().map(|s| format!("{} ({})", c.secondary_channel, s)).unwrap_or_else(|| c.secondary_channel.to_string()))),
+        ("Timestamp", value(|c| format!("{} µs", c.timestamp))),
+        ("Sig Len", value(|c| c.sig_len.to_string())),
+        ("Rx State", value(|c| c.rx_state.to_string())),
+        ("SGI", value(|c| c.sgi.to_string())),
+        ("Antenna", value(|c| c.ant.to_string())),
+        ("AMPDU Count", value(|c| c.ampdu_cnt.to_string())),
+        ("Sig Mode", value(|c| format!("{} ({})", c.sig_mode, c.mode_label()))),
+        ("MCS", value(|c| c.mcs.to_string())),
+        ("CWB", value(|c| format!("{} ({} MHz)", c.cwb, c.bandwidth_mhz()))),
+        ("Smoothing", value(|c| c.smoothing.to_string())),
+        ("Not Sounding", value(|c| c.not_sounding.to_string())),
+        ("Aggregation", value(|c| c.aggregation.to_string())),
+        ("STBC", value(|c| c.stbc.to_string())),
+        ("FEC Coding", value(|c| c.fec_coding.to_string())),
+        ("Sig Len (2nd)", value(|c| c.sig_len_extra.to_string())),
+        ("Data Length", value(|c| c.data_length.to_string())),
+        ("Subcarriers", value(|c| (c.csi_raw_data.len() / 2).to_string())),
+    ]
+}
+
+/// Alternate Dashboard mode: the metadata of the shown packet in two field/value column pairs
+fn draw_metadata(f: &mut Frame, app: &App, csi: Option<&CsiData>, area: Rect) {
+    let fields = metadata_fields(csi);
+    let half = fields.len().div_ceil(2);
+    let cell = |field: Option<&(&'static str, String)>| match field {
+        Some((name, value)) => [Cell::from(format!(" {}", name)), Cell::from(Span::styled(value.clone(), app.theme.text_highlight))],
+        None => [Cell::from(""), Cell::from("")],
+    };
+
+    let rows: Vec<Row> = (0..half)
+        .map(|i| {
+            let [name_a, value_a] = cell(fields.get(i));
+            let [name_b, value_b] = cell(fields.get(half + i));
+            Row::new(vec![name_a, value_a, name_b, value_b])
+        })
+        .collect();
+
+    let table = Table::new(rows, [Constraint::Length(15), Constraint::Percentage(35), Constraint::Length(15), Constraint::Percentage(35)])
+        .style(app.theme.text_normal)
+        .header(
+            Row::new(vec![" Field", "Value", " Field", "Value"])
+                .style(Style::default().add_modifier(Modifier::BOLD))
+                .bottom_margin(1),
+        )
+        .footer(Row::new(vec![Cell::from(" [Shift+D] Gauges")]).style(app.theme.text_normal).top_margin(1));
+
+    f.render_widget(table, area);
+}
@@ -25,14 +25,19 @@
 use ratatui::{prelude::*, widgets::*};
 use ratatui::widgets::canvas::{Canvas, Line as CanvasLine};
 use crate::App;
+use crate::frontend::view_state::Projection;
 use std::f64::consts::PI;
 
+// Packets drawn behind the newest one (per-pane override: ViewState::history_depth)
+pub const DEFAULT_DEPTH: usize = 30;
+
 pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let theme = &app.theme;
     let state = app.pane_states.get(&id).cloned().unwrap_or_else(crate::frontend::view_state::ViewState::new);
 
-    let border_style = if is_focused { theme.focused_border } else { theme.normal_border };
-    let history_len = app.history.len();
+    let border_style = app.pane_border(is_focused, crate::layout_tree::ViewType::Isometric);
+    let source = app.source_for(id);
+    let history_len = source.history.len();
 
     // 1. Determine Status & Target Packet
     let mut status_label = " [LIVE] ".to_string();
@@ -40,7 +45,7 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     let mut target_index = history_len.saturating_sub(1);
 
     if let Some(anchor) = state.anchor_packet_id {
-        if let Some(idx) = app.history.iter().position(|p| p.id == anchor) {
+        if let Some(idx) = source.history.iter().position(|p| p.id == anchor) {
             target_index = idx;
             status_label = format!(" [REPLAY ID:{}] ", anchor);
             status_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
@@ -52,11 +57,14 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
 
     // 2. Build Block
     let title_top = Line::from(vec![
-        Span::styled(format!(" #{} CIR (Multipath) ", id), theme.text_normal),
+        Span::styled(format!(" #{} {} ", id, app.pane_label(id, "CIR (Multipath)")), app.title_style(crate::layout_tree::ViewType::Isometric)),
         Span::styled(status_label, status_style),
     ]);
 
-    let footer_text = format!(" Skew X: {:.1} | Skew Y: {:.1} ", state.camera_x, state.camera_y);
+    let depth = state.history_depth(DEFAULT_DEPTH, source.history.len());
+    let projection = state.projection(Projection::Oblique);
+    let dc_label = if state.null_dc { "Nulled" } else { "Included" };
+    let footer_text = format!(" Skew X: {:.1} | Skew Y: {:.1} | Zoom: {:.2}x | Depth: {} | Smooth: {} | Proj: {} [K] | DC: {} [L] ", state.camera_x, state.camera_y, state.zoom, depth, state.smoothing_label(), projection.label(), dc_label);
     let title_bottom = Line::from(Span::styled(footer_text, theme.text_highlight));
 
     let block = Block::default()
@@ -72,64 +80,76 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     }
 
     // 3. Prepare Data
-    const DEPTH: usize = 30;
-    let start_idx = target_index.saturating_sub(DEPTH);
-    let end_idx = target_index.min(history_len - 1);
-    // Ensure we have a valid range
-    let slice = if start_idx <= end_idx {
-        &app.history[start_idx..=end_idx]
-    } else {
-        &[]
-    };
+    // Smoothed I/Q per packet (extra leading packets let the EMA settle before the visible slice)
+    let window = source.history_window(target_index, 2 * depth);
+    let mut frames = crate::frontend::smoothing::ema_iq(window, state.smoothing_alpha);
+    frames.drain(..frames.len() - (depth + 1).min(frames.len()));
+    if state.null_dc && let Some(newest) = window.last() {
+        crate::frontend::smoothing::null_dc(&mut frames, newest);
+    }
+    crate::frontend::smoothing::apply_gain(&mut frames, app.amplitude_gain);
 
     // 4. Projection Parameters
     let skew_x = 0.5 + state.camera_x * 0.1;
     let skew_y = 0.3 + state.camera_y * 0.1;
 
     let z_spacing = 3.0;
-    let max_z = DEPTH as f64 * z_spacing;
+    let show_grid = !state.hide_grid;
+    let max_z = depth as f64 * z_spacing;
 
     // We will plot 64 delay bins
     let x_bins = 64.0f64;
 
-    // Calculate Bounds dynamically to handle negative skew (rotation)
-    let x_min_val = 0.0f64.min(max_z * skew_x);
-    let x_max_val = x_bins.max(x_bins + max_z * skew_x);
+    // Scene point (delay bin, power, packets back) -> canvas. The delay axis is centered so the
+    // perspective vanishing point sits mid-floor; z is negated (older packets are into the screen).
+    let project = move |bin: f64, power: f64, z: f64| -> (f64, f64) {
+        let (x, y) = projection.project(bin - x_bins / 2.0, power, -z, (skew_x, skew_y), max_z);
+        (x + x_bins / 2.0, y)
+    };
 
-    let y_min_val = 0.0f64.min(max_z * skew_y);
-    let y_max_val = 100.0f64.max(100.0 + max_z * skew_y);
+    // Calculate Bounds from the projected corners of the scene box (handles negative skew)
+    let corners: Vec<(f64, f64)> = [0.0, x_bins].iter()
+        .flat_map(|&x| [0.0, 100.0].map(|y| (x, y)))
+        .flat_map(|(x, y)| [0.0, max_z].map(|z| project(x, y, z)))
+        .collect();
+    let x_min_val = corners.iter().map(|c| c.0).fold(f64::INFINITY, f64::min);
+    let x_max_val = corners.iter().map(|c| c.0).fold(f64::NEG_INFINITY, f64::max);
+    let y_min_val = corners.iter().map(|c| c.1).fold(f64::INFINITY, f64::min);
+    let y_max_val = corners.iter().map(|c| c.1).fold(f64::NEG_INFINITY, f64::max);
+
+    // Wheel zoom: shrink/grow the visible window around its center
+    let zoom_bounds = |min: f64, max: f64| -> [f64; 2] {
+        let center = (min + max) / 2.0;
+        let half = (max - min) / 2.0 / state.zoom;
+        [center - half, center + half]
+    };
 
     let canvas = Canvas::default()
+        .marker(app.canvas_marker.marker())
         .block(block)
         .background_color(theme.root.bg.unwrap_or(Color::Reset))
-        .x_bounds([x_min_val - 20.0, x_max_val + 20.0])
-        .y_bounds([y_min_val - 20.0, y_max_val + 20.0])
+        .x_bounds(zoom_bounds(x_min_val - 20.0, x_max_val + 20.0))
+        .y_bounds(zoom_bounds(y_min_val - 20.0, y_max_val + 20.0))
         .paint(move |ctx| {
             // Draw Grid / Floor
-            let z_len = slice.len() as f64 * z_spacing;
-
-            // Left Edge (Delay 0 - LOS)
-            ctx.draw(&CanvasLine {
-                x1: 0.0, y1: 0.0,
-                x2: z_len * skew_x, y2: z_len * skew_y,
-                color: Color::DarkGray,
-            });
-            // Right Edge (Max Delay)
-            ctx.draw(&CanvasLine {
-                x1: x_bins, y1: 0.0,
-                x2: x_bins + z_len * skew_x, y2: z_len * skew_y,
-                color: Color::DarkGray,
-            });
+            let z_len = frames.len() as f64 * z_spacing;
+
+            // Left Edge (Delay 0 - LOS), then Right Edge (Max Delay).
+            // With the grid hidden ([Shift+A]) the LOS edge stays as the one reference.
+            let edges = if show_grid { vec![0.0, x_bins] } else { vec![0.0] };
+            for x in edges {
+                let (x1, y1) = project(x, 0.0, 0.0);
+                let (x2, y2) = project(x, 0.0, z_len);
+                ctx.draw(&CanvasLine { x1, y1, x2, y2, color: Color::DarkGray });
+            }
 
             // Draw Packets (Back to Front)
-            for (i, packet) in slice.iter().enumerate() {
-                let z_index = (slice.len() - 1 - i) as f64;
-                let z_offset_x = z_index * z_spacing * skew_x;
-                let z_offset_y = z_index * z_spacing * skew_y;
+            for (i, iq) in frames.iter().enumerate() {
+                let z = (frames.len() - 1 - i) as f64 * z_spacing;
 
-                if let Some(csi) = &packet.csi {
+                if !iq.is_empty() {
                     // Compute Impulse Response (IDFT)
-                    let cir = compute_cir(&csi.csi_raw_data);
+                    let cir = compute_cir(iq);
 
                     let mut prev_x = 0.0;
                     let mut prev_y = 0.0;
@@ -138,9 +158,7 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
                         // Scale Power for Display
                         let y_val = (power * 0.5).min(80.0);
 
-                        let x_base = bin as f64;
-                        let x_screen = x_base + z_offset_x;
-                        let y_screen = y_val + z_offset_y;
+                        let (x_screen, y_screen) = project(bin as f64, y_val, z);
 
                         // Color based on Power (Heatmap style)
                         let color = if y_val > 60.0 { Color::Red }
@@ -165,6 +183,9 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
         });
 
     f.render_widget(canvas, area);
+    if !show_grid {
+        return;
+    }
 
     // Render static labels on top (Outside the Canvas coordinate system)
     let legend_text = vec![
@@ -186,9 +207,8 @@ pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
     f.render_widget(axis_label, area);
 }/// Computes the Channel Impulse Response (CIR) magnitude via IDFT
 /// Returns a vector of magnitudes (Power Delay Profile)
-fn compute_cir(raw_data: &[i32]) -> Vec<f64> {
-    let sc_count = raw_data.len() / 2;
-    let n = sc_count; // Transform size
+fn compute_cir(iq: &[(f64, f64)]) -> Vec<f64> {
+    let n = iq.len(); // Transform size
     let mut output = Vec::with_capacity(n);
 
     // Naive IDFT O(N^2) - Fast enough for N=64
@@ -198,11 +218,8 @@ fn compute_cir(raw_data: &[i32]) -> Vec<f64> {
         let mut sum_i = 0.0;
         let mut sum_q = 0.0;
 
-        for k in 0..n {
-            // Parse Complex CSI X[k]
-            let i_val = raw_data.get(k * 2).copied().unwrap_or(0) as f64;
-            let q_val = raw_data.get(k * 2 + 1).copied().unwrap_or(0) as f64;
-
+        // Complex CSI X[k]
+        for (k, &(i_val, q_val)) in iq.iter().enumerate() {
             // Exponent: e^(j * theta) = cos(theta) + j*sin(theta)
             let theta = 2.0 * PI * (k as f64) * (t as f64) / (n as f64);
             let cos_t = theta.cos();
@@ -0,0 +1,214 @@
+// --- File: src/frontend/views/waterfall.rs ---
+// --- Purpose: Amplitude Waterfall (all subcarriers over time) ---
+//
+// [Graph Description]
+// A 2D Heatmap of CSI amplitude.
+// X-Axis: Time (History), oldest on the left, newest on the right. With the per-second
+//         history (Shift+T) each column is a one-second average, so the window spans minutes.
+// Y-Axis: Subcarrier number, DC centred (lowest frequency at the bottom)
+// Color: Amplitude |H| of each subcarrier, normalized to the window maximum.
+//
+// [Plotting Logic]
+// Each terminal cell holds two subcarriers using a half block ('▀'): the foreground
+// paints the upper one, the background the lower one.
+// When the window holds more packets than there are columns (or more subcarriers than
+// half-rows), neighbouring samples are averaged into one cell.
+//
+// [Concepts & Application]
+// This is the canonical CSI plot in the literature. A static channel shows horizontal
+// bands (frequency-selective fading that doesn't change over time). Motion shows up as
+// vertical disturbances across many subcarriers at once.
+//
+// [Demo]
+// Stand still: the bands stay flat.
+// Walk between TX and RX: vertical stripes appear as the fading pattern shifts.
+//
+use ratatui::{prelude::*, widgets::*};
+use crate::App;
+use crate::frontend::widgets::colorbar;
+
+// Packets in the waterfall window (per-pane override: ViewState::history_depth)
+pub const DEFAULT_DEPTH: usize = 200;
+
+// Columns reserved for the subcarrier labels on the left
+const Y_LABEL_WIDTH: u16 = 4;
+
+pub fn draw(f: &mut Frame, app: &App, area: Rect, is_focused: bool, id: usize) {
+    let theme = &app.theme;
+    let state = app.pane_states.get(&id).cloned().unwrap_or_else(crate::frontend::view_state::ViewState::new);
+
+    let border_style = app.pane_border(is_focused, crate::layout_tree::ViewType::Waterfall);
+    // Per-second averages for long trends, or every snapshot
+    // The per-second history is only kept for the primary source
+    let source = app.source_for(id);
+    let coarse = state.coarse_history && source.overlay_label.is_none();
+    let history_len = if coarse { app.history_coarse.len() } else { source.history.len() };
+
+    // 1. Determine Status & Target Packet
+    let mut status_label = " [LIVE] ".to_string();
+    let mut status_style = Style::default().fg(Color::Green).add_modifier(Modifier::BOLD);
+    let mut target_index = history_len.saturating_sub(1);
+
+    if let Some(anchor) = state.anchor_packet_id {
+        let found = if coarse { app.coarse_index_of(anchor) } else { source.index_of(anchor) };
+        if let Some(idx) = found {
+            target_index = idx;
+            status_label = format!(" [REPLAY ID:{}] ", anchor);
+            status_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        } else {
+            status_label = " [EXPIRED] ".to_string();
+            status_style = Style::default().fg(Color::Red);
+        }
+    }
+
+    // 2. Setup Data Slice
+    let depth = state.history_depth(DEFAULT_DEPTH, history_len);
+    let coarse_slice;
+    let slice = if coarse {
+        coarse_slice = app.coarse_window(target_index, depth);
+        &coarse_slice[..]
+    } else {
+        source.history_window(target_index, depth)
+    };
+
+    let title_top = Line::from(vec![
+        Span::styled(format!(" #{} {} ", id, app.pane_label(id, "Amplitude Waterfall")), app.title_style(crate::layout_tree::ViewType::Waterfall)),
+        Span::styled(status_label, status_style),
+    ]);
+
+    let Some(stats) = slice.last() else {
+        let block = Block::default()
+            .title(title_top)
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .style(theme.root);
+        f.render_widget(block, area);
+        return;
+    };
+
+    // 3. Raw I/Q per packet, optionally relative to the captured reference
+    let mut frames = crate::frontend::smoothing::ema_iq(slice, 1.0);
+    let ref_tag = crate::frontend::smoothing::apply_reference(app, &state, &mut frames);
+    let flat_tag = crate::frontend::smoothing::apply_flatten(app, id, &mut frames);
+    let sc_labels = crate::frontend::smoothing::center_dc(&mut frames, stats);
+    crate::frontend::smoothing::apply_gain(&mut frames, app.amplitude_gain);
+
+    // 4. Build Block
+    let window_label = if coarse { format!("{} s (1/s)", slice.len()) } else { format!("{} pkts", slice.len()) };
+    let footer_text = format!(" Window: {} | Time: {} {}{}", window_label, app.stats_format.timestamp(stats.timestamp), ref_tag, flat_tag);
+    let title_bottom = Line::from(Span::styled(footer_text, theme.text_highlight));
+
+    let block = Block::default()
+        .title(title_top)
+        .title_bottom(title_bottom.alignment(Alignment::Right))
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .style(theme.root);
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // 5. Carve out colorbar, Y labels and the bottom time axis
+    let (plot_area, bar_area) = colorbar::split(inner);
+    if plot_area.width <= Y_LABEL_WIDTH + 1 || plot_area.height < 3 {
+        return;
+    }
+    let label_area = Rect { width: Y_LABEL_WIDTH, height: plot_area.height - 1, ..plot_area };
+    let axis_area = Rect { y: plot_area.bottom() - 1, height: 1, ..plot_area };
+    let heat_area = Rect {
+        x: plot_area.x + Y_LABEL_WIDTH,
+        width: plot_area.width - Y_LABEL_WIDTH,
+        height: plot_area.height - 1,
+        ..plot_area
+    };
+
+    // 6. Amplitude Matrix [packet][subcarrier]
+    let amplitudes: Vec<Vec<f64>> = frames
+        .iter()
+        .map(|row| row.iter().map(|&(i, q)| (i * i + q * q).sqrt()).collect())
+        .collect();
+
+    let subcarriers = amplitudes.iter().map(|a| a.len()).max().unwrap_or(0);
+    // Auto-scale to the window maximum (or a percentile of it, see App::amplitude_clamp),
+    // or a fixed full scale when a global gain is set
+    let max_amp = if app.amplitude_gain != 1.0 {
+        crate::frontend::smoothing::FULL_SCALE_AMPLITUDE
+    } else {
+        crate::frontend::smoothing::robust_max(amplitudes.iter().flatten().copied(), app.amplitude_clamp.percentile())
+    };
+    if subcarriers == 0 || max_amp <= 0.0 {
+        return;
+    }
+
+    // 7. Downsample to the pane: columns = time bins, half-rows = subcarrier bins
+    let cols = (heat_area.width as usize).min(amplitudes.len());
+    let half_rows = (heat_area.height as usize * 2).min(subcarriers);
+
+    let cell_value = |col: usize, half_row: usize| -> f64 {
+        let (t0, t1) = bin_range(col, cols, amplitudes.len());
+        let (s0, s1) = bin_range(half_row, half_rows, subcarriers);
+        let mut sum = 0.0;
+        let mut n = 0;
+        for packet in &amplitudes[t0..t1] {
+            for &amp in packet.iter().take(s1).skip(s0) {
+                sum += amp;
+                n += 1;
+            }
+        }
+        if n > 0 { (sum / n as f64 / max_amp).min(1.0) } else { 0.0 }
+    };
+
+    // Newest packet sits against the right edge
+    let x_offset = heat_area.width as usize - cols;
+    let buf = f.buffer_mut();
+    for col in 0..cols {
+        let x = heat_area.x + (x_offset + col) as u16;
+        for row in 0..heat_area.height as usize {
+            // Lowest subcarrier at the bottom: the bottom row holds half-rows 0 (lower) and 1 (upper)
+            let lower = (heat_area.height as usize - 1 - row) * 2;
+            let upper = lower + 1;
+            if lower >= half_rows {
+                continue;
+            }
+
+            let bg = theme.heatmap_color(cell_value(col, lower));
+            let fg = if upper < half_rows { theme.heatmap_color(cell_value(col, upper)) } else { theme.root.bg.unwrap_or(Color::Reset) };
+
+            if let Some(cell) = buf.cell_mut((x, heat_area.y + row as u16)) {
+                cell.set_symbol("▀").set_fg(fg).set_bg(bg);
+            }
+        }
+    }
+
+    // 8. Axes: subcarrier labels every few rows, time span underneath
+    let label_rows = heat_area.height as usize;
+    let label_step = (label_rows / 4).max(1);
+    let mut labels = vec![Line::from(""); label_rows];
+    for (row, label) in labels.iter_mut().enumerate() {
+        if row % label_step == 0 || row == label_rows - 1 {
+            let half_row = ((label_rows - 1 - row) * 2).min(half_rows.saturating_sub(1));
+            let (sc, _) = bin_range(half_row, half_rows, subcarriers);
+            let number = sc_labels.get(sc).copied().unwrap_or(sc as i32);
+            *label = Line::from(Span::styled(format!("{:>3}", number), theme.text_normal));
+        }
+    }
+    f.render_widget(Paragraph::new(labels), label_area);
+
+    let span_label = crate::app::format_seconds_ago(app.seconds_between(&slice[0], stats));
+    let axis_text = Line::from(vec![
+        Span::styled(format!("{:<w$}", format!(" {}", span_label), w = (axis_area.width as usize).saturating_sub(4)), theme.text_normal),
+        Span::styled("now", theme.text_normal),
+    ]);
+    f.render_widget(Paragraph::new(axis_text), axis_area);
+
+    if let Some(bar_area) = bar_area {
+        colorbar::draw(f, theme, bar_area, 0.0, max_amp, "|H|");
+    }
+}
+
+/// Maps bin `i` of `bins` onto [start, end) of `len` samples (every bin gets at least one sample)
+fn bin_range(i: usize, bins: usize, len: usize) -> (usize, usize) {
+    let start = i * len / bins;
+    let end = ((i + 1) * len / bins).max(start + 1).min(len);
+    (start, end)
+}
@@ -0,0 +1,102 @@
+// --- File: src/frontend/widgets/colorbar.rs ---
+// --- Purpose: Vertical colorbar legends (heatmap intensity ramp and phase colormap) with value labels ---
+
+use ratatui::{prelude::*, widgets::*};
+use crate::frontend::theme::{self, PhaseColormap, Theme};
+
+// Width of the side strip: 2 cells of swatch, a gap and the value label
+pub const COLORBAR_WIDTH: u16 = 10;
+
+// Below this inner width the plot needs every column, so the colorbar is hidden
+const MIN_PLOT_WIDTH: u16 = 40;
+
+/// Splits a pane's inner area into (plot, colorbar strip).
+/// The strip is None when the pane is too narrow or too short to fit a readable bar.
+pub fn split(area: Rect) -> (Rect, Option<Rect>) {
+    if area.width < MIN_PLOT_WIDTH + COLORBAR_WIDTH || area.height < 5 {
+        return (area, None);
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(MIN_PLOT_WIDTH), Constraint::Length(COLORBAR_WIDTH)])
+        .split(area);
+
+    (chunks[0], Some(chunks[1]))
+}
+
+/// Draws the bar with `max` at the top and `min` at the bottom, labelled every few rows.
+pub fn draw(f: &mut Frame, theme: &Theme, area: Rect, min: f64, max: f64, unit: &str) {
+    // 1. Header row holds the unit, the rest is the bar itself
+    let bar_rows = area.height.saturating_sub(1) as usize;
+    if bar_rows < 2 {
+        return;
+    }
+
+    let mut lines = Vec::with_capacity(area.height as usize);
+    lines.push(Line::from(Span::styled(unit.to_string(), theme.text_normal)));
+
+    // 2. Label roughly every 4th row, always including both ends
+    let label_step = (bar_rows / 4).max(1);
+
+    for row in 0..bar_rows {
+        let intensity = 1.0 - row as f64 / (bar_rows - 1) as f64;
+        let color = theme.heatmap_color(intensity);
+
+        let is_label_row = row % label_step == 0 || row == bar_rows - 1;
+        let label = if is_label_row {
+            format_value(min + (max - min) * intensity)
+        } else {
+            String::new()
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled("██", Style::default().fg(color)),
+            Span::styled(format!(" {}", label), theme.text_normal),
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines).style(theme.root), area);
+}
+
+/// Phase legend for `colormap`: +π at the top, -π at the bottom, 0 in the middle.
+pub fn draw_phase(f: &mut Frame, theme: &Theme, area: Rect, colormap: PhaseColormap) {
+    let bar_rows = area.height.saturating_sub(1) as usize;
+    if bar_rows < 2 {
+        return;
+    }
+
+    let mut lines = Vec::with_capacity(area.height as usize);
+    lines.push(Line::from(Span::styled("phase", theme.text_normal)));
+
+    for row in 0..bar_rows {
+        let phase = (1.0 - 2.0 * row as f64 / (bar_rows - 1) as f64) * std::f64::consts::PI;
+        let label = if row == 0 {
+            "+π"
+        } else if row == bar_rows - 1 {
+            "-π"
+        } else if row == (bar_rows - 1) / 2 {
+            "0"
+        } else {
+            ""
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled("██", Style::default().fg(theme::phase_to_color(phase, colormap, theme))),
+            Span::styled(format!(" {}", label), theme.text_normal),
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines).style(theme.root), area);
+}
+
+// Keeps labels within the strip width for both small (radians) and large (counts) ranges
+fn format_value(value: f64) -> String {
+    if value.abs() >= 100.0 {
+        format!("{:.0}", value)
+    } else if value.abs() >= 10.0 {
+        format!("{:.1}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
@@ -0,0 +1,86 @@
+// --- File: src/frontend/widgets/heatmap.rs ---
+// --- Purpose: Resamples a heatmap grid to the canvas resolution (bilinear up, box-average down) ---
+
+use crate::frontend::theme::CanvasMarker;
+
+/// Canvas dots per terminal cell (columns, rows) for a marker
+pub fn dots_per_cell(marker: CanvasMarker) -> (usize, usize) {
+    match marker {
+        CanvasMarker::Braille => (2, 4),
+        CanvasMarker::HalfBlock => (1, 2),
+        CanvasMarker::Block | CanvasMarker::Dot => (1, 1),
+    }
+}
+
+/// Resamples `grid` (rows of possibly different lengths, missing values read as 0.0) to
+/// `out_rows` x `out_cols`. Each axis is interpolated linearly between cell centres where the
+/// output is larger than the input, and averaged over the covered cells where it is smaller,
+/// so no cell is skipped when there are more cells than dots.
+pub fn resample(grid: &[Vec<f64>], out_rows: usize, out_cols: usize) -> Vec<Vec<f64>> {
+    let cols = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    if grid.is_empty() || cols == 0 || out_rows == 0 || out_cols == 0 {
+        return vec![vec![0.0; out_cols]; out_rows];
+    }
+
+    // Along the subcarrier axis first, then along time
+    let widened: Vec<Vec<f64>> = grid
+        .iter()
+        .map(|row| {
+            let mut padded = row.clone();
+            padded.resize(cols, 0.0);
+            resample_1d(&padded, out_cols)
+        })
+        .collect();
+
+    let mut result = vec![vec![0.0; out_cols]; out_rows];
+    for c in 0..out_cols {
+        let column: Vec<f64> = widened.iter().map(|row| row[c]).collect();
+        for (r, value) in resample_1d(&column, out_rows).into_iter().enumerate() {
+            result[r][c] = value;
+        }
+    }
+    result
+}
+
+fn resample_1d(values: &[f64], out: usize) -> Vec<f64> {
+    let n = values.len();
+    if out >= n {
+        // Upsampling: linear between the centres of the input cells, edges held
+        (0..out)
+            .map(|k| {
+                let u = ((k as f64 + 0.5) * n as f64 / out as f64 - 0.5).clamp(0.0, (n - 1) as f64);
+                let i = u.floor() as usize;
+                let j = (i + 1).min(n - 1);
+                let t = u - i as f64;
+                values[i] * (1.0 - t) + values[j] * t
+            })
+            .collect()
+    } else {
+        // Downsampling: mean of the cells each output dot covers
+        (0..out)
+            .map(|k| {
+                let start = k * n / out;
+                let end = ((k + 1) * n / out).max(start + 1);
+                values[start..end].iter().sum::<f64>() / (end - start) as f64
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resample_interpolates_up_and_averages_down() {
+        // Upsampling keeps the edge cells and blends linearly in between
+        let grid = vec![vec![0.0, 1.0], vec![2.0, 3.0]];
+        let up = resample(&grid, 2, 4);
+        assert_eq!(up[0], vec![0.0, 0.25, 0.75, 1.0]);
+        assert_eq!(up[1], vec![2.0, 2.25, 2.75, 3.0]);
+
+        // Downsampling averages every cell instead of skipping some; short rows read as 0.0
+        let grid = vec![vec![1.0, 3.0, 5.0, 7.0], vec![1.0]];
+        assert_eq!(resample(&grid, 1, 2), vec![vec![1.25, 3.0]]);
+    }
+}
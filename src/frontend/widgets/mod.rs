@@ -0,0 +1,5 @@
+// --- File: src/frontend/widgets/mod.rs ---
+// --- Purpose: Registers reusable drawing helpers shared between views ---
+
+pub mod colorbar;
+pub mod heatmap;
@@ -1,17 +1,23 @@
 // --- File: src/input_handler.rs ---
 // --- Purpose: Handles keyboard input events and maps them to App actions (Controller Logic) ---
 
-use crossterm::event::{self, Event, KeyCode, KeyModifiers, MouseEventKind, MouseButton, KeyEventKind};
+use crossterm::event::{self, Event, KeyCode, MouseEventKind, MouseButton, KeyEventKind};
 use std::io;
 use ratatui::layout::Direction;
 use crate::App;
 use crate::frontend::layout_tree::SplitDirection;
+use crate::frontend::view_traits::ViewBehavior;
 use crate::frontend::overlays::view_selector::AVAILABLE_VIEWS;
 use crate::frontend::overlays::main_menu::MENU_ITEMS;
 use crate::frontend::overlays::theme_selector::AVAILABLE_THEMES;
+use crate::frontend::overlays::gauge_settings::GAUGE_SETTINGS;
 use crate::config_manager;
+use crate::clipboard;
+use crate::keybindings::{Action, Scope};
 use crate::frontend::theme::Theme;
 
+/// Handles one terminal event. Returns true if it may have changed what is drawn, so the main
+/// loop knows to redraw (resizes always count).
 pub fn handle_event(app: &mut App) -> io::Result<bool> {
     match event::read()? {
         Event::Key(key) => {
@@ -24,107 +30,189 @@ pub fn handle_event(app: &mut App) -> io::Result<bool> {
 
             if handle_popups(app, key)? { return Ok(true); }
 
+            // --- FILE REPLAY CONTROLS ---
+            if app.is_replaying() {
+                match key.code {
+                    KeyCode::Char('{') => { app.adjust_replay_speed(false); return Ok(true); }
+                    KeyCode::Char('}') => { app.adjust_replay_speed(true); return Ok(true); }
+                    KeyCode::Char('o') => { app.replay_loop = !app.replay_loop; return Ok(true); }
+                    KeyCode::Char(',') => { app.seek_replay(-5); return Ok(true); }
+                    KeyCode::Char('.') => { app.seek_replay(5); return Ok(true); }
+                    _ => {}
+                }
+            }
+
             // --- FULLSCREEN MODE NAVIGATION ---
             if let Some(fs_id) = app.fullscreen_pane_id {
                 let current_view_type = get_view_type_for_pane(app, fs_id);
                 // REFACTOR: Changed packet_count to id
-                let current_live_id = app.current_stats.id;
-                let min_id = app.history.first().map(|p| p.id).unwrap_or(0);
+                let source = app.source_for(fs_id);
+                let current_live_id = source.live_id();
+                let min_id = source.min_id();
+                let total_subcarriers = source.current_stats.csi.as_ref().map(|c| c.csi_raw_data.len() / 2).unwrap_or(64);
+                let is_phase = current_view_type == crate::frontend::layout_tree::ViewType::Phase;
+                let default_depth = current_view_type.default_depth();
+                let default_projection = current_view_type.default_projection();
+                let is_coarse = current_view_type == crate::frontend::layout_tree::ViewType::Waterfall
+                    && source.overlay_label.is_none()
+                    && app.pane_states.get(&fs_id).is_some_and(|s| s.coarse_history);
+                let history_len = if is_coarse { app.history_coarse.len() } else { source.history.len() };
+                let is_temporal = current_view_type.is_temporal();
+                let is_spatial = current_view_type.is_spatial();
+                let Some(action) = app.keybindings.action_for(&key, Scope::Fullscreen) else { return Ok(false) };
                 let state = app.get_pane_state_mut(fs_id);
 
-                match key.code {
-                    KeyCode::Char('q') => { app.show_quit_popup = true; return Ok(true); }
-                    KeyCode::Char(' ') | KeyCode::Esc => { app.fullscreen_pane_id = None; return Ok(true); }
-                    KeyCode::Char('r') => { state.reset_live(); return Ok(true); }
-                    KeyCode::Left if current_view_type.is_temporal() => { state.step_back(current_live_id, min_id); return Ok(true); }
-                    KeyCode::Right if current_view_type.is_temporal() => { state.step_forward(current_live_id, min_id); return Ok(true); }
-                    KeyCode::Char('w') if current_view_type.is_spatial() => { state.move_camera(0.0, -1.0); return Ok(true); }
-                    KeyCode::Char('s') if current_view_type.is_spatial() => { state.move_camera(0.0, 1.0); return Ok(true); }
-                    KeyCode::Char('a') if current_view_type.is_spatial() => { state.move_camera(-1.0, 0.0); return Ok(true); }
-                    KeyCode::Char('d') if current_view_type.is_spatial() => { state.move_camera(1.0, 0.0); return Ok(true); }
+                match action {
+                    Action::Quit => { app.show_quit_popup = true; }
+                    Action::GainUp => app.adjust_amplitude_gain(true),
+                    Action::GainDown => app.adjust_amplitude_gain(false),
+                    Action::ComparePackets => { app.show_packet_diff = true; }
+                    Action::Inspector => { app.show_inspector = true; app.inspector_input.clear(); }
+                    Action::ExportPacket => export_packet(app, fs_id),
+                    Action::ToggleSource => toggle_pane_source(app, fs_id),
+                    Action::ExitFullscreen => app.exit_fullscreen(),
+                    Action::ResetLive => state.reset_live(),
+                    Action::ToggleReference if current_view_type.uses_reference() => state.subtract_reference = !state.subtract_reference,
+                    Action::StepBack if is_temporal => state.step_back(current_live_id, min_id),
+                    Action::StepForward if is_temporal => state.step_forward(current_live_id, min_id),
+                    Action::AutoAdvance if is_temporal => state.cycle_auto_advance(min_id),
+                    Action::ToggleAdvanceLoop if is_temporal => state.advance_loop = !state.advance_loop,
+                    Action::CameraUp if is_spatial => state.move_camera(0.0, -1.0),
+                    Action::CameraDown if is_spatial => state.move_camera(0.0, 1.0),
+                    Action::CameraLeft if is_spatial => state.move_camera(-1.0, 0.0),
+                    Action::CameraRight if is_spatial => state.move_camera(1.0, 0.0),
+                    Action::CycleSmoothing if current_view_type.is_smoothable() => state.cycle_smoothing(),
+                    Action::CycleSmoothing if current_view_type == crate::frontend::layout_tree::ViewType::Spectrogram => {
+                        state.smooth_heatmap = !state.smooth_heatmap;
+                    }
+                    Action::TogglePhaseUnwrap if current_view_type.uses_phase() => state.unwrap_phase = !state.unwrap_phase,
+                    Action::ToggleAmplitudeColor if current_view_type == crate::frontend::layout_tree::ViewType::Polar => {
+                        state.cycle_ring_color();
+                    }
+                    Action::ToggleDcNull if current_view_type == crate::frontend::layout_tree::ViewType::Isometric => {
+                        state.null_dc = !state.null_dc;
+                    }
+                    Action::ToggleCoarseHistory if current_view_type == crate::frontend::layout_tree::ViewType::Waterfall => {
+                        state.coarse_history = !state.coarse_history;
+                        // The depth was counted in the other history's points
+                        state.history_depth = None;
+                    }
+                    Action::ToggleGrid if is_spatial => state.hide_grid = !state.hide_grid,
+                    Action::ToggleMetadata if current_view_type == crate::frontend::layout_tree::ViewType::Dashboard => {
+                        state.show_metadata = !state.show_metadata;
+                    }
+                    Action::ThresholdUp if current_view_type == crate::frontend::layout_tree::ViewType::RawScatter => state.adjust_iq_threshold(true),
+                    Action::ThresholdDown if current_view_type == crate::frontend::layout_tree::ViewType::RawScatter => state.adjust_iq_threshold(false),
+                    Action::CycleProjection if default_projection.is_some() => {
+                        state.cycle_projection(default_projection.unwrap_or(crate::frontend::view_state::Projection::Oblique));
+                    }
+                    Action::SubcarrierZoomIn if is_phase => state.zoom_subcarriers(true, total_subcarriers),
+                    Action::SubcarrierZoomOut if is_phase => state.zoom_subcarriers(false, total_subcarriers),
+                    Action::SubcarrierPanLeft if is_phase => state.pan_subcarriers(-4, total_subcarriers),
+                    Action::SubcarrierPanRight if is_phase => state.pan_subcarriers(4, total_subcarriers),
+                    Action::DepthIncrease if default_depth.is_some() => {
+                        state.adjust_history_depth(true, default_depth.unwrap_or(1), history_len);
+                    }
+                    Action::DepthDecrease if default_depth.is_some() => {
+                        state.adjust_history_depth(false, default_depth.unwrap_or(1), history_len);
+                    }
+                    Action::DepthIncrease if current_view_type.has_persistence() => state.adjust_persistence(true),
+                    Action::DepthDecrease if current_view_type.has_persistence() => state.adjust_persistence(false),
                     _ => return Ok(false),
                 }
+                return Ok(true);
             }
 
             // --- STANDARD NAVIGATION ---
-            if key.modifiers.contains(KeyModifiers::SHIFT) {
-                match key.code {
-                    KeyCode::Left | KeyCode::Right => { app.tiling.split(Direction::Horizontal); return Ok(true); }
-                    KeyCode::Up | KeyCode::Down => { app.tiling.split(Direction::Vertical); return Ok(true); }
-                    KeyCode::Char('r') | KeyCode::Char('R') => {
-                        // Toggle Rerun live streaming
-                        if let Some(ref streamer) = app.rerun_streamer {
-                            if let Ok(mut s) = streamer.lock() {
-                                if s.is_connected() {
-                                    s.disconnect();
-                                } else {
-                                    s.connect("127.0.0.1:9876");
-                                }
-                            }
-                        }
-                        return Ok(true);
-                    }
-                    KeyCode::Char('l') | KeyCode::Char('L') => {
-                        // Toggle Rerun RRD recording
-                        if let Some(ref streamer) = app.rerun_streamer {
-                            if let Ok(mut s) = streamer.lock() {
-                                if s.is_recording() {
-                                    s.stop_record();
-                                } else {
-                                    let timestamp = std::time::SystemTime::now()
-                                        .duration_since(std::time::UNIX_EPOCH)
-                                        .unwrap()
-                                        .as_secs();
-                                    match s.start_record(&format!("logs/csi_{}.rrd", timestamp)) {
-                                        Ok(_) => {}
-                                        Err(_e) => {}
-                                    }
-                                }
-                            }
-                        }
-                        return Ok(true);
+            let focused_id = app.tiling.focused_pane_id;
+            let current_view_type = get_view_type_for_pane(app, focused_id);
+            let current_live_id = app.source_for(focused_id).live_id();
+            let min_id = app.source_for(focused_id).min_id();
+
+            match app.keybindings.action_for(&key, Scope::Standard) {
+                Some(Action::StepBack) if current_view_type.is_temporal() => {
+                    app.get_pane_state_mut(focused_id).step_back(current_live_id, min_id);
+                    return Ok(true);
+                }
+                Some(Action::StepForward) if current_view_type.is_temporal() => {
+                    app.get_pane_state_mut(focused_id).step_forward(current_live_id, min_id);
+                    return Ok(true);
+                }
+                Some(Action::AutoAdvance) if current_view_type.is_temporal() => {
+                    app.get_pane_state_mut(focused_id).cycle_auto_advance(min_id);
+                    return Ok(true);
+                }
+                Some(Action::ToggleAdvanceLoop) if current_view_type.is_temporal() => {
+                    let state = app.get_pane_state_mut(focused_id);
+                    state.advance_loop = !state.advance_loop;
+                    return Ok(true);
+                }
+                Some(Action::ToggleSource) => { toggle_pane_source(app, focused_id); return Ok(true); }
+                Some(Action::Quit) => { app.show_quit_popup = true; return Ok(true); }
+                Some(Action::Help) => { app.show_help = !app.show_help; return Ok(true); }
+                Some(Action::MainMenu) => { app.show_main_menu = !app.show_main_menu; return Ok(true); }
+                Some(Action::NextTheme) => { app.next_theme(); return Ok(true); }
+                Some(Action::Screenshot) => { app.screenshot_requested = true; return Ok(true); }
+                Some(Action::ExportReport) => { export_report(app); return Ok(true); }
+                Some(Action::FocusNext) => { app.tiling.focus_next(); return Ok(true); }
+                Some(Action::ClosePane) => { app.close_focused_pane(); return Ok(true); }
+                Some(Action::Fullscreen) => { app.enter_fullscreen(focused_id, false); return Ok(true); }
+                Some(Action::Maximize) => { app.enter_fullscreen(focused_id, true); return Ok(true); }
+                Some(Action::ResetLive) => { app.get_pane_state_mut(focused_id).reset_live(); return Ok(true); }
+                Some(Action::SplitHorizontal) => { app.split_focused_pane(Direction::Horizontal); return Ok(true); }
+                Some(Action::SplitVertical) => { app.split_focused_pane(Direction::Vertical); return Ok(true); }
+                Some(Action::SplitHorizontalSame) => { app.tiling.split_with(Direction::Horizontal, current_view_type); return Ok(true); }
+                Some(Action::SplitVerticalSame) => { app.tiling.split_with(Direction::Vertical, current_view_type); return Ok(true); }
+                Some(Action::DuplicatePane) => { app.duplicate_focused_pane(); return Ok(true); }
+                Some(Action::ToggleStream) => {
+                    // Toggle Rerun live streaming (reconnects to the last used address)
+                    if app.is_rerun_connected() {
+                        app.disconnect_rerun();
+                    } else {
+                        let addr = app.rerun_addr.clone();
+                        app.connect_rerun(&addr);
                     }
-                    _ => return Ok(false),
+                    return Ok(true);
                 }
-            } else {
-                let focused_id = app.tiling.focused_pane_id;
-                let current_view_type = get_view_type_for_pane(app, focused_id);
-                let current_live_id = app.current_stats.id;
-                let min_id = app.history.first().map(|p| p.id).unwrap_or(0);
+                Some(Action::ToggleRecord) => { app.toggle_rerun_recording(); return Ok(true); }
+                Some(Action::ResetEsp) => { app.request_esp_reset(); return Ok(true); }
+                Some(Action::CaptureReference) => { app.capture_reference(); return Ok(true); }
+                Some(Action::CopyLayout) => { copy_layout(app); return Ok(true); }
+                Some(Action::PasteLayout) => { paste_layout(app); return Ok(true); }
+                Some(Action::RenamePane) => {
+                    app.rename_input_buffer = app.pane_states.get(&focused_id).and_then(|s| s.label.clone()).unwrap_or_default();
+                    app.show_rename_input = true;
+                    return Ok(true);
+                }
+                Some(Action::Inspector) => { app.show_inspector = true; app.inspector_input.clear(); return Ok(true); }
+                Some(Action::ExportPacket) => { export_packet(app, focused_id); return Ok(true); }
+                Some(Action::GainUp) => { app.adjust_amplitude_gain(true); return Ok(true); }
+                Some(Action::GainDown) => { app.adjust_amplitude_gain(false); return Ok(true); }
+                Some(Action::ToggleReference) if current_view_type.uses_reference() => {
+                    let state = app.get_pane_state_mut(focused_id);
+                    state.subtract_reference = !state.subtract_reference;
+                    return Ok(true);
+                }
+                Some(Action::ViewSelector) => {
+                    app.show_view_selector = true;
+                    app.view_selector_index = 0;
+                    app.view_selector_marks.clear();
+                    return Ok(true);
+                }
+                Some(Action::NextView) => { cycle_view(app, current_view_type, true); return Ok(true); }
+                Some(Action::PrevView) => { cycle_view(app, current_view_type, false); return Ok(true); }
+                _ => {}
+            }
 
-                match key.code {
-                    KeyCode::Left if current_view_type.is_temporal() => {
-                        app.get_pane_state_mut(focused_id).step_back(current_live_id, min_id);
-                        return Ok(true);
-                    }
-                    KeyCode::Right if current_view_type.is_temporal() => {
-                        app.get_pane_state_mut(focused_id).step_forward(current_live_id, min_id);
-                        return Ok(true);
-                    }
-                    KeyCode::Char('q') => { app.show_quit_popup = true; return Ok(true); }
-                    KeyCode::Char('h') => { app.show_help = !app.show_help; return Ok(true); }
-                    KeyCode::Char('m') => { app.show_main_menu = !app.show_main_menu; return Ok(true); }
-                    KeyCode::Char('t') => { app.next_theme(); return Ok(true); }
-                    KeyCode::Tab => { app.tiling.focus_next(); return Ok(true); }
-                    KeyCode::Delete => { app.tiling.close_focused_pane(); return Ok(true); }
-                    KeyCode::Char(' ') => { app.fullscreen_pane_id = Some(app.tiling.focused_pane_id); return Ok(true); }
-                    KeyCode::Char('r') => { app.get_pane_state_mut(app.tiling.focused_pane_id).reset_live(); return Ok(true); }
-
-                    KeyCode::Char(c) if c.is_digit(10) => {
-                        let id = if c == '0' { 10 } else { c.to_digit(10).unwrap() as usize };
-                        if app.pane_regions.borrow().iter().any(|(pid, _)| *pid == id) {
-                            app.tiling.focused_pane_id = id;
-                            return Ok(true);
-                        }
-                    }
-                    KeyCode::Enter => {
-                        app.show_view_selector = true;
-                        app.view_selector_index = 0;
-                        return Ok(true);
-                    }
-                    _ => return Ok(false),
+            // Pane focus by number (fixed, not remappable)
+            if let KeyCode::Char(c) = key.code && c.is_ascii_digit() {
+                let id = if c == '0' { 10 } else { c.to_digit(10).unwrap() as usize };
+                if app.pane_regions.borrow().iter().any(|(pid, _)| *pid == id) {
+                    app.tiling.focused_pane_id = id;
+                    return Ok(true);
                 }
             }
+            return Ok(false);
         },
 
         Event::Mouse(mouse) => {
@@ -178,14 +266,125 @@ pub fn handle_event(app: &mut App) -> io::Result<bool> {
                     app.drag_state = None;
                     return Ok(true);
                 },
+                MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                    // Wheel targets the fullscreen pane, else the pane under the cursor (which also takes focus)
+                    let target = app.fullscreen_pane_id.or_else(|| {
+                        app.pane_regions.borrow().iter()
+                            .find(|(_, rect)| rect.contains(ratatui::layout::Position { x: mouse.column, y: mouse.row }))
+                            .map(|(id, _)| *id)
+                    });
+
+                    if let Some(id) = target {
+                        app.tiling.focused_pane_id = id;
+                        if get_view_type_for_pane(app, id).is_spatial() {
+                            app.get_pane_state_mut(id).zoom_camera(mouse.kind == MouseEventKind::ScrollUp);
+                        }
+                        return Ok(true);
+                    }
+                },
                 _ => {}
             }
         },
-        _ => {} // Handle FocusGained, FocusLost, Paste, Resize, etc.
+        // Every pane has to be laid out again
+        Event::Resize(_, _) => return Ok(true),
+        _ => {} // Handle FocusGained, FocusLost, Paste, etc.
     }
     Ok(false)
 }
 
+/// Writes reports/report_<unix secs>.md and reports the outcome in the footer
+fn export_report(app: &mut App) {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let path = format!("reports/report_{}.md", timestamp);
+
+    let result = std::fs::create_dir_all("reports").and_then(|_| app.export_report(&path));
+    match result {
+        Ok(_) => app.notify(format!("Saved {}", path)),
+        Err(e) => app.notify(format!("Report failed: {}", e)),
+    }
+}
+
+/// Switches the focused pane to the next/previous entry of AVAILABLE_VIEWS (wrapping),
+/// skipping views that can't be shown in this build
+fn cycle_view(app: &mut App, current: crate::frontend::layout_tree::ViewType, forward: bool) {
+    let views: Vec<_> = AVAILABLE_VIEWS.iter().map(|(v, _)| *v).filter(|v| v.is_available()).collect();
+    let len = views.len();
+    // A view outside the list (e.g. Empty) starts from the first/last entry
+    let next = match views.iter().position(|v| *v == current) {
+        Some(i) if forward => (i + 1) % len,
+        Some(i) => (i + len - 1) % len,
+        None if forward => 0,
+        None => len - 1,
+    };
+    app.tiling.set_current_view(views[next]);
+}
+
+/// Switches a pane between the primary and the overlay source (replay anchors don't carry over)
+fn toggle_pane_source(app: &mut App, id: usize) {
+    if app.overlay.is_none() {
+        app.notify("No second source: start with --overlay <reference.csv>");
+        return;
+    }
+    let state = app.get_pane_state_mut(id);
+    state.overlay_source = !state.overlay_source;
+    state.reset_live();
+}
+
+/// Writes the packet shown in pane `id` (anchored or live) to exports/packet_<id>_<unix secs>.csv
+fn export_packet(app: &mut App, id: usize) {
+    let Some(packet) = app.displayed_packet(id) else {
+        app.notify("Packet export: anchored packet has expired");
+        return;
+    };
+    let Some(csi) = packet.csi.as_ref() else {
+        app.notify("Packet export: no CSI data yet");
+        return;
+    };
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let path = format!("exports/packet_{}_{}.csv", packet.id, timestamp);
+
+    let result = std::fs::create_dir_all("exports")
+        .map_err(|e| e.into())
+        .and_then(|_| crate::dataloader::export_packet_csv(csi, &path));
+    match result {
+        Ok(_) => app.notify(format!("Saved {}", path)),
+        Err(e) => app.notify(format!("Packet export failed: {}", e)),
+    }
+}
+
+/// Writes the Doppler spectrogram (fed alongside the Rerun stream) to exports/doppler_<unix secs>.csv,
+/// plus a .png with the "screenshot" feature
+fn export_doppler(app: &mut App) {
+    let Some(streamer) = app.rerun_streamer.clone() else {
+        app.notify("Doppler export: no spectrogram in this build");
+        return;
+    };
+    let Ok(streamer) = streamer.lock() else { return };
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let path = format!("exports/doppler_{}.csv", timestamp);
+
+    let result = std::fs::create_dir_all("exports")
+        .map_err(|e| e.into())
+        .and_then(|_| streamer.doppler().to_csv(&path));
+    #[cfg(feature = "screenshot")]
+    let result = result.and_then(|_| streamer.doppler().to_png(&path.replace(".csv", ".png")));
+    match result {
+        Ok(_) => app.notify(format!("Saved {}", path)),
+        Err(e) => app.notify(format!("Doppler export failed: {}", e)),
+    }
+}
+
 fn get_view_type_for_pane(app: &App, id: usize) -> crate::frontend::layout_tree::ViewType {
     find_view_type_recursive(&app.tiling.root, id).unwrap_or(crate::frontend::layout_tree::ViewType::Empty)
 }
@@ -208,21 +407,93 @@ fn find_view_type_recursive(node: &crate::frontend::layout_tree::LayoutNode, tar
 
 // Handles all popup overlays
 fn handle_popups(app: &mut App, key: crossterm::event::KeyEvent) -> io::Result<bool> {
+    // First-run guidance: any key dismisses it
+    if app.onboarding_visible() {
+        app.show_onboarding = false;
+        return Ok(true);
+    }
+
+    // 0. OVERWRITE CONFIRMATION (sits on top of the save input)
+    if app.show_overwrite_confirm {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter | KeyCode::Char(' ') => {
+                app.show_overwrite_confirm = false;
+                save_current_template(app);
+            }
+            KeyCode::Char('n') | KeyCode::Char('q') | KeyCode::Esc => app.show_overwrite_confirm = false,
+            _ => {}
+        }
+        return Ok(true);
+    }
+
+    // 0.5 DELETE CONFIRMATION (sits on top of the load selector)
+    if let Some(filename) = app.confirm_delete_template.clone() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                app.confirm_delete_template = None;
+                match config_manager::delete_template(&filename) {
+                    Ok(_) => app.notify(format!("Deleted template {}", filename)),
+                    Err(e) => app.notify(format!("Delete failed: {}", e)),
+                }
+                if let Ok(list) = config_manager::list_templates() { app.available_templates = list; }
+                let visible = app.filtered_templates().len();
+                app.load_selector_index = app.load_selector_index.min(visible.saturating_sub(1));
+            }
+            KeyCode::Char('n') | KeyCode::Char('q') | KeyCode::Esc => app.confirm_delete_template = None,
+            _ => {}
+        }
+        return Ok(true);
+    }
+
     // 1. SAVE INPUT
     if app.show_save_input {
         match key.code {
-            KeyCode::Enter => {
-                if !app.input_buffer.is_empty() {
-                    app.tiling.theme_variant = Some(app.theme.variant);
-                    app.tiling.is_default = false;
-                    let _ = config_manager::save_template(&app.input_buffer, &app.tiling);
-                    app.show_save_input = false;
-                    app.input_buffer.clear();
+            KeyCode::Enter if !app.input_buffer.is_empty() => {
+                if config_manager::sanitize_template_name(&app.input_buffer).is_empty() {
+                    app.save_input_error = Some("Invalid name (no usable characters)".to_string());
+                } else if config_manager::template_exists(&app.input_buffer) {
+                    app.show_overwrite_confirm = true;
+                } else {
+                    save_current_template(app);
                 }
             }
-            KeyCode::Esc => { app.show_save_input = false; app.input_buffer.clear(); }
-            KeyCode::Backspace => { app.input_buffer.pop(); }
-            KeyCode::Char(c) => { app.input_buffer.push(c); }
+            KeyCode::Esc => { app.show_save_input = false; app.input_buffer.clear(); app.save_input_error = None; }
+            KeyCode::Backspace => { app.input_buffer.pop(); app.save_input_error = None; }
+            KeyCode::Char(c) => { app.input_buffer.push(c); app.save_input_error = None; }
+            _ => {}
+        }
+        return Ok(true);
+    }
+
+    // 1.25 RERUN ADDRESS INPUT
+    if app.show_rerun_input {
+        match key.code {
+            KeyCode::Enter => {
+                let addr = app.rerun_input_buffer.clone();
+                app.show_rerun_input = false;
+                app.rerun_input_buffer.clear();
+                app.connect_rerun(&addr);
+            }
+            KeyCode::Esc => { app.show_rerun_input = false; app.rerun_input_buffer.clear(); }
+            KeyCode::Backspace => { app.rerun_input_buffer.pop(); }
+            KeyCode::Char(c) => { app.rerun_input_buffer.push(c); }
+            _ => {}
+        }
+        return Ok(true);
+    }
+
+    // 1.3 PANE LABEL INPUT (applies to the focused pane)
+    if app.show_rename_input {
+        match key.code {
+            KeyCode::Enter => {
+                let label = std::mem::take(&mut app.rename_input_buffer);
+                app.show_rename_input = false;
+                let id = app.tiling.focused_pane_id;
+                app.set_pane_label(id, &label);
+            }
+            KeyCode::Esc => { app.show_rename_input = false; app.rename_input_buffer.clear(); }
+            KeyCode::Backspace => { app.rename_input_buffer.pop(); }
+            KeyCode::Char(c) => { app.rename_input_buffer.push(c); }
             _ => {}
         }
         return Ok(true);
@@ -231,23 +502,32 @@ fn handle_popups(app: &mut App, key: crossterm::event::KeyEvent) -> io::Result<b
     // 1.5 EXPORT INPUT
     if app.show_export_input {
         match key.code {
-            KeyCode::Enter => {
-                if !app.export_input_buffer.is_empty() {
-                    let timestamp = std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs();
+            KeyCode::Enter if !app.export_input_buffer.is_empty() => {
+                let timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
 
-                    // Export CSV
+                // Export CSV (raw rows replay with the CSV importer; tidy rows are for analysis tools)
+                let (filename, result) = if app.export_tidy {
+                    let filename = format!("{}_{}_tidy.csv", app.export_input_buffer, timestamp);
+                    let result = app.dataloader.export_tidy_csv(&filename);
+                    (filename, result)
+                } else {
                     let filename = format!("{}_{}.csv", app.export_input_buffer, timestamp);
-                    // Use Dataloader's raw history for CSV export
-                    let _ = app.dataloader.export_history_to_csv(&filename);
-
-                    app.show_export_input = false;
-                    app.export_input_buffer.clear();
+                    let result = app.dataloader.export_history_to_csv(&filename);
+                    (filename, result)
+                };
+                match result {
+                    Ok(_) => app.notify(format!("Exported {}", filename)),
+                    Err(e) => app.notify(format!("Export failed: {}", e)),
                 }
+
+                app.show_export_input = false;
+                app.export_input_buffer.clear();
             }
             KeyCode::Esc => { app.show_export_input = false; app.export_input_buffer.clear(); }
+            KeyCode::Tab => { app.export_tidy = !app.export_tidy; }
             KeyCode::Backspace => { app.export_input_buffer.pop(); }
             KeyCode::Char(c) => { app.export_input_buffer.push(c); }
             _ => {}
@@ -275,37 +555,91 @@ fn handle_popups(app: &mut App, key: crossterm::event::KeyEvent) -> io::Result<b
         return Ok(true);
     }
 
-    // 3. LOAD SELECTOR
+    // 2.5 GAUGE RANGES
+    if app.show_gauge_settings {
+        match key.code {
+            KeyCode::Up => {
+                if app.gauge_settings_index > 0 { app.gauge_settings_index -= 1; }
+                else { app.gauge_settings_index = GAUGE_SETTINGS.len() - 1; }
+            }
+            KeyCode::Down => {
+                app.gauge_settings_index = (app.gauge_settings_index + 1) % GAUGE_SETTINGS.len();
+            }
+            KeyCode::Left => app.gauge_ranges.adjust(app.gauge_settings_index, false),
+            KeyCode::Right => app.gauge_ranges.adjust(app.gauge_settings_index, true),
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => app.show_gauge_settings = false,
+            _ => {}
+        }
+        return Ok(true);
+    }
+
+    // 2.75 PACKET DIFF (read-only)
+    if app.show_packet_diff {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('c')) {
+            app.show_packet_diff = false;
+        }
+        return Ok(true);
+    }
+
+    // 2.8 SUBCARRIER INSPECTOR (digits + Enter jump to an index, Left/Right step)
+    if app.show_inspector {
+        let count = app.current_stats.csi.as_ref().map_or(0, |c| c.csi_raw_data.len() / 2);
+        match key.code {
+            KeyCode::Left => { app.inspector_index = app.inspector_index.saturating_sub(1); }
+            KeyCode::Right if app.inspector_index + 1 < count => { app.inspector_index += 1; }
+            KeyCode::Char(c) if c.is_ascii_digit() && app.inspector_input.len() < 4 => { app.inspector_input.push(c); }
+            KeyCode::Backspace => { app.inspector_input.pop(); }
+            KeyCode::Enter => {
+                if let Ok(index) = std::mem::take(&mut app.inspector_input).parse() {
+                    app.inspector_index = index;
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('i') => { app.show_inspector = false; app.inspector_input.clear(); }
+            _ => {}
+        }
+        return Ok(true);
+    }
+
+    // 3. LOAD SELECTOR (typing narrows the list)
     if app.show_load_selector {
+        let visible = app.filtered_templates();
         match key.code {
-            KeyCode::Up | KeyCode::Down | KeyCode::Char('d') | KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char(' ') => {
-                if key.code == KeyCode::Esc || key.code == KeyCode::Char('q') { app.show_load_selector = false; }
-                else {
-                    if key.code == KeyCode::Up {
-                        if app.load_selector_index > 0 { app.load_selector_index -= 1; }
-                        else if !app.available_templates.is_empty() { app.load_selector_index = app.available_templates.len() - 1; }
-                    }
-                    if key.code == KeyCode::Down {
-                        if !app.available_templates.is_empty() { app.load_selector_index = (app.load_selector_index + 1) % app.available_templates.len(); }
-                    }
-                    if (key.code == KeyCode::Enter || key.code == KeyCode::Char(' ')) && !app.available_templates.is_empty() {
-                        let (filename, _) = &app.available_templates[app.load_selector_index];
-                        if let Ok(new_tiling) = config_manager::load_template(filename) {
-                            if let Some(variant) = new_tiling.theme_variant { app.theme = crate::theme::Theme::new(variant); }
-                            app.tiling = new_tiling;
+            KeyCode::Esc => close_load_selector(app),
+            KeyCode::Up if !visible.is_empty() => {
+                app.load_selector_index = (app.load_selector_index + visible.len() - 1) % visible.len();
+            }
+            KeyCode::Down if !visible.is_empty() => {
+                app.load_selector_index = (app.load_selector_index + 1) % visible.len();
+            }
+            KeyCode::Enter => {
+                if let Some(&index) = visible.get(app.load_selector_index) {
+                    let filename = app.available_templates[index].0.clone();
+                    match config_manager::load_template(&filename) {
+                        Ok(new_tiling) => {
+                            app.apply_layout(new_tiling);
+                            app.notify(format!("Loaded template {}", filename));
                         }
-                        app.show_load_selector = false;
-                    }
-                    if key.code == KeyCode::Char('d') && !app.available_templates.is_empty() {
-                         let (filename, _) = &app.available_templates[app.load_selector_index];
-                         let _ = config_manager::set_default_template(filename);
-                         if let Ok(list) = config_manager::list_templates() { app.available_templates = list; }
+                        Err(e) => app.notify(format!("Load failed: {}", e)),
                     }
+                    close_load_selector(app);
                 }
-                return Ok(true);
             }
-            _ => return Ok(false),
+            KeyCode::Tab => {
+                if let Some(&index) = visible.get(app.load_selector_index) {
+                    let _ = config_manager::set_default_template(&app.available_templates[index].0);
+                    if let Ok(list) = config_manager::list_templates() { app.available_templates = list; }
+                }
+            }
+            KeyCode::Delete => {
+                if let Some(&index) = visible.get(app.load_selector_index) {
+                    app.confirm_delete_template = Some(app.available_templates[index].0.clone());
+                }
+            }
+            KeyCode::Backspace => { app.template_filter.pop(); app.load_selector_index = 0; }
+            KeyCode::Char(c) => { app.template_filter.push(c); app.load_selector_index = 0; }
+            _ => {}
         }
+        return Ok(true);
     }
 
     // 4. QUIT POPUP
@@ -319,13 +653,31 @@ fn handle_popups(app: &mut App, key: crossterm::event::KeyEvent) -> io::Result<b
     }
 
     // 5. MENUS
+    // View selector: digits mark/unmark the panes the chosen view goes to
+    if app.show_view_selector && let KeyCode::Char(c) = key.code && c.is_ascii_digit() {
+        let id = if c == '0' { 10 } else { c.to_digit(10).unwrap() as usize };
+        if app.tiling.panes().iter().any(|(pid, _)| *pid == id) {
+            if let Some(pos) = app.view_selector_marks.iter().position(|&m| m == id) {
+                app.view_selector_marks.remove(pos);
+            } else {
+                app.view_selector_marks.push(id);
+            }
+        }
+        return Ok(true);
+    }
+
     if app.show_view_selector || app.show_main_menu {
         match key.code {
             KeyCode::Up | KeyCode::Down | KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('m') | KeyCode::Char(' ') => {
                 if app.show_view_selector {
                     if key.code == KeyCode::Enter || key.code == KeyCode::Char(' ') {
                         let (selected_view, _) = AVAILABLE_VIEWS[app.view_selector_index];
-                        app.tiling.set_current_view(selected_view);
+                        if app.view_selector_marks.is_empty() {
+                            app.tiling.set_current_view(selected_view);
+                        } else {
+                            let marks = std::mem::take(&mut app.view_selector_marks);
+                            app.tiling.set_view_for(&marks, selected_view);
+                        }
                         app.show_view_selector = false;
                     } else if key.code == KeyCode::Up {
                          if app.view_selector_index > 0 { app.view_selector_index -= 1; } else { app.view_selector_index = AVAILABLE_VIEWS.len() - 1; }
@@ -339,10 +691,33 @@ fn handle_popups(app: &mut App, key: crossterm::event::KeyEvent) -> io::Result<b
                         match app.main_menu_index {
                             0 => { app.show_main_menu = false; app.show_theme_selector = true; app.theme_selector_index = 0; },
                             1 => { app.show_main_menu = false; app.show_save_input = true; app.input_buffer.clear(); },
-                            2 => { app.show_main_menu = false; if let Ok(list) = config_manager::list_templates() { app.available_templates = list; } app.load_selector_index = 0; app.show_load_selector = true; },
+                            2 => { app.show_main_menu = false; if let Ok(list) = config_manager::list_templates() { app.available_templates = list; } app.load_selector_index = 0; app.template_filter.clear(); app.show_load_selector = true; },
                             3 => { app.show_main_menu = false; app.show_export_input = true; app.export_input_buffer.clear(); },
-                            4 => { app.show_main_menu = false; app.should_reset_esp = true; },
-                            5 => app.show_main_menu = false,
+                            4 => { app.show_main_menu = false; export_doppler(app); },
+                            5 => { app.averaging_mode = app.averaging_mode.next(); },
+                            6 => app.cycle_outlier_sigma(),
+                            7 => { app.show_main_menu = false; app.show_gauge_settings = true; app.gauge_settings_index = 0; },
+                            8 => {
+                                app.show_main_menu = false;
+                                if app.is_rerun_connected() {
+                                    app.disconnect_rerun();
+                                } else {
+                                    app.rerun_input_buffer = app.rerun_addr.clone();
+                                    app.show_rerun_input = true;
+                                }
+                            },
+                            9 => { app.show_main_menu = false; app.screenshot_requested = true; },
+                            10 => { app.canvas_marker = app.canvas_marker.next(); },
+                            11 => app.stats_format.next_unit(),
+                            12 => app.stats_format.next_decimals(),
+                            13 => app.phase_colormap = app.phase_colormap.next(),
+                            14 => app.amplitude_clamp = app.amplitude_clamp.next(),
+                            15 => app.flatten_subcarriers = !app.flatten_subcarriers,
+                            16 => app.pps_smoothing = app.pps_smoothing.next(),
+                            17 => app.header_metric = app.header_metric.next(),
+                            18 => { app.show_main_menu = false; app.capture_reference(); },
+                            19 => { app.show_main_menu = false; app.request_esp_reset(); },
+                            20 => app.show_main_menu = false,
                             _ => {}
                         }
                     } else if key.code == KeyCode::Up {
@@ -359,4 +734,53 @@ fn handle_popups(app: &mut App, key: crossterm::event::KeyEvent) -> io::Result<b
         }
     }
     Ok(false)
-}
\ No newline at end of file
+}
+
+fn close_load_selector(app: &mut App) {
+    app.show_load_selector = false;
+    app.template_filter.clear();
+    app.load_selector_index = 0;
+}
+
+/// Copies the layout as template JSON (clipboard, or a temp file without one) for sharing
+fn copy_layout(app: &mut App) {
+    let json = match serde_json::to_string_pretty(&app.layout_for_export()) {
+        Ok(json) => json,
+        Err(e) => { app.notify(format!("Copy failed: {}", e)); return; }
+    };
+    match clipboard::copy(&json) {
+        Ok(clipboard::CopyTarget::Clipboard) => app.notify("Layout copied to clipboard"),
+        Ok(clipboard::CopyTarget::File(path)) => app.notify(format!("No clipboard, layout written to {}", path.display())),
+        Err(e) => app.notify(format!("Copy failed: {}", e)),
+    }
+}
+
+/// Loads a layout from template JSON on the clipboard (or the copy_layout fallback file)
+fn paste_layout(app: &mut App) {
+    let text = match clipboard::paste() {
+        Ok(text) => text,
+        Err(e) => { app.notify(format!("Paste failed: {}", e)); return; }
+    };
+    match serde_json::from_str::<crate::layout_tree::TilingManager>(text.trim()) {
+        Ok(tiling) => {
+            app.apply_layout(tiling);
+            app.fullscreen_pane_id = None;
+            app.notify("Layout pasted");
+        }
+        Err(e) => app.notify(format!("Paste failed: not a layout ({})", e)),
+    }
+}
+
+/// Writes the current layout/theme/pane states under the name in `input_buffer` and closes the prompt
+fn save_current_template(app: &mut App) {
+    app.tiling = app.layout_for_export();
+    match config_manager::save_template(&app.input_buffer, &app.tiling) {
+        Ok(_) => {
+            app.notify(format!("Saved template {}", config_manager::sanitize_template_name(&app.input_buffer)));
+            app.show_save_input = false;
+            app.input_buffer.clear();
+            app.save_input_error = None;
+        }
+        Err(e) => app.save_input_error = Some(format!("Save failed: {}", e)),
+    }
+}
@@ -0,0 +1,372 @@
+// --- File: src/keybindings.rs ---
+// --- Purpose: Loads user key remapping from keybindings.json and resolves key events to actions ---
+//
+// The file is a JSON list of { "action": <Action>, "keys": ["q", "Shift+Left", ...] } entries.
+// Order matters: if two actions in the same scope claim the same key, the first one keeps it.
+// Actions missing from the file fall back to their default keys.
+//
+// Not remappable (fixed in input_handler.rs): popup/menu navigation, replay controls,
+// digit keys for focusing panes, and the mouse.
+
+use std::fs;
+use std::path::Path;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Serialize, Deserialize};
+
+// Read from the working directory, next to templates/
+const KEYBINDINGS_FILE: &str = "keybindings.json";
+
+// Shown by KeyBindings::keys_for for an action without keys
+pub const UNBOUND: &str = "(unbound)";
+
+/// Where an action is available. Keys only conflict within the same scope.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Scope {
+    Standard,   // Tiled layout
+    Fullscreen, // A single pane maximized
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Action {
+    // Standard
+    Help,
+    MainMenu,
+    NextTheme,
+    Screenshot,
+    ExportReport,
+    FocusNext,
+    ClosePane,
+    Fullscreen,
+    Maximize,
+    ViewSelector,
+    NextView,
+    PrevView,
+    SplitHorizontal,
+    SplitVertical,
+    SplitHorizontalSame,
+    SplitVerticalSame,
+    DuplicatePane,
+    ToggleStream,
+    ToggleRecord,
+    ResetEsp,
+    CaptureReference,
+    RenamePane,
+    CopyLayout,
+    PasteLayout,
+    // Both
+    Quit,
+    ResetLive,
+    StepBack,
+    StepForward,
+    ToggleReference,
+    GainUp,
+    GainDown,
+    Inspector,
+    ExportPacket,
+    AutoAdvance,
+    ToggleAdvanceLoop,
+    // Fullscreen
+    ExitFullscreen,
+    CameraUp,
+    CameraDown,
+    CameraLeft,
+    CameraRight,
+    CycleSmoothing,
+    DepthIncrease,
+    DepthDecrease,
+    SubcarrierZoomIn,
+    SubcarrierZoomOut,
+    SubcarrierPanLeft,
+    SubcarrierPanRight,
+    ComparePackets,
+    TogglePhaseUnwrap,
+    ToggleAmplitudeColor,
+    CycleProjection,
+    ToggleDcNull,
+    ToggleCoarseHistory,
+    ToggleMetadata,
+    ToggleSource,
+    ToggleGrid,
+    ThresholdUp,
+    ThresholdDown,
+}
+
+impl Action {
+    pub fn in_scope(&self, scope: Scope) -> bool {
+        match self {
+            Action::Quit | Action::ResetLive | Action::StepBack | Action::StepForward | Action::ToggleReference |
+            Action::GainUp | Action::GainDown | Action::Inspector | Action::ExportPacket | Action::AutoAdvance | Action::ToggleAdvanceLoop | Action::ToggleSource => true,
+            Action::Help | Action::MainMenu | Action::NextTheme | Action::Screenshot | Action::ExportReport |
+            Action::FocusNext | Action::ClosePane | Action::Fullscreen | Action::Maximize | Action::ViewSelector | Action::NextView | Action::PrevView |
+            Action::SplitHorizontal | Action::SplitVertical | Action::SplitHorizontalSame | Action::SplitVerticalSame | Action::DuplicatePane | Action::ToggleStream | Action::ToggleRecord |
+            Action::ResetEsp | Action::CaptureReference | Action::RenamePane | Action::CopyLayout | Action::PasteLayout => scope == Scope::Standard,
+            _ => scope == Scope::Fullscreen,
+        }
+    }
+}
+
+/// The built-in bindings, in priority order. Also written out as the default keybindings.json.
+pub fn default_bindings() -> Vec<(Action, Vec<&'static str>)> {
+    vec![
+        (Action::Quit, vec!["q"]),
+        (Action::Help, vec!["h"]),
+        (Action::MainMenu, vec!["m"]),
+        (Action::NextTheme, vec!["t"]),
+        (Action::Screenshot, vec!["p"]),
+        (Action::ExportReport, vec!["e"]),
+        (Action::FocusNext, vec!["Tab"]),
+        (Action::ClosePane, vec!["Delete"]),
+        (Action::Fullscreen, vec!["Space"]),
+        (Action::Maximize, vec!["Shift+M"]),
+        (Action::ViewSelector, vec!["Enter"]),
+        (Action::NextView, vec!["]"]),
+        (Action::PrevView, vec!["["]),
+        (Action::ResetLive, vec!["r"]),
+        (Action::SplitHorizontal, vec!["Shift+Left", "Shift+Right"]),
+        (Action::SplitVertical, vec!["Shift+Up", "Shift+Down"]),
+        (Action::SplitHorizontalSame, vec!["Alt+Left", "Alt+Right"]),
+        (Action::SplitVerticalSame, vec!["Alt+Up", "Alt+Down"]),
+        (Action::DuplicatePane, vec!["Ctrl+d"]),
+        (Action::ToggleStream, vec!["Shift+R"]),
+        (Action::ToggleRecord, vec!["Shift+L"]),
+        (Action::ResetEsp, vec!["Shift+E"]),
+        (Action::CaptureReference, vec!["Shift+B"]),
+        (Action::RenamePane, vec!["n"]),
+        (Action::CopyLayout, vec!["Shift+C"]),
+        (Action::PasteLayout, vec!["Shift+V"]),
+        (Action::ToggleReference, vec!["b"]),
+        (Action::GainUp, vec!["g"]),
+        (Action::GainDown, vec!["Shift+G"]),
+        (Action::Inspector, vec!["i"]),
+        (Action::StepBack, vec!["Left"]),
+        (Action::StepForward, vec!["Right"]),
+        (Action::ExportPacket, vec!["y"]),
+        (Action::AutoAdvance, vec!["j"]),
+        (Action::ToggleAdvanceLoop, vec!["Shift+J"]),
+        (Action::ToggleSource, vec!["Shift+S"]),
+        (Action::ExitFullscreen, vec!["Space", "Esc"]),
+        (Action::CameraUp, vec!["w", "Up"]),
+        (Action::CameraDown, vec!["s", "Down"]),
+        (Action::CameraLeft, vec!["a"]),
+        (Action::CameraRight, vec!["d"]),
+        (Action::CycleSmoothing, vec!["f"]),
+        (Action::DepthIncrease, vec!["+", "="]),
+        (Action::DepthDecrease, vec!["-"]),
+        (Action::SubcarrierZoomIn, vec!["z"]),
+        (Action::SubcarrierZoomOut, vec!["x"]),
+        (Action::SubcarrierPanLeft, vec!["<"]),
+        (Action::SubcarrierPanRight, vec![">"]),
+        (Action::ComparePackets, vec!["c"]),
+        (Action::TogglePhaseUnwrap, vec!["u"]),
+        (Action::ToggleAmplitudeColor, vec!["v"]),
+        (Action::CycleProjection, vec!["k"]),
+        (Action::ToggleDcNull, vec!["l"]),
+        (Action::ToggleCoarseHistory, vec!["Shift+T"]),
+        (Action::ToggleMetadata, vec!["Shift+D"]),
+        (Action::ToggleGrid, vec!["Shift+A"]),
+        (Action::ThresholdUp, vec!["Shift+Up"]),
+        (Action::ThresholdDown, vec!["Shift+Down"]),
+    ]
+}
+
+/// A key plus modifiers, normalized so "Shift+r", "R" and a shifted 'r' event compare equal
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shift is already part of a character ('R', '+'), so it's dropped for Char keys
+        match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                Self { code: KeyCode::Char(c.to_ascii_uppercase()), modifiers: modifiers - KeyModifiers::SHIFT }
+            }
+            KeyCode::BackTab => Self { code: KeyCode::Tab, modifiers: modifiers | KeyModifiers::SHIFT },
+            _ => Self { code, modifiers },
+        }
+    }
+
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+
+    /// Parses "q", "Shift+Left", "Ctrl+s", "Space", "F5", "+" ...
+    pub fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+        let (mods_part, key_part) = match spec.rfind('+') {
+            // A trailing '+' is the key itself ("+", "Shift++")
+            Some(i) if i == spec.len() - 1 => (&spec[..i.saturating_sub(1)], "+"),
+            Some(i) => (&spec[..i], &spec[i + 1..]),
+            None => ("", spec),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for m in mods_part.split('+').map(str::trim).filter(|m| !m.is_empty()) {
+            modifiers |= match m.to_ascii_lowercase().as_str() {
+                "shift" => KeyModifiers::SHIFT,
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                _ => return None,
+            };
+        }
+
+        let code = match key_part.to_ascii_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "enter" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "delete" | "del" => KeyCode::Delete,
+            "backspace" => KeyCode::Backspace,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            f if f.len() > 1 && f.starts_with('f') => KeyCode::F(f[1..].parse().ok()?),
+            _ => {
+                let mut chars = key_part.chars();
+                let c = chars.next()?;
+                if chars.next().is_some() { return None; }
+                KeyCode::Char(c)
+            }
+        };
+
+        Some(Self::new(code, modifiers))
+    }
+}
+
+impl std::fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) { write!(f, "Ctrl+")?; }
+        if self.modifiers.contains(KeyModifiers::ALT) { write!(f, "Alt+")?; }
+        if self.modifiers.contains(KeyModifiers::SHIFT) { write!(f, "Shift+")?; }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) if c.is_ascii_uppercase() => write!(f, "Shift+{}", c),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+// On-disk entry
+#[derive(Serialize, Deserialize)]
+struct BindingEntry {
+    action: Action,
+    keys: Vec<String>,
+}
+
+pub struct KeyBindings {
+    bindings: Vec<(Action, KeyBinding)>,
+    /// Problems found while loading (bad key names, conflicts). Shown once at startup.
+    pub warnings: Vec<String>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let entries = default_bindings()
+            .into_iter()
+            .map(|(action, keys)| (action, keys.into_iter().map(str::to_string).collect()))
+            .collect();
+        Self::from_entries(entries)
+    }
+}
+
+impl KeyBindings {
+    /// Builds the table in order, dropping bad key names and keys already taken in an overlapping scope
+    pub fn from_entries(entries: Vec<(Action, Vec<String>)>) -> Self {
+        let mut result = Self { bindings: Vec::new(), warnings: Vec::new() };
+
+        for (action, keys) in entries {
+            for spec in keys {
+                let Some(binding) = KeyBinding::parse(&spec) else {
+                    result.warnings.push(format!("{:?}: unknown key \"{}\"", action, spec));
+                    continue;
+                };
+
+                let taken = result.bindings.iter().find(|(other, b)| {
+                    *b == binding && [Scope::Standard, Scope::Fullscreen].iter().any(|s| other.in_scope(*s) && action.in_scope(*s))
+                });
+                if let Some((other, _)) = taken {
+                    if *other != action {
+                        result.warnings.push(format!("{:?}: \"{}\" already bound to {:?}, ignored", action, spec, other));
+                    }
+                    continue;
+                }
+
+                result.bindings.push((action, binding));
+            }
+        }
+
+        result
+    }
+
+    /// The action bound to this key in `scope`, if any
+    pub fn action_for(&self, key: &KeyEvent, scope: Scope) -> Option<Action> {
+        let pressed = KeyBinding::from_event(key);
+        self.bindings.iter()
+            .find(|(action, binding)| *binding == pressed && action.in_scope(scope))
+            .map(|(action, _)| *action)
+    }
+
+    /// Human-readable keys for an action, e.g. "w / Up"
+    pub fn keys_for(&self, action: Action) -> String {
+        let keys: Vec<String> = self.bindings.iter()
+            .filter(|(a, _)| *a == action)
+            .map(|(_, b)| b.to_string())
+            .collect();
+        if keys.is_empty() { UNBOUND.to_string() } else { keys.join(" / ") }
+    }
+
+    /// The first key bound to an action, for the compact footer hints (None if unbound)
+    pub fn first_key_for(&self, action: Action) -> Option<String> {
+        self.bindings.iter().find(|(a, _)| *a == action).map(|(_, b)| b.to_string())
+    }
+}
+
+/// Writes the default keybindings.json if there isn't one yet
+pub fn init() -> std::io::Result<()> {
+    if Path::new(KEYBINDINGS_FILE).exists() {
+        return Ok(());
+    }
+    let entries: Vec<BindingEntry> = default_bindings()
+        .into_iter()
+        .map(|(action, keys)| BindingEntry { action, keys: keys.into_iter().map(str::to_string).collect() })
+        .collect();
+    let json = serde_json::to_string_pretty(&entries)?;
+    fs::write(KEYBINDINGS_FILE, json)
+}
+
+/// Loads keybindings.json. Falls back to the defaults (with a warning) if it can't be read.
+pub fn load() -> KeyBindings {
+    let parsed = fs::read_to_string(KEYBINDINGS_FILE)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str::<Vec<BindingEntry>>(&json).map_err(|e| e.to_string()));
+
+    match parsed {
+        Ok(entries) => {
+            let mut all: Vec<(Action, Vec<String>)> = entries.into_iter().map(|e| (e.action, e.keys)).collect();
+            // Anything the file doesn't mention keeps its default keys (after the user's own)
+            for (action, keys) in default_bindings() {
+                if !all.iter().any(|(a, _)| *a == action) {
+                    all.push((action, keys.into_iter().map(str::to_string).collect()));
+                }
+            }
+            KeyBindings::from_entries(all)
+        }
+        Err(e) => {
+            let mut defaults = KeyBindings::default();
+            if Path::new(KEYBINDINGS_FILE).exists() {
+                defaults.warnings.push(format!("{}: {} (using defaults)", KEYBINDINGS_FILE, e));
+            }
+            defaults
+        }
+    }
+}
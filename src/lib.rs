@@ -0,0 +1,27 @@
+// --- File: src/lib.rs ---
+// --- Purpose: Library root. Declares the module tree shared by the binary and examples. ---
+
+// 1. Declare modules
+pub mod app;
+pub mod input_handler;
+pub mod frontend;
+pub mod backend;
+pub mod config_manager;
+pub mod clipboard;
+pub mod keybindings;
+pub mod esp_com;
+pub mod rerun_stream;
+pub mod logging;
+
+// 2. Re-exports
+pub use app::{App, NetworkStats};
+
+pub use frontend::layout_tree;
+pub use frontend::theme;
+pub use frontend::view_router;
+pub use frontend::view_traits;
+pub use frontend::view_state;
+pub use frontend::screenshot;
+pub use frontend::views::stats;
+pub use frontend::overlays::{help, options, quit, view_selector, main_menu, save_template, load_template, theme_selector};
+pub use backend::dataloader;
@@ -0,0 +1,46 @@
+// --- File: src/logging.rs ---
+// --- Purpose: Optional diagnostic log file (tracing). Off unless --log-dir is given, so the TUI stays clean ---
+//
+// Modules report failures with tracing's warn!/error! macros. Without an installed subscriber those
+// calls are no-ops; `init` installs one that writes a daily-rotated file (feature "file-log").
+// The level filter comes from RUST_LOG (default: DEFAULT_FILTER).
+
+// Our own crate at info, dependencies (Rerun, etc.) only when they warn
+#[cfg(feature = "file-log")]
+const DEFAULT_FILTER: &str = "warn,project=info";
+
+// File name prefix inside the log directory (the date is appended on rotation)
+#[cfg(feature = "file-log")]
+const LOG_FILE_PREFIX: &str = "esp-csi-tui.log";
+
+/// Keeps the background log writer alive; dropping it flushes the file. Hold it until exit.
+#[cfg(feature = "file-log")]
+pub type LogGuard = tracing_appender::non_blocking::WorkerGuard;
+#[cfg(not(feature = "file-log"))]
+pub type LogGuard = ();
+
+/// Starts logging to `<dir>/esp-csi-tui.log.<date>`, creating the directory if needed
+pub fn init(dir: &str) -> Result<LogGuard, String> {
+    #[cfg(feature = "file-log")]
+    {
+        use tracing_subscriber::EnvFilter;
+
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir, e))?;
+        let appender = tracing_appender::rolling::daily(dir, LOG_FILE_PREFIX);
+        let (writer, guard) = tracing_appender::non_blocking(appender);
+        let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+
+        tracing_subscriber::fmt()
+            .with_writer(writer)
+            .with_ansi(false)
+            .with_env_filter(filter)
+            .try_init()
+            .map_err(|e| e.to_string())?;
+        tracing::info!(dir, "logging started");
+        Ok(guard)
+    }
+    #[cfg(not(feature = "file-log"))]
+    {
+        Err(format!("{}: built without the file-log feature", dir))
+    }
+}
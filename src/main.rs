@@ -3,6 +3,7 @@
 
 use std::{io, thread, time::{Duration, Instant}};
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crossterm::{
     execute,
@@ -11,37 +12,98 @@ use crossterm::{
 };
 use ratatui::prelude::*;
 
-// 1. Declare modules
-pub mod app;
-pub mod input_handler;
-pub mod frontend;
-pub mod backend;
-pub mod config_manager;
-pub mod esp_com;
-pub mod rerun_stream;
-
-// 2. Re-exports
-pub use app::{App, NetworkStats};
-
-pub use frontend::layout_tree;
-pub use frontend::theme;
-pub use frontend::view_router;
-pub use frontend::view_traits;
-pub use frontend::view_state;
-pub use frontend::views::stats;
-pub use frontend::overlays::{help, options, quit, view_selector, main_menu, save_template, load_template, theme_selector};
-pub use backend::dataloader;
+use project::app::{SourceStream, UPDATE_INTERVAL};
+use project::{App, config_manager, keybindings, dataloader, esp_com, input_handler, logging, screenshot, view_router};
+use project::layout_tree::{self, TilingManager, ViewType};
+use project::esp_com::DataSource;
+
+// Longest time one frame spends handling queued input before drawing again
+const INPUT_BUDGET: Duration = Duration::from_millis(20);
+
+// Redraw cap (--fps), and the slowest the screen is refreshed even when nothing is reported as
+// changed (catches anything else that depends on the clock)
+const DEFAULT_MAX_FPS: u32 = 30;
+const IDLE_REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
+// Longest the exit waits for the serial thread to flush --raw-log (its reads time out after 1 s)
+const SOURCE_STOP_TIMEOUT: Duration = Duration::from_millis(1500);
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Parse CLI args for --rerun <addr> and optional CSV file
+    // Parse CLI args for --rerun <addr>, --raw-log <path>, --log-dir <dir>, --headless --out <path>, --mock [--seed <n>], --fps <n>, --overlay <csv>, --layout <CxR>, --views <a,b,..>, --default-view <name> and optional CSV file
     let args: Vec<String> = std::env::args().collect();
     let mut rerun_addr = None;
     let mut csv_file = None;
+    let mut raw_log_path = None;
+    let mut log_dir = None;
+    let mut headless = false;
+    let mut out_path = None;
+    let mut mock = false;
+    let mut mock_seed = esp_com::DEFAULT_MOCK_SEED;
+    let mut max_fps = DEFAULT_MAX_FPS;
+    let mut overlay_file = None;
+    let mut layout_grid = None;
+    let mut layout_views = Vec::new();
+    let mut default_view = None;
     let mut i = 1;
     while i < args.len() {
         if args[i] == "--rerun" && i + 1 < args.len() {
             rerun_addr = Some(args[i+1].clone());
             i += 2;
+        } else if args[i] == "--raw-log" && i + 1 < args.len() {
+            raw_log_path = Some(args[i+1].clone());
+            i += 2;
+        } else if args[i] == "--log-dir" && i + 1 < args.len() {
+            log_dir = Some(args[i+1].clone());
+            i += 2;
+        } else if args[i] == "--headless" {
+            headless = true;
+            i += 1;
+        } else if args[i] == "--out" && i + 1 < args.len() {
+            out_path = Some(args[i+1].clone());
+            i += 2;
+        } else if args[i] == "--mock" {
+            mock = true;
+            i += 1;
+        } else if args[i] == "--seed" && i + 1 < args.len() {
+            let Ok(seed) = args[i+1].parse() else {
+                eprintln!("--seed needs a non-negative integer, got '{}'", args[i+1]);
+                std::process::exit(2);
+            };
+            mock_seed = seed;
+            i += 2;
+        } else if args[i] == "--fps" && i + 1 < args.len() {
+            let Some(fps) = args[i+1].parse().ok().filter(|&fps: &u32| fps > 0) else {
+                eprintln!("--fps needs a positive integer, got '{}'", args[i+1]);
+                std::process::exit(2);
+            };
+            max_fps = fps;
+            i += 2;
+        } else if args[i] == "--overlay" && i + 1 < args.len() {
+            overlay_file = Some(args[i+1].clone());
+            i += 2;
+        } else if args[i] == "--layout" && i + 1 < args.len() {
+            let Some(grid) = layout_tree::parse_grid(&args[i+1]) else {
+                eprintln!("--layout needs COLSxROWS with at most {} panes (e.g. 2x2), got '{}'", layout_tree::MAX_PANES, args[i+1]);
+                std::process::exit(2);
+            };
+            layout_grid = Some(grid);
+            i += 2;
+        } else if args[i] == "--views" && i + 1 < args.len() {
+            for name in args[i+1].split(',') {
+                let Some(view) = ViewType::from_name(name) else {
+                    eprintln!("--views: unknown view '{}' (one of {})", name.trim(), layout_tree::VIEW_NAMES.join(", "));
+                    std::process::exit(2);
+                };
+                layout_views.push(view);
+            }
+            i += 2;
+        } else if args[i] == "--default-view" && i + 1 < args.len() {
+            let Some(view) = ViewType::from_name(&args[i+1]) else {
+                eprintln!("--default-view: unknown view '{}' (one of {})", args[i+1].trim(), layout_tree::VIEW_NAMES.join(", "));
+                std::process::exit(2);
+            };
+            default_view = Some(view);
+            i += 2;
         } else if args[i].ends_with(".csv") {
             csv_file = Some(args[i].clone());
             i += 1;
@@ -50,19 +112,100 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    let _ = config_manager::init();
+    // Diagnostics go to a file only when asked for; the guard flushes it when main returns
+    let _log_guard = match log_dir.as_deref().map(logging::init) {
+        Some(Ok(guard)) => Some(guard),
+        Some(Err(e)) => {
+            eprintln!("--log-dir: {}", e);
+            std::process::exit(2);
+        }
+        None => None,
+    };
+
+    if let Err(e) = config_manager::init() {
+        tracing::warn!(error = %e, "creating the templates directory failed");
+    }
+    if let Err(e) = keybindings::init() {
+        tracing::warn!(error = %e, "writing default keybindings.json failed");
+    }
 
     // 1. Wrap App in Arc<Mutex<>> to allow sharing across threads
-    let app = Arc::new(Mutex::new(App::new(rerun_addr, csv_file.clone())));
+    // --mock wins over a CSV argument
+    if mock {
+        csv_file = None;
+    }
+    let mut app_state = App::new(rerun_addr, csv_file.clone());
+    for warning in &app_state.keybindings.warnings {
+        eprintln!("keybindings.json: {}", warning);
+    }
+    app_state.raw_log_path = raw_log_path;
+
+    // --layout / --views replace the default template's panes (theme and settings stay).
+    // Without --layout the grid is just big enough for the views, as square as possible.
+    if layout_grid.is_some() || !layout_views.is_empty() {
+        let (cols, rows) = layout_grid.unwrap_or_else(|| {
+            let cols = (1..).find(|c| c * c >= layout_views.len()).unwrap_or(1);
+            (cols, layout_views.len().div_ceil(cols).max(1))
+        });
+        if layout_views.len() > cols * rows || cols * rows > layout_tree::MAX_PANES {
+            eprintln!("--views: {} views don't fit a {}x{} layout (at most {} panes)", layout_views.len(), cols, rows, layout_tree::MAX_PANES);
+            std::process::exit(2);
+        }
+        app_state.apply_layout(TilingManager::grid(cols, rows, &layout_views));
+    }
+    // --default-view wins over the template's (including an explicit "empty")
+    if let Some(view) = default_view {
+        app_state.default_view = view;
+    }
+    let app = Arc::new(Mutex::new(app_state));
 
     // 2. Clone the reference for the background thread
     let app_access = Arc::clone(&app);
 
-    // Only spawn ESP com if NO CSV file
-    if csv_file.is_none() {
+    // Headless capture: no terminal, just the serial thread and a file writer
+    if headless {
+        let Some(out) = out_path else {
+            eprintln!("--headless needs --out <capture.csv|capture.rrd>");
+            std::process::exit(2);
+        };
+        let source = if mock { DataSource::Mock { seed: mock_seed } } else { DataSource::Serial };
         thread::spawn(move || {
-            esp_com::esp_com(app_access);
+            esp_com::run_source(app_access, source);
         });
+        return run_headless(app, &out);
+    }
+
+    // Mock data if asked for, else replay the CSV if one was given, otherwise read from the ESP
+    let source = if mock {
+        DataSource::Mock { seed: mock_seed }
+    } else if let Some(path) = csv_file {
+        let mut loader = dataloader::Dataloader::new();
+        if let Err(e) = loader.import_history_from_csv(&path) {
+            eprintln!("Failed to load CSV: {}", e);
+        }
+        DataSource::Replay(loader.history)
+    } else {
+        DataSource::Serial
+    };
+    let writes_raw_log = matches!(source, DataSource::Serial) && app.lock().unwrap().raw_log_path.is_some();
+    thread::spawn(move || {
+        esp_com::run_source(app_access, source);
+    });
+
+    // Second source for A/B comparison, replayed on its own thread into App::overlay
+    if let Some(path) = overlay_file {
+        let mut loader = dataloader::Dataloader::new();
+        match loader.import_history_from_csv(&path) {
+            Ok(_) => {
+                let label = std::path::Path::new(&path).file_stem().map_or_else(|| path.clone(), |s| s.to_string_lossy().into_owned());
+                app.lock().unwrap().overlay = Some(SourceStream::new(label));
+                let overlay_access = Arc::clone(&app);
+                thread::spawn(move || {
+                    esp_com::run_overlay(overlay_access, loader.history);
+                });
+            }
+            Err(e) => eprintln!("Failed to load overlay CSV: {}", e),
+        }
     }
 
     enable_raw_mode()?;
@@ -74,29 +217,58 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Loop Timing Control
     let tick_rate = Duration::from_millis(100); // 10Hz Data Updates
     let mut last_tick = Instant::now();
+    // Only redraw when input or new data changed something, at most max_fps times a second
+    let frame_interval = Duration::from_secs(1) / max_fps;
+    let mut dirty = true;
+    let mut last_draw = Instant::now();
+    let mut last_toast_at = None; // Toasts can also come from the source thread
+    let mut last_serial_status = None;
 
     loop {
         // 1. Render Layer
-        // Lock the app briefly to draw the UI
-        terminal.draw(|f| {
-            let app = app.lock().unwrap();
-            view_router::ui(f, &app)
-        })?;
+        let frame_due = dirty && last_draw.elapsed() >= frame_interval;
+        if frame_due || last_draw.elapsed() >= IDLE_REDRAW_INTERVAL {
+            // Lock the app briefly to draw the UI
+            let completed = terminal.draw(|f| {
+                let mut app = app.lock().unwrap();
+                app.clear_stale_fullscreen();
+                view_router::ui(f, &app)
+            })?;
+            dirty = false;
+            last_draw = Instant::now();
+
+            // Snapshot requests are served from the frame that was just drawn
+            let mut app_guard = app.lock().unwrap();
+            if app_guard.screenshot_requested {
+                app_guard.screenshot_requested = false;
+                match screenshot::save_snapshot(completed.buffer) {
+                    Ok(path) => app_guard.notify(format!("Saved {}", path)),
+                    Err(e) => app_guard.notify(format!("Screenshot failed: {}", e)),
+                }
+                dirty = true;
+            }
+        }
 
         // 2. Input Layer
-        let timeout = tick_rate
+        // Wake up for the next tick, or for the pending frame once the frame cap allows it
+        let mut timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
+        if dirty {
+            timeout = timeout.min(frame_interval.saturating_sub(last_draw.elapsed()));
+        }
 
         if event::poll(timeout)? {
             // Processing LOOP: Drain the event queue
             let start = Instant::now();
+            let mut events = 0;
 
-            // Loop while events are available AND we haven't spent too long (20ms) processing them.
-            while event::poll(Duration::from_millis(0))? && start.elapsed() < Duration::from_millis(20) {
+            // Loop while events are available AND we haven't spent too long (INPUT_BUDGET) processing them.
+            while event::poll(Duration::from_millis(0))? && start.elapsed() < INPUT_BUDGET {
                 // Lock the app to handle input
                 let mut app_guard = app.lock().unwrap();
-                let _ = input_handler::handle_event(&mut app_guard)?;
+                dirty |= input_handler::handle_event(&mut app_guard)?;
+                events += 1;
 
                 if app_guard.should_quit {
                     // We need to release the lock before breaking,
@@ -105,6 +277,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     break;
                 }
             }
+
+            // Out of budget with input still queued: the UI is falling behind (e.g. a mouse-move flood)
+            let elapsed = start.elapsed();
+            let backlogged = elapsed >= INPUT_BUDGET && event::poll(Duration::from_millis(0))?;
+            app.lock().unwrap().record_input_drain(events, elapsed, backlogged);
+        } else {
+            app.lock().unwrap().record_input_drain(0, Duration::ZERO, false);
         }
 
         // Check quit condition from input loop (requires re-locking or checking flags)
@@ -119,7 +298,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         if last_tick.elapsed() >= tick_rate {
             let should_quit = {
                 let mut app_guard = app.lock().unwrap();
-                app_guard.on_tick();
+                dirty |= app_guard.on_tick();
+                let toast_at = app_guard.toast.as_ref().map(|(_, at)| *at);
+                dirty |= toast_at != last_toast_at;
+                last_toast_at = toast_at;
+                dirty |= app_guard.serial_status != last_serial_status;
+                last_serial_status = app_guard.serial_status.clone();
                 last_tick = Instant::now();
                 app_guard.should_quit
             };
@@ -134,5 +318,82 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
     terminal.show_cursor()?;
 
+    // 4. Flush what is still being written before the process exits
+    if let Some(path) = app.lock().unwrap().finish_recording() {
+        eprintln!("Saved {}", path);
+    }
+    if writes_raw_log {
+        // The serial thread flushes the raw log once it sees should_quit (reads time out after 1 s)
+        let deadline = Instant::now() + SOURCE_STOP_TIMEOUT;
+        while !app.lock().unwrap().source_stopped && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes incoming packets to `out` until Ctrl-C.
+/// `.rrd` records the averaged stream through Rerun (same as Shift+L), anything else is raw CSV.
+fn run_headless(app: Arc<Mutex<App>>, out: &str) -> Result<(), Box<dyn std::error::Error>> {
+    // 1. SIGINT only raises a flag so the loop can flush and close the file
+    let running = Arc::new(AtomicBool::new(true));
+    let flag = Arc::clone(&running);
+    ctrlc::set_handler(move || flag.store(false, Ordering::SeqCst))?;
+
+    let record_rrd = out.ends_with(".rrd");
+    let mut csv_writer = None;
+
+    if record_rrd {
+        let app_guard = app.lock().unwrap();
+        if let Some(ref streamer) = app_guard.rerun_streamer {
+            streamer.lock().unwrap().start_record(out)?;
+        }
+    } else {
+        csv_writer = Some(dataloader::create_csv_writer(out)?);
+    }
+
+    eprintln!("Capturing to {} (Ctrl-C to stop)", out);
+
+    // 2. Drain the queue on the same cadence as the UI
+    let mut captured: usize = 0;
+    let mut last_report = Instant::now();
+
+    while running.load(Ordering::SeqCst) {
+        thread::sleep(UPDATE_INTERVAL);
+
+        let mut app_guard = app.lock().unwrap();
+        if let Some(ref mut wtr) = csv_writer {
+            let packets = app_guard.dataloader.drain_buffer();
+            for packet in &packets {
+                dataloader::write_csv_row(wtr, packet)?;
+            }
+            captured += packets.len();
+        } else {
+            captured += app_guard.dataloader.queue.len();
+            app_guard.on_tick();
+        }
+        // Nothing reads the in-memory copy without a UI; keep it from growing forever
+        app_guard.dataloader.history.clear();
+        drop(app_guard);
+
+        if last_report.elapsed() >= Duration::from_secs(5) {
+            eprintln!("Captured {} packets", captured);
+            last_report = Instant::now();
+        }
+    }
+
+    // 3. Flush and close
+    if let Some(mut wtr) = csv_writer {
+        wtr.flush()?;
+    }
+    if record_rrd {
+        let app_guard = app.lock().unwrap();
+        if let Some(ref streamer) = app_guard.rerun_streamer {
+            streamer.lock().unwrap().stop_record();
+        }
+    }
+
+    eprintln!("Stopped. Captured {} packets to {}", captured, out);
     Ok(())
-}
\ No newline at end of file
+}
@@ -5,6 +5,7 @@ use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
 use crate::backend::csi_data::CsiData;
 use crate::backend::doppler::DopplerSpectrogram;
+use crate::frontend::theme::PhaseColormap;
 
 #[cfg(feature = "rerun")]
 use rerun::{RecordingStream, RecordingStreamBuilder};
@@ -13,6 +14,14 @@ use rerun::archetypes::{BarChart, Tensor, Points3D};
 #[cfg(feature = "rerun")]
 use rerun::components::{Color, Position3D};
 
+/// Logs one archetype; failures (e.g. a dropped viewer connection) go to the diagnostic log
+#[cfg(feature = "rerun")]
+pub(crate) fn log_or_warn<AS: ?Sized + rerun::AsComponents>(rec: &RecordingStream, entity: &str, archetype: &AS) {
+    if let Err(e) = rec.log(entity, archetype) {
+        tracing::warn!(entity, error = %e, "Rerun log failed");
+    }
+}
+
 // Data Model "CsiFrame"
 #[derive(Debug, Clone, Copy)]
 pub struct CsiFrame {
@@ -63,6 +72,12 @@ pub struct RerunStreamer {
     doppler: DopplerSpectrogram,
 
     app_id: String,
+
+    // Complex scatter point colors (kept in sync with App::phase_colormap by the callers of push_csi)
+    pub phase_colormap: PhaseColormap,
+
+    // Why the last connect() failed. Cleared by a successful connect.
+    last_error: Option<String>,
 }
 
 impl RerunStreamer {
@@ -75,9 +90,13 @@ impl RerunStreamer {
             #[cfg(feature = "rerun")]
             heatmap: VecDeque::with_capacity(500),
             
-            doppler: DopplerSpectrogram::new(128, 200), // Window=128, History=200
+            doppler: DopplerSpectrogram::new(128, 200), // Window=128, History=200, Hop=32
 
             app_id: app_id.to_string(),
+
+            phase_colormap: PhaseColormap::default(),
+
+            last_error: None,
         }
     }
 
@@ -96,10 +115,30 @@ impl RerunStreamer {
                 .connect_grpc_opts(target.clone());
 
             match rec {
-                Ok(r) => {self.rr = Some(r);},
-                Err(_e) => {}
+                Ok(r) => {
+                    self.rr = Some(r);
+                    self.last_error = None;
+                },
+                Err(e) => {
+                    tracing::warn!(addr = %target, error = %e, "Rerun connect failed");
+                    self.last_error = Some(format!("{}: {}", target, e));
+                }
             }
         }
+        #[cfg(not(feature = "rerun"))]
+        {
+            self.last_error = Some(format!("{}: Rerun feature disabled", addr));
+        }
+    }
+
+    /// Doppler spectrogram fed by push_csi (for standalone export)
+    pub fn doppler(&self) -> &DopplerSpectrogram {
+        &self.doppler
+    }
+
+    /// Error from the last failed connect(), if any
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
     }
 
     pub fn push_csi(&mut self, csi: &CsiFrame) {
@@ -119,10 +158,7 @@ impl RerunStreamer {
                 rec.set_time_sequence("frame_idx", csi.timestamp as i64);
 
                 // 1. Bar Plot (Amplitude) -> "csi/bar_amplitude"
-                let _ = rec.log(
-                    "csi/bar_amplitude",
-                    &BarChart::new(csi.amplitude.to_vec()),
-                );
+                log_or_warn(rec, "csi/bar_amplitude", &BarChart::new(csi.amplitude.to_vec()));
 
                 // 2. Heatmap -> "csi/heatmap"
                 // Convert heatmap buffer to Image (u8 grayscale)
@@ -145,10 +181,7 @@ impl RerunStreamer {
                     rerun::TensorBuffer::U8(img_data.into())
                 );
 
-                let _ = rec.log(
-                    "csi/heatmap",
-                    &Tensor::new(tensor_data),
-                );
+                log_or_warn(rec, "csi/heatmap", &Tensor::new(tensor_data));
 
                 // 3. 3D Scatter -> "csi/complex_scatter"
                 let positions: Vec<Position3D> = (0..64).map(|i| {
@@ -156,17 +189,12 @@ impl RerunStreamer {
                 }).collect();
 
                 let colors: Vec<Color> = (0..64).map(|i| {
-                    // Map phase (-PI..PI) to 0..255
-                    let p = csi.phase[i];
-                    let norm = (p + std::f32::consts::PI) / (2.0 * std::f32::consts::PI);
-                    let c = (norm * 255.0).clamp(0.0, 255.0) as u8;
-                    Color::from_unmultiplied_rgba(c, 100, 255 - c, 255)
+                    // Same phase colors as the TUI (PhaseColormap)
+                    let [r, g, b] = self.phase_colormap.rgb(csi.phase[i] as f64);
+                    Color::from_unmultiplied_rgba(r, g, b, 255)
                 }).collect();
 
-                let _ = rec.log(
-                    "csi/complex_scatter",
-                    &Points3D::new(positions).with_colors(colors),
-                );
+                log_or_warn(rec, "csi/complex_scatter", &Points3D::new(positions).with_colors(colors));
 
                 // 4. Doppler Spectrogram -> "csi/doppler_spectrogram"
                 self.doppler.to_rerun(rec);
@@ -193,7 +221,8 @@ impl RerunStreamer {
             }
 
             let rec = RecordingStreamBuilder::new(self.app_id.as_str())
-                .save(path)?;
+                .save(path)
+                .inspect_err(|e| tracing::warn!(path, error = %e, "starting RRD recording failed"))?;
 
             self.rrd_record = Some(rec);
             Ok(())
@@ -248,10 +277,7 @@ impl RerunStreamer {
                 rec.set_time_sequence("frame_idx", frame.timestamp as i64);
 
                 // 1. Bar Plot (Amplitude) -> "csi/bar_amplitude"
-                let _ = rec.log(
-                    "csi/bar_amplitude",
-                    &BarChart::new(frame.amplitude.to_vec()),
-                );
+                log_or_warn(&rec, "csi/bar_amplitude", &BarChart::new(frame.amplitude.to_vec()));
 
                 // 2. Heatmap -> "csi/heatmap"
                 // (We don't have the heatmap history here, so we skip it or just log the current frame as a row?
@@ -266,17 +292,12 @@ impl RerunStreamer {
                 }).collect();
 
                 let colors: Vec<Color> = (0..64).map(|i| {
-                    // Map phase (-PI..PI) to 0..255
-                    let p = frame.phase[i];
-                    let norm = (p + std::f32::consts::PI) / (2.0 * std::f32::consts::PI);
-                    let c = (norm * 255.0).clamp(0.0, 255.0) as u8;
-                    Color::from_unmultiplied_rgba(c, 100, 255 - c, 255)
+                    // Same phase colors as the TUI (PhaseColormap)
+                    let [r, g, b] = self.phase_colormap.rgb(frame.phase[i] as f64);
+                    Color::from_unmultiplied_rgba(r, g, b, 255)
                 }).collect();
 
-                let _ = rec.log(
-                    "csi/complex_scatter",
-                    &Points3D::new(positions).with_colors(colors),
-                );
+                log_or_warn(&rec, "csi/complex_scatter", &Points3D::new(positions).with_colors(colors));
             }
 
             // Explicitly drop rec to flush and close
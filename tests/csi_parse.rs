@@ -0,0 +1,182 @@
+// --- File: tests/csi_parse.rs ---
+// --- Purpose: CsiData::parse against firmware blocks (including the quirks seen on real ESP32s), splitting the serial stream into blocks, and the CSV export/import round trip ---
+
+use project::backend::csi_data::{BlockAssembler, CsiData, CsiParseError};
+use project::dataloader::Dataloader;
+
+/// A csi-cli block as printed by the firmware, with the given rssi line and CSI array line
+fn block(rssi: &str, csi: &str) -> String {
+    format!(
+        "mac: DC:ED:83:4A:55:9A\n\
+         rssi: {}\n\
+         rate: 11\n\
+         noise floor: 161\n\
+         channel: 6\n\
+         timestamp: 3764286\n\
+         sig len: 28\n\
+         rx state: 0\n\
+         secondary channel: 0\n\
+         sgi: 0\n\
+         ant: 0\n\
+         ampdu cnt: 0\n\
+         sig_mode: 1\n\
+         mcs: 7\n\
+         cwb: 0\n\
+         smoothing: 1\n\
+         not sounding: 1\n\
+         aggregation: 0\n\
+         stbc: 0\n\
+         fec coding: 0\n\
+         sig_len: 52\n\
+         data length: 8\n\
+         csi raw data:\n\
+         {}\n",
+        rssi, csi
+    )
+}
+
+#[test]
+fn normal_packet_fills_every_field() {
+    let data = CsiData::parse(&block("-83", "[0, 0, 6, -10, 7, 9, 8, 9]")).expect("valid block");
+    assert_eq!(data.mac, "DC:ED:83:4A:55:9A");
+    assert_eq!(data.rssi, -83);
+    assert_eq!(data.rate, 11);
+    assert_eq!(data.channel, 6);
+    assert_eq!(data.timestamp, 3764286);
+    assert_eq!((data.sig_mode, data.mcs, data.cwb), (1, 7, 0));
+    assert_eq!((data.smoothing, data.not_sounding), (1, 1));
+    assert_eq!(data.data_length, 8);
+    assert_eq!(data.csi_raw_data, vec![0, 0, 6, -10, 7, 9, 8, 9]);
+}
+
+#[test]
+fn both_sig_len_keys_are_kept_apart() {
+    // The firmware prints both "sig len" and "sig_len" in one block;
+    // the first goes to sig_len, the second to sig_len_extra
+    let data = CsiData::parse(&block("-83", "[1, 2]")).expect("valid block");
+    assert_eq!(data.sig_len, 28);
+    assert_eq!(data.sig_len_extra, 52);
+}
+
+#[test]
+fn unsigned_rssi_and_noise_floor_wrap_to_negative_dbm() {
+    // Some firmware prints the i8 fields as unsigned bytes: 173 is -83 dBm, 161 is -95 dBm
+    let data = CsiData::parse(&block("173", "[1, 2]")).expect("valid block");
+    assert_eq!(data.rssi, -83);
+    assert_eq!(data.noise_floor, -95);
+
+    // Values that already fit an i8 are left alone
+    let data = CsiData::parse(&block("127", "[1, 2]")).expect("valid block");
+    assert_eq!(data.rssi, 127);
+}
+
+#[test]
+fn empty_csi_array_is_rejected() {
+    assert!(matches!(CsiData::parse(&block("-83", "[]")), Err(CsiParseError::NoCsi)));
+    assert!(matches!(CsiData::parse(&block("-83", "")), Err(CsiParseError::NoCsi)));
+}
+
+#[test]
+fn trailing_comma_rejects_the_array() {
+    // Not tolerated (yet): the empty element after the comma is not a number
+    match CsiData::parse(&block("-83", "[1, 2, 3, 4,]")) {
+        Err(CsiParseError::BadField { field, .. }) => assert_eq!(field, "csi raw data"),
+        other => panic!("expected a BadField error, got {:?}", other.map(|d| d.csi_raw_data)),
+    }
+}
+
+#[test]
+fn truncated_blocks() {
+    let full = block("-83", "[1, 2, 3, 4, 5, 6]");
+
+    // Cut before the marker: only metadata, no CSI
+    let cut = &full[..full.find("csi raw data:").unwrap()];
+    assert!(matches!(CsiData::parse(cut), Err(CsiParseError::NoCsi)));
+
+    // Cut right after the marker line
+    let cut = &full[..full.find("[").unwrap()];
+    assert!(matches!(CsiData::parse(cut), Err(CsiParseError::NoCsi)));
+
+    // Cut between two values: the values read so far are kept, even an unpaired I
+    let cut = &full[..full.find(", 4").unwrap()];
+    assert_eq!(CsiData::parse(cut).expect("partial array").csi_raw_data, vec![1, 2, 3]);
+
+    // Cut inside a number's sign
+    let cut = format!("{}, -", &full[..full.find(", 4").unwrap()]);
+    assert!(matches!(CsiData::parse(&cut), Err(CsiParseError::BadField { .. })));
+}
+
+#[test]
+fn unknown_fields_and_blank_lines_are_ignored() {
+    let input = format!("\n  firmware: v1.2  \n\n{}", block("-60", "[9, -9]"));
+    let data = CsiData::parse(&input).expect("valid block");
+    assert_eq!(data.rssi, -60);
+    assert_eq!(data.csi_raw_data, vec![9, -9]);
+}
+
+#[test]
+fn block_assembler_splits_on_the_csi_array() {
+    let stream = "rssi: -40\nchannel: 6\nextra field: 1\ncsi raw data:\nI (1234) wifi: beacon lost\n[1, 2,\n3, 4]\n\
+                  rssi: -41\ncsi raw data:\nmac: AA:BB\n\
+                  rssi: -42\ncsi raw data:\n[5, 6]\n";
+    let mut assembler = BlockAssembler::default();
+    let packets: Vec<CsiData> = stream
+        .lines()
+        .filter_map(|line| assembler.push_line(line))
+        .map(|block| CsiData::parse(&block).expect("complete block"))
+        .collect();
+
+    // The second packet lost its array; its metadata must not leak into the third
+    assert_eq!(packets.len(), 2);
+    assert_eq!((packets[0].rssi, packets[0].channel), (-40, 6));
+    assert_eq!(packets[0].csi_raw_data, vec![1, 2, 3, 4]);
+    assert_eq!((packets[1].rssi, packets[1].mac.as_str()), (-42, "AA:BB"));
+    assert_eq!(packets[1].csi_raw_data, vec![5, 6]);
+}
+
+#[test]
+fn csv_export_and_import_round_trip() {
+    let packet = |i: u32, csi_raw_data: Vec<i32>| CsiData {
+        mac: format!("DC:ED:83:4A:55:{:02X}", i),
+        rssi: -40 - i as i32,
+        rate: 11 + i,
+        noise_floor: -95,
+        channel: 1 + i,
+        timestamp: u64::MAX - i as u64,
+        sig_len: 28 + i,
+        rx_state: i,
+        secondary_channel: 1,
+        sgi: 1,
+        ant: i % 2,
+        ampdu_cnt: 3,
+        sig_mode: 1,
+        mcs: 7,
+        cwb: 1,
+        smoothing: 1,
+        not_sounding: 1,
+        aggregation: 1,
+        stbc: 1,
+        fec_coding: 1,
+        sig_len_extra: 52 + i,
+        data_length: csi_raw_data.len() as u32,
+        csi_raw_data,
+    };
+
+    let mut exported = Dataloader::new();
+    exported.history = vec![
+        packet(0, vec![0, 0, 6, -10, 7, 9, 8, 9]),
+        packet(1, Vec::new()), // Written as "[]"
+        packet(2, vec![i32::MAX, i32::MIN, 1_000_000, -1_000_000]),
+    ];
+
+    let path = std::env::temp_dir().join(format!("esp-csi-tui-roundtrip-{}.csv", std::process::id()));
+    let path = path.to_str().expect("temp dir is valid UTF-8");
+    exported.export_history_to_csv(path).expect("export");
+
+    let mut imported = Dataloader::new();
+    let result = imported.import_history_from_csv(path);
+    let _ = std::fs::remove_file(path);
+    result.expect("import");
+
+    assert_eq!(imported.history, exported.history);
+}
@@ -0,0 +1,656 @@
+// --- File: tests/render.rs ---
+// --- Purpose: Off-screen rendering of the whole UI (tiling, panes, overlays) via view_router::render_to_buffer ---
+
+use project::{App, NetworkStats};
+use project::backend::csi_data::CsiData;
+use project::layout_tree::{TilingManager, ViewType};
+use project::theme::{Theme, ThemeType};
+use project::view_router::render_to_buffer;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Direction, Rect};
+
+const AREA: Rect = Rect { x: 0, y: 0, width: 120, height: 40 };
+
+/// App that doesn't depend on the machine: default layout, fixed theme, no Rerun, synthetic history
+fn test_app(packets: usize) -> App {
+    let mut app = App::new(None, None);
+    app.tiling = TilingManager::new();
+    app.pane_states.clear();
+    app.theme = Theme::new(ThemeType::Dark);
+    app.rerun_streamer = None;
+    app.toast = None;
+
+    for i in 0..packets {
+        let mut stats = NetworkStats::empty();
+        stats.id = i as u64 + 1;
+        stats.csi = Some(CsiData {
+            timestamp: i as u64 * 100_000,
+            csi_raw_data: (0..128).map(|k| ((k * 7 + i as i32 * 3) % 60) - 30).collect(),
+            ..CsiData::default()
+        });
+        app.history.push(stats);
+    }
+    if let Some(last) = app.history.last() {
+        app.current_stats = last.clone();
+    }
+    app
+}
+
+fn buffer_text(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    buffer
+        .content
+        .chunks(width)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn buffer_matches_requested_area() {
+    let app = test_app(0);
+    let buffer = render_to_buffer(&app, AREA);
+    assert_eq!(buffer.area, AREA);
+}
+
+#[test]
+fn rendering_is_deterministic() {
+    let mut app = test_app(50);
+    app.tiling.split(Direction::Horizontal);
+    app.tiling.set_current_view(ViewType::Polar);
+    app.tiling.split(Direction::Vertical);
+    app.tiling.set_current_view(ViewType::Spectrogram);
+
+    assert_eq!(render_to_buffer(&app, AREA), render_to_buffer(&app, AREA));
+}
+
+#[test]
+fn every_pane_of_a_split_layout_is_drawn() {
+    let mut app = test_app(20);
+    app.tiling.set_current_view(ViewType::Dashboard);
+    app.tiling.split(Direction::Horizontal);
+    app.tiling.set_current_view(ViewType::Waterfall);
+
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("Network Stats"), "dashboard missing from:\n{}", text);
+    assert!(text.contains("Amplitude Waterfall"), "waterfall missing from:\n{}", text);
+}
+
+#[test]
+fn every_view_renders_at_small_and_large_sizes() {
+    let views = [
+        ViewType::Dashboard, ViewType::Polar, ViewType::Isometric, ViewType::Spectrogram,
+        ViewType::Phase, ViewType::RawScatter, ViewType::Waterfall, ViewType::Ridgeline, ViewType::Constellation, ViewType::Overview, ViewType::Empty,
+    ];
+    for packets in [0, 1, 300] {
+        for view in views {
+            let mut app = test_app(packets);
+            app.tiling.set_current_view(view);
+            for area in [AREA, Rect::new(0, 0, 12, 4)] {
+                render_to_buffer(&app, area);
+            }
+        }
+    }
+}
+
+#[test]
+fn overlays_are_drawn_over_the_tiling() {
+    let mut app = test_app(0);
+    let before = render_to_buffer(&app, AREA);
+
+    app.show_main_menu = true;
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("Close Menu"));
+    assert!(!buffer_text(&before).contains("Close Menu"));
+}
+
+#[test]
+fn pane_label_is_shown_in_the_title() {
+    let mut app = test_app(5);
+    app.tiling.set_current_view(ViewType::Phase);
+    let id = app.tiling.focused_pane_id;
+
+    app.set_pane_label(id, "  TX-left ");
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("TX-left · Phase Wireframe"));
+
+    // Blank label reverts to the view name
+    app.set_pane_label(id, "   ");
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(!text.contains("TX-left"));
+    assert!(text.contains(&format!("#{} Phase Wireframe", id)));
+}
+
+#[test]
+fn overview_falls_back_to_dashboard_when_small() {
+    let mut app = test_app(30);
+    app.tiling.set_current_view(ViewType::Overview);
+
+    let large = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(large.contains("Overview"));
+    assert!(large.contains("Amplitude Waterfall"));
+
+    let small = buffer_text(&render_to_buffer(&app, Rect::new(0, 0, 50, 14)));
+    assert!(!small.contains("Amplitude Waterfall"));
+    assert!(small.contains("Network Stats"));
+}
+
+#[test]
+fn stale_data_is_flagged_as_signal_lost() {
+    let mut app = test_app(10);
+    app.tiling.set_current_view(ViewType::Dashboard);
+    assert!(!buffer_text(&render_to_buffer(&app, AREA)).contains("SIGNAL LOST"));
+
+    app.last_packet_time = std::time::Instant::now() - std::time::Duration::from_secs(5);
+    assert!(app.signal_lost());
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("SIGNAL LOST"));
+}
+
+#[test]
+fn inspector_shows_values_or_no_data() {
+    let mut app = test_app(3);
+    app.show_inspector = true;
+    app.inspector_index = 2;
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("Inspector: Subcarrier 2"));
+    assert!(text.contains("Amplitude"));
+
+    app.inspector_index = 500;
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("No data for subcarrier 500"), "missing notice in:\n{}", text);
+}
+
+#[test]
+fn motion_above_threshold_is_flagged_in_the_header() {
+    let mut app = test_app(10);
+    app.motion_energy = app.gauge_ranges.motion_threshold / 2.0;
+    assert!(!buffer_text(&render_to_buffer(&app, AREA)).contains("MOTION"));
+
+    app.motion_energy = app.gauge_ranges.motion_threshold * 2.0;
+    assert!(app.motion_detected());
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("MOTION"));
+}
+
+#[test]
+fn pane_titles_use_the_view_accent_color() {
+    let mut app = test_app(5);
+    app.tiling.set_current_view(ViewType::Waterfall);
+    let buffer = render_to_buffer(&app, AREA);
+
+    let text = buffer_text(&buffer);
+    let row = text.lines().position(|l| l.contains("Amplitude Waterfall")).expect("title drawn");
+    let col = text.lines().nth(row).unwrap().chars().position(|c| c == 'W').unwrap() as u16;
+    assert_eq!(buffer[(col, row as u16)].fg, ViewType::Waterfall.accent_color(&app.theme));
+}
+
+#[test]
+fn tiny_panes_show_a_placeholder_instead_of_the_view() {
+    let mut app = test_app(30);
+    app.tiling.set_current_view(ViewType::Polar);
+    let id = app.tiling.focused_pane_id;
+    let text = buffer_text(&render_to_buffer(&app, Rect::new(0, 0, 22, 8)));
+    assert!(text.contains("too small"));
+    assert!(text.contains(&format!("#{}", id)));
+
+    // The dashboard still fits in the same area
+    app.tiling.set_current_view(ViewType::Dashboard);
+    assert!(!buffer_text(&render_to_buffer(&app, Rect::new(0, 0, 22, 8))).contains("too small"));
+}
+
+#[test]
+fn channel_line_flags_unexpected_subcarrier_counts() {
+    let mut app = test_app(1);
+    app.tiling.set_current_view(ViewType::Dashboard);
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("LLTF"), "channel line missing from:\n{}", text);
+    assert!(!text.contains("expected"));
+
+    // 64 subcarriers can't be a 40 MHz HT packet
+    let csi = app.current_stats.csi.as_mut().unwrap();
+    csi.sig_mode = 1;
+    csi.cwb = 1;
+    assert!(!csi.subcarrier_count_ok());
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("expected 128/192/256"));
+
+    app.current_stats.csi = None;
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("No CSI"));
+}
+
+#[test]
+fn onboarding_is_shown_only_until_data_arrives() {
+    let mut app = test_app(0);
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("Waiting for CSI data"));
+
+    app.show_onboarding = false;
+    assert!(!buffer_text(&render_to_buffer(&app, AREA)).contains("Waiting for CSI data"));
+
+    let app = test_app(1);
+    assert!(app.show_onboarding);
+    assert!(!buffer_text(&render_to_buffer(&app, AREA)).contains("Waiting for CSI data"));
+}
+
+#[test]
+fn exported_layout_round_trips_through_json() {
+    let mut app = test_app(0);
+    app.tiling.split(Direction::Horizontal);
+    app.tiling.set_current_view(ViewType::Waterfall);
+    let id = app.tiling.focused_pane_id;
+    app.set_pane_label(id, "RX");
+
+    let json = serde_json::to_string(&app.layout_for_export()).unwrap();
+    let mut other = test_app(0);
+    other.apply_layout(serde_json::from_str(&json).unwrap());
+    assert_eq!(other.tiling.panes().len(), 2);
+    assert_eq!(other.pane_label(id, "Waterfall"), "RX · Waterfall");
+    assert!(buffer_text(&render_to_buffer(&other, AREA)).contains("RX · Amplitude Waterfall"));
+}
+
+#[test]
+fn iq_threshold_steps_and_is_shown_in_the_footer() {
+    use project::view_state::ViewState;
+    let mut state = ViewState::new();
+    state.adjust_iq_threshold(false);
+    assert_eq!(state.iq_threshold, 0.0);
+    for _ in 0..20 {
+        state.adjust_iq_threshold(true);
+    }
+    assert_eq!(state.iq_threshold, 176.0);
+
+    let mut app = test_app(30);
+    app.tiling.set_current_view(ViewType::RawScatter);
+    let id = app.tiling.focused_pane_id;
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("Tail: Off"));
+    app.get_pane_state_mut(id).adjust_iq_threshold(true);
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("Tail: |H| ≥ 16"));
+}
+
+#[test]
+fn ridgeline_stacks_the_configured_number_of_packets() {
+    let mut app = test_app(30);
+    app.tiling.set_current_view(ViewType::Ridgeline);
+    let id = app.tiling.focused_pane_id;
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("Amplitude Ridgeline"));
+    assert!(text.contains("Lines: 21"), "default depth missing from:\n{}", text);
+
+    // Replay: only the packets up to the anchor are stacked
+    app.get_pane_state_mut(id).anchor_packet_id = Some(5);
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("[REPLAY ID:5]"));
+    assert!(text.contains("Lines: 5"));
+
+    app.get_pane_state_mut(id).history_depth = Some(3);
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("Lines: 4"));
+}
+
+#[test]
+fn missing_serial_device_is_explained() {
+    let mut app = test_app(0);
+    assert!(!buffer_text(&render_to_buffer(&app, AREA)).contains("NO SERIAL DEVICE"));
+
+    app.serial_status = Some(project::esp_com::NO_SERIAL_DEVICE.to_string());
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("NO SERIAL DEVICE"), "header badge missing from:\n{}", text);
+    assert!(text.contains("No serial device"), "onboarding reason missing from:\n{}", text);
+}
+
+#[test]
+fn lost_serial_link_shows_reconnecting() {
+    let mut app = test_app(0);
+    app.serial_status = Some("Lost /dev/ttyUSB0 — waiting for the ESP32 to be plugged back in".to_string());
+    app.serial_reconnecting = true;
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("SERIAL RECONNECTING"), "header badge missing from:\n{}", text);
+    assert!(!text.contains("NO SERIAL DEVICE"));
+}
+
+#[test]
+fn phase_colormap_wraps_and_polar_can_color_by_phase() {
+    use project::theme::{phase_to_color, PhaseColormap};
+    use std::f64::consts::PI;
+    for colormap in [PhaseColormap::Hue, PhaseColormap::RedBlue] {
+        assert_eq!(colormap.rgb(PI + 0.5), colormap.rgb(-PI + 0.5));
+        assert_eq!(colormap.rgb(3.0 * PI), colormap.rgb(-PI));
+    }
+    assert_eq!(PhaseColormap::Hue.rgb(-PI), [255, 0, 0]);
+    assert_eq!(PhaseColormap::RedBlue.rgb(-PI), [0, 100, 255]);
+    assert_eq!(phase_to_color(0.0, PhaseColormap::Hue, &Theme::new(ThemeType::Dark)), ratatui::style::Color::Rgb(0, 255, 255));
+
+    let mut app = test_app(30);
+    app.tiling.set_current_view(ViewType::Polar);
+    let id = app.tiling.focused_pane_id;
+    let state = app.get_pane_state_mut(id);
+    state.cycle_ring_color();
+    assert!(state.color_by_amplitude && !state.color_by_phase);
+    state.cycle_ring_color();
+    assert!(!state.color_by_amplitude && state.color_by_phase);
+    // Wide enough for the whole Polar footer
+    let text = buffer_text(&render_to_buffer(&app, Rect::new(0, 0, 180, 40)));
+    assert!(text.contains("Color: Phase"), "footer missing from:\n{}", text);
+    assert!(text.contains("+π") && text.contains("-π"), "phase legend missing from:\n{}", text);
+
+    app.get_pane_state_mut(id).cycle_ring_color();
+    assert!(!app.pane_states[&id].color_by_phase);
+}
+
+#[test]
+fn dashboard_metadata_table_lists_every_field() {
+    let mut app = test_app(1);
+    app.tiling.set_current_view(ViewType::Dashboard);
+    let pane = app.tiling.focused_pane_id;
+    app.get_pane_state_mut(pane).show_metadata = true;
+    app.current_stats.csi.as_mut().unwrap().mcs = 7;
+
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    for field in ["Noise Floor", "MCS", "SGI", "STBC", "Antenna", "FEC Coding", "Subcarriers"] {
+        assert!(text.contains(field), "{} missing from:\n{}", field, text);
+    }
+    assert!(!text.contains("Packets Per Second"), "gauges should be replaced");
+
+    app.current_stats.csi = None;
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("—"));
+}
+
+#[test]
+fn panes_can_draw_from_the_overlay_source() {
+    use project::app::SourceStream;
+    let mut app = test_app(5);
+    app.tiling.set_current_view(ViewType::Dashboard);
+    let pane = app.tiling.focused_pane_id;
+
+    let mut overlay = SourceStream::new("baseline");
+    overlay.dataloader.queue.push_back(CsiData { rssi: -42, csi_raw_data: vec![4, 3, 8, 6], ..Default::default() });
+    app.overlay = Some(overlay);
+    app.last_update_time = std::time::Instant::now() - project::app::UPDATE_INTERVAL;
+    app.on_tick();
+
+    // The overlay ticks on its own; the main history is untouched
+    let overlay = app.overlay.as_ref().unwrap();
+    assert_eq!(overlay.history.len(), 1);
+    assert_eq!(overlay.current_stats.rssi, -42);
+    assert_eq!(app.history.len(), 5);
+
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("[MAIN]"), "source tag missing from:\n{}", text);
+    assert!(!text.contains("-42 dBm"));
+
+    app.get_pane_state_mut(pane).overlay_source = true;
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("[baseline]"), "overlay tag missing from:\n{}", text);
+    assert!(text.contains("-42 dBm"), "overlay RSSI missing from:\n{}", text);
+    assert_eq!(app.source_for(pane).live_id(), 1);
+}
+
+#[test]
+fn grid_and_labels_can_be_hidden_in_3d_views() {
+    let mut app = test_app(10);
+    let pane = app.tiling.focused_pane_id;
+
+    // The pane titles stay; the in-canvas ring / subcarrier / axis labels go
+    app.tiling.set_current_view(ViewType::Polar);
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("Angle: Subcarrier"), "labels missing from:\n{}", text);
+    app.get_pane_state_mut(pane).hide_grid = true;
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(!text.contains("Angle: Subcarrier") && !text.contains("SC8") && !text.contains(" dB"), "labels left in:\n{}", text);
+    assert!(text.contains("Polar Amplitude Tunnel"), "title missing");
+    assert!(text.contains('+'), "origin marker missing");
+
+    app.tiling.set_current_view(ViewType::Isometric);
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(!text.contains("X: Delay | Y: Power") && !text.contains("Delay ->"));
+    assert!(text.contains("CIR (Multipath)"), "title missing");
+    app.get_pane_state_mut(pane).hide_grid = false;
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("X: Delay | Y: Power") && text.contains("Delay ->"));
+}
+
+#[test]
+fn constellation_plots_one_packet_scaled_to_its_extent() {
+    let mut app = test_app(10);
+    app.tiling.set_current_view(ViewType::Constellation);
+    let id = app.tiling.focused_pane_id;
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("I/Q Constellation"));
+    assert!(text.contains("SC: 64 points"), "point count missing from:\n{}", text);
+    assert!(text.contains("Extent: ±30"), "extent missing from:\n{}", text);
+
+    app.get_pane_state_mut(id).anchor_packet_id = Some(3);
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("[REPLAY ID:3]"));
+}
+
+#[test]
+fn persistence_keeps_recent_packets_in_iq_views() {
+    let mut app = test_app(30);
+    let id = app.tiling.focused_pane_id;
+
+    app.tiling.set_current_view(ViewType::Constellation);
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("Persist: Off"));
+    app.get_pane_state_mut(id).adjust_persistence(true);
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("Persist: 1 packet"));
+    app.get_pane_state_mut(id).persistence = 10;
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("Persist: 10 packets"));
+
+    // The Distribution's histogram shrinks from the whole history to the weighted window
+    app.tiling.set_current_view(ViewType::RawScatter);
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("Persist: 10 packets"), "persistence missing from:\n{}", text);
+    app.get_pane_state_mut(id).persistence = 0;
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("Persist: All"));
+}
+
+#[test]
+fn maximize_restores_the_pane_and_survives_closing_it() {
+    let mut app = test_app(10);
+    app.tiling.set_current_view(ViewType::Polar);
+    app.tiling.split(Direction::Horizontal); // Focus moves to the new pane 2
+    app.tiling.focused_pane_id = 1;
+
+    // Maximize: changes made while fullscreen are undone on exit, focus comes back
+    app.enter_fullscreen(1, true);
+    app.get_pane_state_mut(1).zoom_camera(true);
+    app.tiling.focused_pane_id = 2;
+    app.exit_fullscreen();
+    assert_eq!(app.fullscreen_pane_id, None);
+    assert_eq!(app.tiling.focused_pane_id, 1);
+    assert_eq!(app.get_pane_state_mut(1).zoom, 1.0);
+
+    // Plain fullscreen keeps them
+    app.enter_fullscreen(1, false);
+    app.get_pane_state_mut(1).zoom_camera(true);
+    app.exit_fullscreen();
+    assert!(app.get_pane_state_mut(1).zoom > 1.0);
+
+    // Closing the fullscreen pane falls back to the layout instead of a blank screen
+    app.tiling.focused_pane_id = 2;
+    app.enter_fullscreen(2, false);
+    app.tiling.close_focused_pane();
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("Polar Amplitude Tunnel"), "layout missing from:\n{}", text);
+    app.clear_stale_fullscreen();
+    assert_eq!(app.fullscreen_pane_id, None);
+}
+
+#[test]
+fn effective_sample_rate_follows_slow_packet_rates() {
+    let mut app = test_app(0);
+    app.current_stats.pps = 0; // Unknown: one frame per tick
+    assert_eq!(app.effective_sample_rate_hz(), 10.0);
+    app.current_stats.pps = 4;
+    assert_eq!(app.effective_sample_rate_hz(), 4.0);
+    app.current_stats.pps = 200; // Averaged down to one frame per tick
+    assert_eq!(app.effective_sample_rate_hz(), 10.0);
+
+    app.current_stats = project::NetworkStats::empty();
+    app.history.push(app.current_stats.clone());
+    app.history.push(app.current_stats.clone());
+    app.tiling.set_current_view(ViewType::Spectrogram);
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("fs: 10.0 Hz"));
+}
+
+#[test]
+fn help_lists_the_keys_actually_bound() {
+    use project::keybindings::{default_bindings, Action, KeyBindings};
+
+    let mut app = test_app(0);
+    app.show_help = true;
+    let area = Rect::new(0, 0, 160, 120);
+
+    let text = buffer_text(&render_to_buffer(&app, area));
+    assert!(text.contains("Shift+M"));
+    assert!(text.contains("Ctrl+d"));
+    assert!(!text.contains("F10") && !text.contains("(unbound)"));
+
+    // Remapped keys replace the defaults, an action without keys says so
+    let entries = default_bindings().into_iter().map(|(action, keys)| {
+        let keys = match action {
+            Action::Quit => vec!["F10".to_string()],
+            Action::Maximize => Vec::new(),
+            _ => keys.into_iter().map(str::to_string).collect(),
+        };
+        (action, keys)
+    }).collect();
+    app.keybindings = KeyBindings::from_entries(entries);
+
+    let text = buffer_text(&render_to_buffer(&app, area));
+    assert!(text.contains("F10"));
+    assert!(!text.contains("Shift+M"));
+    assert!(text.contains("(unbound)"));
+}
+
+#[test]
+fn footer_hints_follow_the_focused_view() {
+    use project::keybindings::{Action, KeyBindings};
+    use project::view_router::hint_text;
+    let mut app = test_app(10);
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("#1 Empty Pane: Enter to select view."), "{}", text);
+
+    app.tiling.set_current_view(ViewType::Polar);
+    app.tiling.split(Direction::Horizontal);
+    app.tiling.set_current_view(ViewType::Constellation);
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("#2 I/Q Constellation: Left/Right step, +/- persistence"));
+
+    app.tiling.focused_pane_id = 1;
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("w/a/s/d rotate, +/- depth, k projection"));
+
+    // Remapped keys show up as they are; unbound actions drop their hint
+    app.keybindings = KeyBindings::from_entries(vec![
+        (Action::CameraUp, vec!["i".to_string()]),
+        (Action::CameraLeft, vec!["j".to_string()]),
+        (Action::CameraDown, vec!["k".to_string()]),
+        (Action::CameraRight, vec!["l".to_string()]),
+    ]);
+    assert_eq!(hint_text(&app, ViewType::Polar), "i/j/k/l rotate");
+}
+
+#[test]
+fn header_badge_shows_the_chosen_metric_with_threshold_colors() {
+    use project::app::HeaderMetric;
+    use ratatui::style::Color;
+    const WIDE: Rect = Rect { x: 0, y: 0, width: 240, height: 40 };
+
+    // Off by default, "—" before any CSI
+    let mut app = test_app(0);
+    assert_eq!(app.header_badge(), None);
+    app.header_metric = HeaderMetric::Rssi;
+    assert_eq!(app.header_badge().map(|(text, _)| text), Some("RSSI —".to_string()));
+    assert!(buffer_text(&render_to_buffer(&app, WIDE)).contains("RSSI —"));
+
+    let mut app = test_app(10);
+    app.header_metric = HeaderMetric::Rssi;
+    app.current_stats.rssi = -50;
+    assert_eq!(app.header_badge(), Some(("RSSI -50 dBm".to_string(), Color::Green)));
+    app.current_stats.rssi = -70;
+    assert_eq!(app.header_badge().map(|(_, color)| color), Some(Color::Yellow));
+    app.current_stats.rssi = -80;
+    assert_eq!(app.header_badge().map(|(_, color)| color), Some(Color::Red));
+
+    app.header_metric = HeaderMetric::Snr;
+    app.current_stats.snr = 5;
+    assert_eq!(app.header_badge(), Some(("SNR 5 dB".to_string(), Color::Red)));
+
+    app.header_metric = HeaderMetric::Pps;
+    app.current_stats.pps = 0;
+    assert_eq!(app.header_badge(), Some(("PPS 0".to_string(), Color::Red)));
+    app.current_stats.pps = 500;
+    assert_eq!(app.header_badge().map(|(_, color)| color), Some(Color::Green));
+
+    // High motion is the alarm
+    app.header_metric = HeaderMetric::Motion;
+    app.motion_energy = app.gauge_ranges.motion_threshold;
+    assert_eq!(app.header_badge().map(|(_, color)| color), Some(Color::Red));
+    app.motion_energy = 0.0;
+    assert_eq!(app.header_badge().map(|(_, color)| color), Some(Color::Green));
+    assert!(buffer_text(&render_to_buffer(&app, WIDE)).contains("Motion 0"));
+
+    assert_eq!(HeaderMetric::Motion.next(), HeaderMetric::Off);
+}
+
+#[test]
+fn cli_grid_layout_draws_every_pane() {
+    let tiling = TilingManager::grid(2, 2, &[ViewType::Dashboard, ViewType::Phase, ViewType::Waterfall]);
+    let mut app = test_app(10);
+    app.apply_layout(tiling);
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains("#3") && text.contains("#4"), "grid panes missing from:\n{}", text);
+}
+
+#[test]
+fn smooth_spectrogram_renders_at_any_size() {
+    let mut app = test_app(300);
+    app.tiling.set_current_view(ViewType::Spectrogram);
+    app.get_pane_state_mut(1).smooth_heatmap = true;
+    for area in [AREA, Rect::new(0, 0, 12, 4), Rect::new(0, 0, 400, 120)] {
+        let text = buffer_text(&render_to_buffer(&app, area));
+        assert!(area.width < 200 || text.contains("Smooth: On"));
+    }
+}
+
+#[test]
+fn flattening_shows_in_header_and_footers() {
+    let mut app = test_app(10);
+    app.flatten_subcarriers = true;
+    app.subcarrier_mean.update(app.history[9].csi.as_ref().unwrap());
+    app.tiling.set_current_view(ViewType::Waterfall);
+    let text = buffer_text(&render_to_buffer(&app, AREA));
+    assert!(text.contains(" FLAT "));
+    assert!(text.contains("| Flat "));
+
+    // The means describe the primary source's channel, not the overlay's
+    let mut overlay = project::app::SourceStream::new("baseline");
+    overlay.history.push(app.history[0].clone());
+    app.overlay = Some(overlay);
+    let pane = app.tiling.focused_pane_id;
+    app.get_pane_state_mut(pane).overlay_source = true;
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("| Flat: n/a "));
+}
+
+#[test]
+fn amplitude_clamp_shows_in_the_header() {
+    use project::frontend::smoothing::AmplitudeClamp;
+    let mut app = test_app(30);
+    app.amplitude_clamp = AmplitudeClamp::P99;
+    assert!(buffer_text(&render_to_buffer(&app, AREA)).contains("CLAMP P99"));
+}
+
+#[test]
+fn header_hotkeys_follow_the_keybindings() {
+    use project::keybindings::{Action, KeyBindings};
+    // Wide enough that the centered header isn't clipped
+    const WIDE: Rect = Rect { x: 0, y: 0, width: 240, height: 40 };
+    let mut app = test_app(10);
+    app.tiling.set_current_view(ViewType::Polar);
+    app.enter_fullscreen(1, false);
+    let text = buffer_text(&render_to_buffer(&app, WIDE));
+    assert!(text.contains("[Space] Exit Fullscreen | [Left/Right] Playback"), "{}", text);
+
+    // Remapped keys show up as they are; unbound actions drop out
+    app.keybindings = KeyBindings::from_entries(vec![
+        (Action::ExitFullscreen, vec!["x".to_string()]),
+        (Action::Quit, vec!["Ctrl+q".to_string()]),
+    ]);
+    let text = buffer_text(&render_to_buffer(&app, WIDE));
+    assert!(text.contains("[x] Exit Fullscreen | [Ctrl+q] Quit"), "{}", text);
+}